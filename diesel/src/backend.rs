@@ -1,6 +1,12 @@
 use query_builder::QueryBuilder;
 use query_builder::pg::PgQueryBuilder;
 use query_builder::debug::DebugQueryBuilder;
+#[cfg(feature = "sqlite")]
+use query_builder::sqlite::SqliteQueryBuilder;
+#[cfg(feature = "sqlite")]
+use connection::sqlite::SqliteValue;
+#[cfg(feature = "mysql")]
+use query_builder::mysql::MysqlQueryBuilder;
 use types::{self, HasSqlType};
 
 pub trait Backend where
@@ -28,6 +34,14 @@ pub trait TypeMetadata {
 
 pub trait SupportsReturningClause {}
 
+/// How the queries combined by `UNION`, `INTERSECT` or `EXCEPT` are written.
+/// SQLite doesn't allow them to be wrapped in parentheses.
+pub trait CombinationSyntax {
+    fn parenthesize_combined_queries() -> bool {
+        true
+    }
+}
+
 pub struct Debug;
 
 impl Backend for Debug {
@@ -41,6 +55,8 @@ impl TypeMetadata for Debug {
 
 impl SupportsReturningClause for Debug {}
 
+impl CombinationSyntax for Debug {}
+
 pub struct Pg;
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -59,3 +75,101 @@ impl TypeMetadata for Pg {
 }
 
 impl SupportsReturningClause for Pg {}
+
+#[doc(hidden)]
+pub fn oid_of<ST>() -> u32 where Pg: HasSqlType<ST> {
+    <Pg as HasSqlType<ST>>::metadata().oid
+}
+
+#[doc(hidden)]
+pub fn array_oid_of<ST>() -> u32 where Pg: HasSqlType<ST> {
+    <Pg as HasSqlType<ST>>::metadata().array_oid
+}
+
+impl CombinationSyntax for Pg {}
+
+#[cfg(feature = "sqlite")]
+pub struct Sqlite;
+
+/// The storage class a bind parameter will be sent to SQLite as. SQLite is
+/// dynamically typed, so this only determines which `sqlite3_bind_*` function
+/// is used for a given value.
+#[cfg(feature = "sqlite")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqliteType {
+    Binary,
+    Text,
+    Float,
+    Double,
+    SmallInt,
+    Integer,
+    Long,
+}
+
+#[cfg(feature = "sqlite")]
+impl Default for SqliteType {
+    // Only used for composite types such as tuples, which are never bound
+    // directly.
+    fn default() -> Self {
+        SqliteType::Binary
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl Backend for Sqlite {
+    type QueryBuilder = SqliteQueryBuilder;
+    type RawValue = SqliteValue;
+}
+
+#[cfg(feature = "sqlite")]
+impl TypeMetadata for Sqlite {
+    type TypeMetadata = SqliteType;
+}
+
+#[cfg(feature = "sqlite")]
+impl CombinationSyntax for Sqlite {
+    fn parenthesize_combined_queries() -> bool {
+        false
+    }
+}
+
+#[cfg(feature = "mysql")]
+pub struct Mysql;
+
+/// The type a bind parameter will be sent to MySQL as. Values are sent using
+/// the binary protocol of server-side prepared statements.
+#[cfg(feature = "mysql")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MysqlType {
+    Tiny,
+    Short,
+    Long,
+    LongLong,
+    Float,
+    Double,
+    String,
+    Blob,
+}
+
+#[cfg(feature = "mysql")]
+impl Default for MysqlType {
+    // Only used for composite types such as tuples, which are never bound
+    // directly.
+    fn default() -> Self {
+        MysqlType::Blob
+    }
+}
+
+#[cfg(feature = "mysql")]
+impl Backend for Mysql {
+    type QueryBuilder = MysqlQueryBuilder;
+    type RawValue = [u8];
+}
+
+#[cfg(feature = "mysql")]
+impl TypeMetadata for Mysql {
+    type TypeMetadata = MysqlType;
+}
+
+#[cfg(feature = "mysql")]
+impl CombinationSyntax for Mysql {}
@@ -1,8 +1,20 @@
 extern crate libc;
 
+#[cfg(feature = "mysql")]
+pub mod mysql;
+mod percent_encoding;
 pub mod pg;
+pub mod pool;
+mod retry;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
 
+#[cfg(feature = "mysql")]
+pub use self::mysql::MysqlConnection;
 pub use self::pg::PgConnection;
+pub use self::retry::RetryPolicy;
+#[cfg(feature = "sqlite")]
+pub use self::sqlite::SqliteConnection;
 
 use backend::Backend;
 use query_builder::{AsQuery, QueryFragment};
@@ -47,6 +59,22 @@ pub trait Connection: SimpleConnection + Sized {
         }
     }
 
+    /// Executes the given function inside of a database transaction like
+    /// `transaction`, running it again in a new transaction if it fails with
+    /// a serialization failure or deadlock. These errors are expected when
+    /// using `SERIALIZABLE` transactions, and the transaction will usually
+    /// succeed when retried. `policy` controls how many attempts are made,
+    /// and how long to wait in between them.
+    ///
+    /// Retrying only happens for the outermost transaction. When called inside
+    /// of another transaction, this behaves like `transaction`, and the error
+    /// is returned so that the outer transaction can be retried instead.
+    fn transaction_with_retry<T, F>(&self, policy: RetryPolicy, mut f: F) -> TransactionResult<T, Error> where
+        F: FnMut() -> Result<T, Error>,
+    {
+        retry::retry_transaction(policy, self.get_transaction_depth(), || self.transaction(&mut f))
+    }
+
     /// Creates a transaction that will never be committed. This is useful for
     /// tests. Panics if called while inside of a transaction.
     fn begin_test_transaction(&self) -> QueryResult<()> {
@@ -88,6 +116,23 @@ pub trait Connection: SimpleConnection + Sized {
         Self::Backend: HasSqlType<T::SqlType>,
         U: Queryable<T::SqlType, Self::Backend>;
 
+    /// Like `query_all`, but deserialization happens as the iterator is
+    /// advanced, and errors which occur while reading rows are returned from
+    /// the iterator. Backends which can read rows from the server one at a
+    /// time should override this, as the default implementation loads every
+    /// row before returning. Dropping the iterator early may block while the
+    /// rest of the results are read and discarded.
+    #[doc(hidden)]
+    fn stream_query<'a, T, U: 'a>(&'a self, source: T) -> QueryResult<Box<Iterator<Item=QueryResult<U>> + 'a>> where
+        T: AsQuery,
+        T::Query: QueryFragment<Self::Backend>,
+        Self::Backend: HasSqlType<T::SqlType>,
+        U: Queryable<T::SqlType, Self::Backend>,
+    {
+        self.query_all(source)
+            .map(|rows| Box::new(rows.map(Ok)) as Box<Iterator<Item=QueryResult<U>>>)
+    }
+
     #[doc(hidden)]
     fn execute_returning_count<T>(&self, source: &T) -> QueryResult<usize> where
         T: QueryFragment<Self::Backend>;
@@ -0,0 +1,211 @@
+extern crate mysqlclient_sys as ffi;
+extern crate libc;
+
+use std::mem;
+
+use backend::{Mysql, MysqlType};
+use row::Row;
+
+/// The buffers MySQL reads bind parameters from, or writes the columns of a
+/// fetched row to. The `MYSQL_BIND` structs handed to the client library
+/// point into this struct, so the buffers must not be moved or resized while
+/// they are bound.
+pub struct Binds {
+    data: Vec<BindData>,
+}
+
+impl Binds {
+    pub fn from_input_data(input: Vec<(MysqlType, Option<Vec<u8>>)>) -> Self {
+        let data = input.into_iter()
+            .map(|(tpe, bytes)| BindData::for_input(tpe, bytes))
+            .collect();
+        Binds { data: data }
+    }
+
+    pub fn from_output_types(types: Vec<ffi::enum_field_types>) -> Self {
+        let data = types.into_iter().map(BindData::for_output).collect();
+        Binds { data: data }
+    }
+
+    pub fn mysql_binds(&mut self) -> Vec<ffi::MYSQL_BIND> {
+        self.data.iter_mut().map(BindData::mysql_bind).collect()
+    }
+
+    pub fn mysql_bind_for_column(&mut self, idx: usize) -> ffi::MYSQL_BIND {
+        self.data[idx].mysql_bind()
+    }
+
+    /// Returns the index of every column which didn't fit into its buffer
+    /// when the row was fetched, after growing those buffers to the size
+    /// reported by MySQL. The values need to be fetched again with
+    /// `mysql_stmt_fetch_column`.
+    pub fn grow_truncated_buffers(&mut self) -> Vec<usize> {
+        self.data.iter_mut().enumerate()
+            .filter(|&(_, ref data)| data.is_truncated != 0)
+            .map(|(idx, data)| {
+                let length = data.length as usize;
+                data.bytes.resize(length, 0);
+                data.is_truncated = 0;
+                idx
+            })
+            .collect()
+    }
+
+    /// MySQL writes numbers using the byte order of the host, while the
+    /// `FromSql` impls shared with PG expect them in network byte order.
+    pub fn convert_fetched_numbers_to_big_endian(&mut self) {
+        for data in self.data.iter_mut().filter(|data| data.is_null == 0) {
+            if fixed_size_of(data.tpe).is_some() {
+                swap_to_or_from_big_endian(&mut data.bytes);
+            }
+        }
+    }
+
+    fn field_data(&self, idx: usize) -> Option<&[u8]> {
+        let data = &self.data[idx];
+        if data.is_null != 0 {
+            None
+        } else if fixed_size_of(data.tpe).is_some() {
+            Some(&data.bytes)
+        } else {
+            Some(&data.bytes[..data.length as usize])
+        }
+    }
+}
+
+struct BindData {
+    tpe: ffi::enum_field_types,
+    bytes: Vec<u8>,
+    length: libc::c_ulong,
+    is_null: ffi::my_bool,
+    is_truncated: ffi::my_bool,
+}
+
+impl BindData {
+    fn for_input(tpe: MysqlType, data: Option<Vec<u8>>) -> Self {
+        let tpe = field_type_for(tpe);
+        let is_null = if data.is_none() { 1 } else { 0 };
+        let mut bytes = data.unwrap_or_else(Vec::new);
+        if fixed_size_of(tpe).is_some() {
+            swap_to_or_from_big_endian(&mut bytes);
+        }
+        let length = bytes.len() as libc::c_ulong;
+
+        BindData {
+            tpe: tpe,
+            bytes: bytes,
+            length: length,
+            is_null: is_null,
+            is_truncated: 0,
+        }
+    }
+
+    fn for_output(tpe: ffi::enum_field_types) -> Self {
+        use self::ffi::enum_field_types::*;
+
+        let tpe = match tpe {
+            MYSQL_TYPE_TINY => MYSQL_TYPE_TINY,
+            MYSQL_TYPE_SHORT | MYSQL_TYPE_YEAR => MYSQL_TYPE_SHORT,
+            MYSQL_TYPE_LONG | MYSQL_TYPE_INT24 => MYSQL_TYPE_LONG,
+            MYSQL_TYPE_LONGLONG => MYSQL_TYPE_LONGLONG,
+            MYSQL_TYPE_FLOAT => MYSQL_TYPE_FLOAT,
+            MYSQL_TYPE_DOUBLE => MYSQL_TYPE_DOUBLE,
+            MYSQL_TYPE_TINY_BLOB |
+            MYSQL_TYPE_MEDIUM_BLOB |
+            MYSQL_TYPE_LONG_BLOB |
+            MYSQL_TYPE_BLOB => MYSQL_TYPE_BLOB,
+            // Anything else (decimals, dates, strings, ...) is asked for as
+            // text, and converted by MySQL.
+            _ => MYSQL_TYPE_STRING,
+        };
+        let size = fixed_size_of(tpe).unwrap_or(DEFAULT_VARIABLE_BUFFER_SIZE);
+
+        BindData {
+            tpe: tpe,
+            bytes: vec![0; size],
+            length: 0,
+            is_null: 0,
+            is_truncated: 0,
+        }
+    }
+
+    fn mysql_bind(&mut self) -> ffi::MYSQL_BIND {
+        let mut bind: ffi::MYSQL_BIND = unsafe { mem::zeroed() };
+        bind.buffer_type = self.tpe;
+        bind.buffer = self.bytes.as_mut_ptr() as *mut libc::c_void;
+        bind.buffer_length = self.bytes.len() as libc::c_ulong;
+        bind.length = &mut self.length;
+        bind.is_null = &mut self.is_null;
+        bind.error = &mut self.is_truncated;
+        bind
+    }
+}
+
+const DEFAULT_VARIABLE_BUFFER_SIZE: usize = 128;
+
+fn field_type_for(tpe: MysqlType) -> ffi::enum_field_types {
+    use self::ffi::enum_field_types::*;
+
+    match tpe {
+        MysqlType::Tiny => MYSQL_TYPE_TINY,
+        MysqlType::Short => MYSQL_TYPE_SHORT,
+        MysqlType::Long => MYSQL_TYPE_LONG,
+        MysqlType::LongLong => MYSQL_TYPE_LONGLONG,
+        MysqlType::Float => MYSQL_TYPE_FLOAT,
+        MysqlType::Double => MYSQL_TYPE_DOUBLE,
+        MysqlType::String => MYSQL_TYPE_STRING,
+        MysqlType::Blob => MYSQL_TYPE_BLOB,
+    }
+}
+
+fn fixed_size_of(tpe: ffi::enum_field_types) -> Option<usize> {
+    use self::ffi::enum_field_types::*;
+
+    match tpe {
+        MYSQL_TYPE_TINY => Some(1),
+        MYSQL_TYPE_SHORT => Some(2),
+        MYSQL_TYPE_LONG | MYSQL_TYPE_FLOAT => Some(4),
+        MYSQL_TYPE_LONGLONG | MYSQL_TYPE_DOUBLE => Some(8),
+        _ => None,
+    }
+}
+
+fn swap_to_or_from_big_endian(bytes: &mut [u8]) {
+    if cfg!(target_endian = "little") {
+        bytes.reverse();
+    }
+}
+
+pub struct MysqlRow<'a> {
+    col_idx: usize,
+    binds: &'a Binds,
+}
+
+impl<'a> MysqlRow<'a> {
+    pub fn new(binds: &'a Binds) -> Self {
+        MysqlRow {
+            col_idx: 0,
+            binds: binds,
+        }
+    }
+}
+
+impl<'a> Row<Mysql> for MysqlRow<'a> {
+    fn take(&mut self) -> Option<&[u8]> {
+        let current_idx = self.col_idx;
+        self.col_idx += 1;
+        self.binds.field_data(current_idx)
+    }
+
+    fn next_is_null(&self, count: usize) -> bool {
+        (0..count).all(|i| self.binds.field_data(self.col_idx + i).is_none())
+    }
+}
+
+#[test]
+fn numbers_are_bound_in_native_byte_order() {
+    let mut binds = Binds::from_input_data(vec![(MysqlType::Long, Some(vec![0, 0, 0, 1]))]);
+    let bind = binds.mysql_bind_for_column(0);
+    let bound_value = unsafe { *(bind.buffer as *const i32) };
+    assert_eq!(1, bound_value);
+}
@@ -0,0 +1,149 @@
+extern crate mysqlclient_sys as ffi;
+extern crate libc;
+
+use std::ffi::CStr;
+use std::ptr;
+use std::sync::{Once, ONCE_INIT};
+
+use result::*;
+use super::url::ConnectionOptions;
+
+pub struct RawConnection {
+    pub internal_connection: *mut ffi::MYSQL,
+}
+
+impl RawConnection {
+    pub fn establish(database_url: &str) -> ConnectionResult<Self> {
+        let options = try!(ConnectionOptions::parse(database_url));
+        perform_library_initialization();
+        let conn_pointer = unsafe { ffi::mysql_init(ptr::null_mut()) };
+        if conn_pointer.is_null() {
+            return Err(ConnectionError::BadConnection(
+                "Out of memory while initializing the MySQL client".into()));
+        }
+        let connection = RawConnection {
+            internal_connection: conn_pointer,
+        };
+
+        let charset_result = unsafe {
+            ffi::mysql_options(
+                conn_pointer,
+                ffi::mysql_option::MYSQL_SET_CHARSET_NAME,
+                b"utf8mb4\0".as_ptr() as *const libc::c_void,
+            )
+        };
+        if charset_result != 0 {
+            return Err(ConnectionError::BadConnection(connection.last_error_message()));
+        }
+
+        let connect_result = unsafe {
+            ffi::mysql_real_connect(
+                conn_pointer,
+                options.host.as_ref().map(|x| x.as_ptr()).unwrap_or(ptr::null()),
+                options.user.as_ptr(),
+                options.password.as_ref().map(|x| x.as_ptr()).unwrap_or(ptr::null()),
+                options.database.as_ref().map(|x| x.as_ptr()).unwrap_or(ptr::null()),
+                options.port.unwrap_or(0) as libc::c_uint,
+                ptr::null(),
+                0,
+            )
+        };
+        if connect_result.is_null() {
+            return Err(ConnectionError::BadConnection(connection.last_error_message()));
+        }
+
+        let multi_statements_result = unsafe {
+            ffi::mysql_set_server_option(
+                conn_pointer,
+                ffi::enum_mysql_set_option::MYSQL_OPTION_MULTI_STATEMENTS_ON,
+            )
+        };
+        if multi_statements_result != 0 {
+            return Err(ConnectionError::BadConnection(connection.last_error_message()));
+        }
+
+        Ok(connection)
+    }
+
+    /// Runs one or more `;` separated statements, discarding any results
+    /// they return.
+    pub fn exec(&self, query: &str) -> QueryResult<()> {
+        let query_result = unsafe {
+            ffi::mysql_real_query(
+                self.internal_connection,
+                query.as_ptr() as *const libc::c_char,
+                query.len() as libc::c_ulong,
+            )
+        };
+        if query_result != 0 {
+            return Err(self.last_error());
+        }
+        self.flush_pending_results()
+    }
+
+    pub fn rows_affected_by_last_query(&self) -> usize {
+        unsafe { ffi::mysql_affected_rows(self.internal_connection) as usize }
+    }
+
+    pub fn last_error_message(&self) -> String {
+        unsafe {
+            let error_ptr = ffi::mysql_error(self.internal_connection);
+            CStr::from_ptr(error_ptr).to_string_lossy().into_owned()
+        }
+    }
+
+    pub fn last_error(&self) -> Error {
+        let errno = unsafe { ffi::mysql_errno(self.internal_connection) };
+        Error::DatabaseError(error_kind(errno), Box::new(self.last_error_message()))
+    }
+
+    /// Every result of a multi statement query has to be read before the
+    /// connection can be used again. An error in any of the statements is
+    /// only reported once we get to it.
+    fn flush_pending_results(&self) -> QueryResult<()> {
+        loop {
+            unsafe {
+                let result = ffi::mysql_store_result(self.internal_connection);
+                if !result.is_null() {
+                    ffi::mysql_free_result(result);
+                }
+            }
+            match unsafe { ffi::mysql_next_result(self.internal_connection) } {
+                0 => continue,
+                -1 => return Ok(()),
+                _ => return Err(self.last_error()),
+            }
+        }
+    }
+}
+
+impl Drop for RawConnection {
+    fn drop(&mut self) {
+        unsafe { ffi::mysql_close(self.internal_connection) };
+    }
+}
+
+/// Maps MySQL's error numbers to the kinds we identify. The SQLSTATE isn't
+/// specific enough for this, as MySQL reports most constraint violations as
+/// `23000`.
+pub fn error_kind(errno: libc::c_uint) -> DatabaseErrorKind {
+    match errno {
+        1062 | 1586 => DatabaseErrorKind::UniqueViolation,
+        1451 | 1452 => DatabaseErrorKind::ForeignKeyViolation,
+        1048 => DatabaseErrorKind::NotNullViolation,
+        1213 => DatabaseErrorKind::Deadlock,
+        1317 | 3024 => DatabaseErrorKind::QueryCanceled,
+        _ => DatabaseErrorKind::__Unknown,
+    }
+}
+
+static MYSQL_THREAD_UNSAFE_INIT: Once = ONCE_INIT;
+
+/// `mysql_init` is not thread safe the first time it is called, as it
+/// initializes the client library. Doing that explicitly before opening any
+/// connections lets us establish them from multiple threads.
+fn perform_library_initialization() {
+    MYSQL_THREAD_UNSAFE_INIT.call_once(|| unsafe {
+        ffi::mysql_server_init(0, ptr::null_mut(), ptr::null_mut());
+    });
+}
@@ -0,0 +1,43 @@
+use std::marker::PhantomData;
+
+use backend::Mysql;
+use query_source::Queryable;
+use result::QueryResult;
+use row::deserialize_row;
+use super::bind::{Binds, MysqlRow};
+use super::stmt::Statement;
+use types::HasSqlType;
+
+/// The result set is buffered on the client, but each row is only
+/// deserialized as the iterator is advanced.
+pub struct StatementIterator<ST, T> {
+    stmt: Statement,
+    output_binds: Binds,
+    _marker: PhantomData<(ST, T)>,
+}
+
+impl<ST, T> StatementIterator<ST, T> {
+    pub fn new(stmt: Statement) -> QueryResult<Self> {
+        let output_binds = try!(stmt.result_binds());
+        Ok(StatementIterator {
+            stmt: stmt,
+            output_binds: output_binds,
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl<ST, T> Iterator for StatementIterator<ST, T> where
+    Mysql: HasSqlType<ST>,
+    T: Queryable<ST, Mysql>,
+{
+    type Item = QueryResult<T>;
+
+    fn next(&mut self) -> Option<QueryResult<T>> {
+        match self.stmt.fetch(&mut self.output_binds) {
+            Ok(true) => Some(deserialize_row(&mut MysqlRow::new(&self.output_binds))),
+            Ok(false) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
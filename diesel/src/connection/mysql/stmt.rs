@@ -0,0 +1,157 @@
+extern crate mysqlclient_sys as ffi;
+extern crate libc;
+
+use std::ffi::CStr;
+use std::rc::Rc;
+
+use backend::MysqlType;
+use result::*;
+use super::bind::Binds;
+use super::raw::{self, RawConnection};
+
+const MYSQL_NO_DATA: libc::c_int = 100;
+const MYSQL_DATA_TRUNCATED: libc::c_int = 101;
+
+pub struct Statement {
+    // Only held so the connection outlives its prepared statements
+    _raw_connection: Rc<RawConnection>,
+    stmt: *mut ffi::MYSQL_STMT,
+    input_binds: Option<Binds>,
+}
+
+impl Statement {
+    pub fn prepare(raw_connection: &Rc<RawConnection>, sql: &str) -> QueryResult<Self> {
+        let stmt = unsafe { ffi::mysql_stmt_init(raw_connection.internal_connection) };
+        if stmt.is_null() {
+            return Err(raw_connection.last_error());
+        }
+        let statement = Statement {
+            _raw_connection: raw_connection.clone(),
+            stmt: stmt,
+            input_binds: None,
+        };
+
+        let prepare_result = unsafe {
+            ffi::mysql_stmt_prepare(
+                stmt,
+                sql.as_ptr() as *const libc::c_char,
+                sql.len() as libc::c_ulong,
+            )
+        };
+        if prepare_result == 0 {
+            Ok(statement)
+        } else {
+            Err(statement.last_error())
+        }
+    }
+
+    pub fn bind(&mut self, binds: Vec<(MysqlType, Option<Vec<u8>>)>) -> QueryResult<()> {
+        let mut input_binds = Binds::from_input_data(binds);
+        let mut mysql_binds = input_binds.mysql_binds();
+        let bind_result = unsafe {
+            ffi::mysql_stmt_bind_param(self.stmt, mysql_binds.as_mut_ptr())
+        };
+        // The buffers have to outlive the call to `execute`
+        self.input_binds = Some(input_binds);
+        if bind_result == 0 {
+            Ok(())
+        } else {
+            Err(self.last_error())
+        }
+    }
+
+    pub fn execute(&self) -> QueryResult<()> {
+        if unsafe { ffi::mysql_stmt_execute(self.stmt) } == 0 {
+            Ok(())
+        } else {
+            Err(self.last_error())
+        }
+    }
+
+    pub fn affected_rows(&self) -> usize {
+        unsafe { ffi::mysql_stmt_affected_rows(self.stmt) as usize }
+    }
+
+    /// Buffers the entire result set on the client, and binds buffers for
+    /// each column of the result to be fetched into.
+    pub fn result_binds(&self) -> QueryResult<Binds> {
+        if unsafe { ffi::mysql_stmt_store_result(self.stmt) } != 0 {
+            return Err(self.last_error());
+        }
+
+        let mut binds = Binds::from_output_types(self.result_types());
+        try!(self.bind_result(&mut binds));
+        Ok(binds)
+    }
+
+    /// Fetches the next row into `binds`, returning `false` once there are no
+    /// rows left.
+    pub fn fetch(&self, binds: &mut Binds) -> QueryResult<bool> {
+        match unsafe { ffi::mysql_stmt_fetch(self.stmt) } {
+            0 => {},
+            MYSQL_NO_DATA => return Ok(false),
+            MYSQL_DATA_TRUNCATED => try!(self.fetch_truncated_columns(binds)),
+            _ => return Err(self.last_error()),
+        }
+        binds.convert_fetched_numbers_to_big_endian();
+        Ok(true)
+    }
+
+    fn fetch_truncated_columns(&self, binds: &mut Binds) -> QueryResult<()> {
+        for idx in binds.grow_truncated_buffers() {
+            let mut bind = binds.mysql_bind_for_column(idx);
+            let fetch_result = unsafe {
+                ffi::mysql_stmt_fetch_column(self.stmt, &mut bind, idx as libc::c_uint, 0)
+            };
+            if fetch_result != 0 {
+                return Err(self.last_error());
+            }
+        }
+        // The buffers we grew have moved, so MySQL needs to know about them
+        // before the next row is fetched.
+        self.bind_result(binds)
+    }
+
+    fn bind_result(&self, binds: &mut Binds) -> QueryResult<()> {
+        let mut mysql_binds = binds.mysql_binds();
+        if mysql_binds.is_empty() {
+            return Ok(());
+        }
+        let bind_result = unsafe {
+            ffi::mysql_stmt_bind_result(self.stmt, mysql_binds.as_mut_ptr())
+        };
+        if bind_result == 0 {
+            Ok(())
+        } else {
+            Err(self.last_error())
+        }
+    }
+
+    fn result_types(&self) -> Vec<ffi::enum_field_types> {
+        unsafe {
+            let metadata = ffi::mysql_stmt_result_metadata(self.stmt);
+            if metadata.is_null() {
+                return Vec::new();
+            }
+            let num_fields = ffi::mysql_num_fields(metadata) as isize;
+            let fields = ffi::mysql_fetch_fields(metadata);
+            let types = (0..num_fields).map(|i| (*fields.offset(i)).type_).collect();
+            ffi::mysql_free_result(metadata);
+            types
+        }
+    }
+
+    fn last_error(&self) -> Error {
+        let (errno, message) = unsafe {
+            let message = CStr::from_ptr(ffi::mysql_stmt_error(self.stmt));
+            (ffi::mysql_stmt_errno(self.stmt), message.to_string_lossy().into_owned())
+        };
+        Error::DatabaseError(raw::error_kind(errno), Box::new(message))
+    }
+}
+
+impl Drop for Statement {
+    fn drop(&mut self) {
+        unsafe { ffi::mysql_stmt_close(self.stmt) };
+    }
+}
@@ -0,0 +1,51 @@
+/// Decodes `%XX` escapes in a component of a connection URL. Returns `None`
+/// if an escape is malformed, or the result isn't valid UTF-8.
+pub fn percent_decode(s: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut iter = s.bytes();
+    while let Some(byte) = iter.next() {
+        if byte == b'%' {
+            let hex = iter.next().and_then(hex_value)
+                .and_then(|high| iter.next().and_then(hex_value).map(|low| high * 16 + low));
+            match hex {
+                Some(decoded) => bytes.push(decoded),
+                None => return None,
+            }
+        } else {
+            bytes.push(byte);
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// Escapes everything but unreserved characters, so that `s` can be used as
+/// any component of a connection URL.
+pub fn percent_encode(s: &str) -> String {
+    let mut encoded = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        match byte {
+            b'A'...b'Z' | b'a'...b'z' | b'0'...b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char);
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+fn hex_value(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'...b'9' => Some(byte - b'0'),
+        b'a'...b'f' => Some(byte - b'a' + 10),
+        b'A'...b'F' => Some(byte - b'A' + 10),
+        _ => None,
+    }
+}
+
+#[test]
+fn percent_encoding_round_trips() {
+    let s = "p@ss/wörd %20";
+    assert_eq!("p%40ss%2Fw%C3%B6rd%20%2520", percent_encode(s));
+    assert_eq!(Some(s.to_string()), percent_decode(&percent_encode(s)));
+    assert_eq!(None, percent_decode("%2"));
+}
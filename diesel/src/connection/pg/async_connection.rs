@@ -0,0 +1,405 @@
+//! A non-blocking connection, for running queries on many connections from
+//! one thread. Only available on unix platforms, as it relies on `poll` and
+//! on the socket being exposed through `AsRawFd`.
+//!
+//! `Async` and `PgFuture` are deliberately minimal and are not an
+//! integration with any futures library or event loop. Queries are sent
+//! unnamed, bypassing `PgConnection`'s prepared statement cache.
+
+extern crate libc;
+
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ffi::CString;
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::rc::Rc;
+
+use backend::Pg;
+use connection::Connection;
+use db_result::PgResult;
+use query_builder::{AsQuery, QueryFragment};
+use query_source::Queryable;
+use result::*;
+use super::cursor::Cursor;
+use super::notification::wait_for_socket;
+use super::{BindParams, PgConnection, PgConnectionOptions};
+use types::HasSqlType;
+
+/// Whether a [`PgFuture`](struct.PgFuture.html) has completed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Async<T> {
+    /// The query has completed, with the given value.
+    Ready(T),
+    /// The query is still waiting for the server.
+    NotReady,
+}
+
+/// A connection to PostgreSQL which never blocks while running a query.
+/// Queries are built the same way as they are for `PgConnection`, and each
+/// method returns a [`PgFuture`](struct.PgFuture.html) for the query's
+/// result.
+///
+/// A connection can only run one query at a time, so queries are sent in the
+/// order they were created, each one as soon as the previous one has
+/// completed. Polling any future of a connection makes progress on all of
+/// them. To run many queries at once, use many connections, and wait for
+/// their sockets to become readable (or writable if
+/// [`wants_write`](#method.wants_write) returns `true`) with an event loop,
+/// polling their futures each time they are.
+///
+/// This connection is only available on unix platforms. `PgFuture` is not an
+/// implementation of any futures library's trait, so integrating with an
+/// event loop is left to the caller. Queries are sent without being prepared,
+/// so unlike `PgConnection` they are parsed and planned by the server every
+/// time they are run.
+///
+/// ```no_run
+/// # #[macro_use] extern crate diesel;
+/// # include!("src/doctest_setup.rs");
+/// # use diesel::connection::pg::AsyncPgConnection;
+/// #
+/// # table! {
+/// #     users {
+/// #         id -> Serial,
+/// #         name -> VarChar,
+/// #     }
+/// # }
+/// #
+/// # fn main() {
+/// # use self::users::dsl::*;
+/// let connection = AsyncPgConnection::establish("postgres://localhost/diesel_demo").unwrap();
+/// let names = connection.load::<_, String>(users.select(name));
+/// let count = connection.get_result::<_, i64>(users.count());
+/// // Both queries are running, and `wait` blocks until each completes
+/// let names = names.wait().unwrap();
+/// let count = count.wait().unwrap();
+/// # }
+/// ```
+pub struct AsyncPgConnection {
+    shared: Rc<Shared>,
+}
+
+impl AsyncPgConnection {
+    /// Establishes a new connection, which takes a connection string in the
+    /// same format as `PgConnection::establish`. This blocks until the
+    /// connection has been established.
+    pub fn establish(database_url: &str) -> ConnectionResult<Self> {
+        PgConnection::establish(database_url).and_then(AsyncPgConnection::new)
+    }
+
+    /// Establishes a new connection using the given options, like
+    /// `PgConnection::establish_with_options`.
+    pub fn establish_with_options(options: &PgConnectionOptions) -> ConnectionResult<Self> {
+        PgConnection::establish_with_options(options).and_then(AsyncPgConnection::new)
+    }
+
+    fn new(connection: PgConnection) -> ConnectionResult<Self> {
+        try!(connection.raw_connection.set_nonblocking().map_err(|e| {
+            ConnectionError::BadConnection(e.to_string())
+        }));
+        Ok(AsyncPgConnection {
+            shared: Rc::new(Shared {
+                connection: connection,
+                next_id: Cell::new(0),
+                queued: RefCell::new(VecDeque::new()),
+                in_flight: Cell::new(None),
+                in_flight_result: RefCell::new(None),
+                completed: RefCell::new(HashMap::new()),
+                abandoned: RefCell::new(HashSet::new()),
+                wants_write: Cell::new(false),
+            }),
+        })
+    }
+
+    /// Runs `source`, returning all of its rows.
+    pub fn load<T, U>(&self, source: T) -> PgFuture<Vec<U>> where
+        T: AsQuery,
+        T::Query: QueryFragment<Pg>,
+        Pg: HasSqlType<T::SqlType>,
+        U: Queryable<T::SqlType, Pg>,
+    {
+        self.query(&source.as_query(), load_rows::<T::SqlType, U>)
+    }
+
+    /// Runs `source`, returning its first row, or `NotFound` if it didn't
+    /// return any.
+    pub fn get_result<T, U>(&self, source: T) -> PgFuture<U> where
+        T: AsQuery,
+        T::Query: QueryFragment<Pg>,
+        Pg: HasSqlType<T::SqlType>,
+        U: Queryable<T::SqlType, Pg>,
+    {
+        self.query(&source.as_query(), first_row::<T::SqlType, U>)
+    }
+
+    /// Runs `source`, such as an `insert`, `update` or `delete`, returning
+    /// the number of rows affected.
+    pub fn execute<T>(&self, source: T) -> PgFuture<usize> where
+        T: QueryFragment<Pg>,
+    {
+        self.query(&source, rows_affected)
+    }
+
+    /// Runs one or more SQL statements separated by semicolons, which can't
+    /// have any bind params.
+    pub fn batch_execute(&self, query: &str) -> PgFuture<()> {
+        self.enqueue(QueuedQuery {
+            sql: query.to_string(),
+            binds: None,
+        }, ignore_result)
+    }
+
+    /// Whether some of the data for the current query couldn't be sent yet.
+    /// When this is `true`, an event loop should wait for the socket to
+    /// become writable as well as readable before polling again.
+    pub fn wants_write(&self) -> bool {
+        self.shared.wants_write.get()
+    }
+
+    fn query<T, R>(&self, source: &T, map_result: fn(PgResult) -> QueryResult<R>) -> PgFuture<R> where
+        T: QueryFragment<Pg>,
+    {
+        match self.shared.connection.prepare_query(source) {
+            Ok(query_builder) => self.enqueue(QueuedQuery {
+                sql: query_builder.sql,
+                binds: Some((query_builder.binds, query_builder.bind_types)),
+            }, map_result),
+            // Never sent, so the future completes with the error right away
+            Err(e) => {
+                let future = self.new_future(map_result);
+                self.shared.complete(future.id, Err(e));
+                future
+            }
+        }
+    }
+
+    fn enqueue<R>(&self, query: QueuedQuery, map_result: fn(PgResult) -> QueryResult<R>) -> PgFuture<R> {
+        let future = self.new_future(map_result);
+        self.shared.queued.borrow_mut().push_back((future.id, query));
+        // Sends the query right away if the connection is idle
+        self.shared.drive();
+        future
+    }
+
+    fn new_future<R>(&self, map_result: fn(PgResult) -> QueryResult<R>) -> PgFuture<R> {
+        let id = self.shared.next_id.get();
+        self.shared.next_id.set(id + 1);
+        PgFuture {
+            shared: self.shared.clone(),
+            id: id,
+            map_result: Some(map_result),
+        }
+    }
+}
+
+impl AsRawFd for AsyncPgConnection {
+    /// The connection's socket, which becomes readable when there is a
+    /// result for one of its queries.
+    fn as_raw_fd(&self) -> RawFd {
+        self.shared.connection.raw_connection.socket()
+    }
+}
+
+/// The eventual result of a query run with an
+/// [`AsyncPgConnection`](struct.AsyncPgConnection.html). Dropping it before
+/// it has completed discards the result once it arrives.
+pub struct PgFuture<T> {
+    shared: Rc<Shared>,
+    id: u64,
+    map_result: Option<fn(PgResult) -> QueryResult<T>>,
+}
+
+impl<T> PgFuture<T> {
+    /// Reads any input which has arrived on the connection without blocking,
+    /// returning the query's result if it has completed.
+    ///
+    /// # Panics
+    ///
+    /// If the future has already returned its result.
+    pub fn poll(&mut self) -> QueryResult<Async<T>> {
+        let map_result = self.map_result.expect("PgFuture polled after it completed");
+        self.shared.drive();
+        let result = self.shared.completed.borrow_mut().remove(&self.id);
+        match result {
+            Some(result) => {
+                self.map_result = None;
+                result.and_then(map_result).map(Async::Ready)
+            }
+            None => Ok(Async::NotReady),
+        }
+    }
+
+    /// Blocks until the query has completed, returning its result.
+    pub fn wait(mut self) -> QueryResult<T> {
+        loop {
+            if let Async::Ready(value) = try!(self.poll()) {
+                return Ok(value);
+            }
+            try!(self.shared.wait_for_socket());
+        }
+    }
+}
+
+impl<T> Drop for PgFuture<T> {
+    fn drop(&mut self) {
+        if self.map_result.is_some() {
+            self.shared.abandon(self.id);
+        }
+    }
+}
+
+struct QueuedQuery {
+    sql: String,
+    // `None` for statements sent with `batch_execute`
+    binds: Option<(Vec<Option<Vec<u8>>>, Vec<u32>)>,
+}
+
+struct Shared {
+    connection: PgConnection,
+    next_id: Cell<u64>,
+    queued: RefCell<VecDeque<(u64, QueuedQuery)>>,
+    in_flight: Cell<Option<u64>>,
+    in_flight_result: RefCell<Option<QueryResult<PgResult>>>,
+    completed: RefCell<HashMap<u64, QueryResult<PgResult>>>,
+    abandoned: RefCell<HashSet<u64>>,
+    wants_write: Cell<bool>,
+}
+
+impl Shared {
+    /// Sends and reads as much as possible without blocking, moving the
+    /// results of any queries which complete into `completed`.
+    fn drive(&self) {
+        loop {
+            let id = match self.in_flight.get() {
+                Some(id) => id,
+                None => match self.send_next() {
+                    Some(id) => id,
+                    None => return,
+                },
+            };
+            match self.read_results() {
+                Some(result) => {
+                    self.in_flight.set(None);
+                    self.complete(id, result);
+                }
+                None => return,
+            }
+        }
+    }
+
+    /// Sends the next queued query, returning its id. Queries which can't be
+    /// sent are completed with the error.
+    fn send_next(&self) -> Option<u64> {
+        loop {
+            let (id, query) = match self.queued.borrow_mut().pop_front() {
+                Some(next) => next,
+                None => return None,
+            };
+            match self.send(query) {
+                Ok(()) => {
+                    self.in_flight.set(Some(id));
+                    return Some(id);
+                }
+                Err(e) => self.complete(id, Err(e)),
+            }
+        }
+    }
+
+    fn send(&self, query: QueuedQuery) -> QueryResult<()> {
+        let raw_connection = &self.connection.raw_connection;
+        let sql = try!(CString::new(query.sql));
+        match query.binds {
+            Some((binds, bind_types)) => {
+                let params = BindParams::new(&binds);
+                unsafe {
+                    raw_connection.send_query_params(
+                        sql.as_ptr(),
+                        params.values.len() as libc::c_int,
+                        bind_types.as_ptr(),
+                        params.values.as_ptr(),
+                        params.lengths.as_ptr(),
+                        params.formats.as_ptr(),
+                        1,
+                    )
+                }
+            }
+            None => unsafe { raw_connection.send_query(sql.as_ptr()) },
+        }
+    }
+
+    /// Returns the result of the query in flight if it has completed.
+    fn read_results(&self) -> Option<QueryResult<PgResult>> {
+        let raw_connection = &self.connection.raw_connection;
+        let read = raw_connection.flush().and_then(|flushed| {
+            self.wants_write.set(!flushed);
+            raw_connection.consume_input()
+        });
+        if let Err(e) = read {
+            self.in_flight_result.borrow_mut().take();
+            return Some(Err(e));
+        }
+
+        while !raw_connection.is_busy() {
+            let internal_result = unsafe { raw_connection.get_result() };
+            if internal_result.is_null() {
+                let result = self.in_flight_result.borrow_mut().take();
+                return Some(result.unwrap_or_else(|| Err(raw_connection.last_error())));
+            }
+            // A batch can have many results, of which we keep the first
+            // error, or the first result if there wasn't one
+            let result = PgResult::new(&self.connection, internal_result);
+            let mut in_flight_result = self.in_flight_result.borrow_mut();
+            let keep = match *in_flight_result {
+                None => true,
+                Some(Ok(_)) => result.is_err(),
+                Some(Err(_)) => false,
+            };
+            if keep {
+                *in_flight_result = Some(result);
+            }
+        }
+        None
+    }
+
+    fn complete(&self, id: u64, result: QueryResult<PgResult>) {
+        if !self.abandoned.borrow_mut().remove(&id) {
+            self.completed.borrow_mut().insert(id, result);
+        }
+    }
+
+    fn abandon(&self, id: u64) {
+        let mut queued = self.queued.borrow_mut();
+        if let Some(index) = queued.iter().position(|&(queued_id, _)| queued_id == id) {
+            queued.remove(index);
+        } else if self.completed.borrow_mut().remove(&id).is_none() {
+            self.abandoned.borrow_mut().insert(id);
+        }
+    }
+
+    fn wait_for_socket(&self) -> QueryResult<()> {
+        let socket = self.connection.raw_connection.socket();
+        wait_for_socket(socket, self.wants_write.get(), -1).map(|_| ()).map_err(Error::IoError)
+    }
+}
+
+fn load_rows<ST, U>(result: PgResult) -> QueryResult<Vec<U>> where
+    Pg: HasSqlType<ST>,
+    U: Queryable<ST, Pg>,
+{
+    Cursor::<ST, U>::new(result).collect()
+}
+
+fn first_row<ST, U>(result: PgResult) -> QueryResult<U> where
+    Pg: HasSqlType<ST>,
+    U: Queryable<ST, Pg>,
+{
+    Cursor::<ST, U>::new(result).next().unwrap_or(Err(Error::NotFound))
+}
+
+fn rows_affected(result: PgResult) -> QueryResult<usize> {
+    Ok(result.rows_affected())
+}
+
+fn ignore_result(_: PgResult) -> QueryResult<()> {
+    Ok(())
+}
@@ -0,0 +1,68 @@
+extern crate pq_sys;
+extern crate libc;
+
+use std::ffi::CStr;
+
+use self::pq_sys::*;
+use result::{Error, QueryResult};
+
+/// Cancels the query a `PgConnection` is running, from any thread. Created
+/// with
+/// [`PgConnection::cancel_handle`](struct.PgConnection.html#method.cancel_handle).
+///
+/// The cancelled query fails with `DatabaseErrorKind::QueryCanceled`. If the
+/// connection is inside of a transaction, the whole transaction is aborted.
+///
+/// ```no_run
+/// # use diesel::connection::PgConnection;
+/// # use diesel::Connection;
+/// # use std::thread;
+/// # use std::time::Duration;
+/// # let connection = PgConnection::establish("postgres://localhost/diesel_demo").unwrap();
+/// let cancel_handle = connection.cancel_handle().unwrap();
+/// thread::spawn(move || {
+///     thread::sleep(Duration::from_secs(10));
+///     cancel_handle.cancel().unwrap();
+/// });
+/// connection.execute("SELECT pg_sleep(60)").unwrap_err();
+/// ```
+pub struct CancelHandle {
+    internal_cancel: *mut PGcancel,
+}
+
+// Not a method, so that it isn't part of the public API
+pub fn new_cancel_handle(internal_cancel: *mut PGcancel) -> CancelHandle {
+    CancelHandle {
+        internal_cancel: internal_cancel,
+    }
+}
+
+// libpq documents `PQcancel` as safe to call from any thread
+unsafe impl Send for CancelHandle {}
+unsafe impl Sync for CancelHandle {}
+
+impl CancelHandle {
+    /// Asks the server to cancel the query the connection is currently
+    /// running. Succeeding doesn't guarantee that the query was cancelled,
+    /// as it may have already completed, and if the connection isn't running
+    /// a query, nothing happens. Fails with `Error::CancelFailed` if the
+    /// cancel request couldn't be sent.
+    pub fn cancel(&self) -> QueryResult<()> {
+        let mut error_buffer = [0 as libc::c_char; 256];
+        let success = unsafe {
+            PQcancel(self.internal_cancel, error_buffer.as_mut_ptr(), error_buffer.len() as libc::c_int)
+        };
+        if success == 1 {
+            Ok(())
+        } else {
+            let message = unsafe { CStr::from_ptr(error_buffer.as_ptr()) };
+            Err(Error::CancelFailed(message.to_string_lossy().into_owned()))
+        }
+    }
+}
+
+impl Drop for CancelHandle {
+    fn drop(&mut self) {
+        unsafe { PQfreeCancel(self.internal_cancel) };
+    }
+}
@@ -0,0 +1,259 @@
+extern crate byteorder;
+
+use std::error::Error as StdError;
+use std::ffi::CString;
+use std::io::Write;
+
+use self::byteorder::{ByteOrder, BigEndian};
+
+use backend::Pg;
+use connection::Connection;
+use db_result::PgResult;
+use persistable::{Insertable, InsertableColumns, CopyInValues};
+use query_builder::{AsQuery, QueryFragment, pg_sql_with_binds};
+use query_builder::pg::PgQueryBuilder;
+use query_source::Table;
+use result::{Error, QueryResult};
+use super::PgConnection;
+
+// The signature, flags field and header extension length of the binary
+// `COPY` format
+const BINARY_HEADER: &'static [u8] = b"PGCOPY\n\xff\r\n\0\0\0\0\0\0\0\0\0";
+const BINARY_TRAILER: i16 = -1;
+// How much data we buffer before sending it to the server
+const BUFFER_SIZE: usize = 64 * 1024;
+
+/// The format data is exported in by
+/// [`PgConnection::copy_out`](struct.PgConnection.html#method.copy_out).
+/// See the PostgreSQL documentation on
+/// [`COPY`](https://www.postgresql.org/docs/current/static/sql-copy.html)
+/// for details of each format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyFormat {
+    /// Tab separated values, with `\N` for `NULL`.
+    Text,
+    /// Comma separated values, with an unquoted empty string for `NULL`.
+    Csv,
+    /// PostgreSQL's binary format.
+    Binary,
+}
+
+/// Options for [`PgConnection::copy_out`](struct.PgConnection.html#method.copy_out).
+///
+/// ```
+/// # use diesel::connection::pg::{CopyFormat, CopyOutOptions};
+/// let options = CopyOutOptions::new(CopyFormat::Csv)
+///     .header(true)
+///     .delimiter(';');
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CopyOutOptions {
+    format: CopyFormat,
+    header: bool,
+    delimiter: Option<char>,
+}
+
+impl CopyOutOptions {
+    pub fn new(format: CopyFormat) -> Self {
+        CopyOutOptions {
+            format: format,
+            header: false,
+            delimiter: None,
+        }
+    }
+
+    /// Whether the first line contains the names of the columns. Only
+    /// supported by the CSV format. Defaults to `false`.
+    pub fn header(mut self, header: bool) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// The character which separates the columns of each row, which must be
+    /// a single byte. Defaults to a tab for the text format, and a comma for
+    /// CSV. Not supported by the binary format.
+    pub fn delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = Some(delimiter);
+        self
+    }
+
+    fn to_sql(&self) -> String {
+        let mut sql = match self.format {
+            CopyFormat::Text => "FORMAT text".to_string(),
+            CopyFormat::Csv => "FORMAT csv".to_string(),
+            CopyFormat::Binary => "FORMAT binary".to_string(),
+        };
+        if self.header {
+            sql.push_str(", HEADER true");
+        }
+        if let Some(delimiter) = self.delimiter {
+            let delimiter = delimiter.to_string().replace('\'', "''");
+            sql.push_str(&format!(", DELIMITER '{}'", delimiter));
+        }
+        sql
+    }
+}
+
+pub fn copy_in<T, I, U>(conn: &PgConnection, table: T, records: I) -> QueryResult<usize> where
+    T: Table,
+    T::FromClause: QueryFragment<Pg>,
+    I: IntoIterator<Item=U>,
+    U: Insertable<T>,
+    U::Values: CopyInValues,
+{
+    let mut query_builder = PgQueryBuilder::new(&conn.raw_connection);
+    try!(build_error(table.from_clause().to_sql(&mut query_builder)));
+    let sql = format!("COPY {} ({}) FROM STDIN WITH (FORMAT binary)",
+                      query_builder.sql, U::columns().names());
+    conn.instrumentation.instrument_rows(&sql, 0, || {
+        try!(start_copy(conn, &sql));
+
+        let mut buffer = Vec::with_capacity(BUFFER_SIZE);
+        buffer.extend_from_slice(BINARY_HEADER);
+        for record in records {
+            let sent = encode_record(record, &mut buffer).and_then(|_| {
+                if buffer.len() >= BUFFER_SIZE {
+                    try!(conn.raw_connection.put_copy_data(&buffer));
+                    buffer.clear();
+                }
+                Ok(())
+            });
+            if let Err(e) = sent {
+                abort_copy(conn, &e);
+                return Err(e);
+            }
+        }
+        push_i16(&mut buffer, BINARY_TRAILER);
+        if let Err(e) = conn.raw_connection.put_copy_data(&buffer) {
+            abort_copy(conn, &e);
+            return Err(e);
+        }
+        try!(conn.raw_connection.put_copy_end(None));
+
+        let result = next_result(conn).map(|r| r.rows_affected());
+        conn.discard_remaining_results();
+        result
+    })
+}
+
+pub fn copy_out<T, W>(conn: &PgConnection, query: T, options: CopyOutOptions, mut out: W)
+    -> QueryResult<usize> where
+        T: AsQuery,
+        T::Query: QueryFragment<Pg>,
+        W: Write,
+{
+    // `COPY` can't be given bind params, so the values are written into the
+    // SQL as literals
+    let query_sql = try!(build_error(pg_sql_with_binds(&query.as_query())));
+    let sql = format!("COPY ({}) TO STDOUT WITH ({})", query_sql, options.to_sql());
+    conn.instrumentation.instrument_rows(&sql, 0, || {
+        try!(start_copy(conn, &sql));
+
+        let mut write_error = None;
+        loop {
+            match conn.raw_connection.get_copy_data() {
+                Ok(Some(data)) => if write_error.is_none() {
+                    if let Err(e) = out.write_all(&data) {
+                        // Cancelling inside of a transaction would abort it, so
+                        // the rest of the data is read and discarded instead
+                        if conn.get_transaction_depth() == 0 {
+                            conn.raw_connection.cancel();
+                        }
+                        write_error = Some(e);
+                    }
+                },
+                Ok(None) => break,
+                Err(e) => {
+                    conn.discard_remaining_results();
+                    return Err(e);
+                }
+            }
+        }
+
+        let result = next_result(conn).map(|r| r.rows_affected());
+        conn.discard_remaining_results();
+        match write_error {
+            Some(e) => Err(Error::IoError(e)),
+            None => result,
+        }
+    })
+}
+
+/// Sends a `COPY` statement, reconnecting first like any other statement.
+fn start_copy(conn: &PgConnection, sql: &str) -> QueryResult<()> {
+    let sql = try!(CString::new(sql));
+    try!(conn.reconnect_if_broken());
+    PgResult::new_copy(conn, unsafe { conn.raw_connection.exec(sql.as_ptr()) }).map(|_| ())
+}
+
+fn encode_record<T, U>(record: U, buffer: &mut Vec<u8>) -> QueryResult<()> where
+    T: Table,
+    U: Insertable<T>,
+    U::Values: CopyInValues,
+{
+    let mut fields = Vec::new();
+    try!(build_error(record.values().push_copy_fields(&mut fields)));
+
+    push_i16(buffer, fields.len() as i16);
+    for field in fields {
+        match field {
+            Some(data) => {
+                push_i32(buffer, data.len() as i32);
+                buffer.extend_from_slice(&data);
+            }
+            None => push_i32(buffer, -1),
+        }
+    }
+    Ok(())
+}
+
+/// Takes a connection out of a `COPY` which wasn't started by `copy_in` or
+/// `copy_out`, such as one run with `execute`. A `COPY ... FROM STDIN` is
+/// ended with an error so that nothing is inserted, and the data from a
+/// `COPY ... TO STDOUT` is discarded.
+pub fn end_unexpected_copy(conn: &PgConnection, copy_in: bool) {
+    if copy_in {
+        let message = CString::new("COPY can only be run with `copy_in` or `copy_out`")
+            .expect("The message has no nul bytes");
+        let _ = conn.raw_connection.put_copy_end(Some(&message));
+    } else {
+        while let Ok(Some(_)) = conn.raw_connection.get_copy_data() {}
+    }
+    conn.discard_remaining_results();
+}
+
+/// Ends the `COPY` with an error, so that none of the rows are inserted.
+fn abort_copy(conn: &PgConnection, error: &Error) {
+    let message = CString::new(error.to_string().replace('\0', ""))
+        .expect("nul bytes were removed");
+    let _ = conn.raw_connection.put_copy_end(Some(&message));
+    conn.discard_remaining_results();
+}
+
+fn next_result(conn: &PgConnection) -> QueryResult<PgResult> {
+    PgResult::new(conn, unsafe { conn.raw_connection.get_result() })
+}
+
+fn build_error<T>(result: Result<T, Box<StdError>>) -> QueryResult<T> {
+    result.map_err(|e| Error::QueryBuilderError(e.to_string().into()))
+}
+
+fn push_i16(buffer: &mut Vec<u8>, value: i16) {
+    let mut bytes = [0; 2];
+    BigEndian::write_i16(&mut bytes, value);
+    buffer.extend_from_slice(&bytes);
+}
+
+fn push_i32(buffer: &mut Vec<u8>, value: i32) {
+    let mut bytes = [0; 4];
+    BigEndian::write_i32(&mut bytes, value);
+    buffer.extend_from_slice(&bytes);
+}
+
+#[test]
+fn copy_out_options_to_sql() {
+    assert_eq!("FORMAT text", CopyOutOptions::new(CopyFormat::Text).to_sql());
+    assert_eq!("FORMAT binary", CopyOutOptions::new(CopyFormat::Binary).to_sql());
+    let options = CopyOutOptions::new(CopyFormat::Csv).header(true).delimiter('\'');
+    assert_eq!("FORMAT csv, HEADER true, DELIMITER ''''", options.to_sql());
+}
@@ -1,12 +1,14 @@
 use backend::Pg;
 use query_source::Queryable;
 use db_result::PgResult;
-use types::{HasSqlType, FromSqlRow};
+use result::QueryResult;
+use row::deserialize_row;
+use types::HasSqlType;
 
 use std::marker::PhantomData;
 
-/// The type returned by various [`Connection`](struct.Connection.html) methods.
-/// Acts as an iterator over `T`.
+/// Iterates over the rows of a `PgResult`, deserializing each one into a `T`
+/// as it is reached.
 pub struct Cursor<ST, T> {
     current_row: usize,
     db_result: PgResult,
@@ -28,20 +30,15 @@ impl<ST, T> Iterator for Cursor<ST, T> where
     Pg: HasSqlType<ST>,
     T: Queryable<ST, Pg>,
 {
-    type Item = T;
+    type Item = QueryResult<T>;
 
-    fn next(&mut self) -> Option<T> {
+    fn next(&mut self) -> Option<QueryResult<T>> {
         if self.current_row >= self.db_result.num_rows() {
             None
         } else {
             let mut row = self.db_result.get_row(self.current_row);
             self.current_row += 1;
-            let values = match T::Row::build_from_row(&mut row) {
-                Ok(value) => value,
-                Err(reason) => panic!("Error reading values {}", reason.description()),
-            };
-            let result = T::build(values);
-            Some(result)
+            Some(deserialize_row(&mut row))
         }
     }
 }
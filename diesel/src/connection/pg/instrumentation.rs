@@ -0,0 +1,105 @@
+use std::cell::{Cell, RefCell};
+use std::time::{Duration, Instant};
+
+use db_result::PgResult;
+use result::{Error, QueryResult};
+
+/// Describes a statement which was run by a `PgConnection`. Passed to the
+/// callback given to
+/// [`PgConnection::set_instrumentation`](struct.PgConnection.html#method.set_instrumentation).
+#[derive(Debug)]
+pub struct StatementEvent<'a> {
+    /// The SQL which was run. Bind params appear as `$1`, `$2`, etc.
+    pub sql: &'a str,
+    /// The number of bind params sent with the statement.
+    pub bind_count: usize,
+    /// How long the statement took, including the round trip to the server.
+    pub duration: Duration,
+    /// The number of rows returned or affected by the statement, or the
+    /// error if it failed.
+    pub result: Result<usize, &'a Error>,
+}
+
+pub type InstrumentationCallback = Box<FnMut(&StatementEvent) + Send>;
+
+pub struct Instrumentation {
+    callback: RefCell<Option<InstrumentationCallback>>,
+    replaced: Cell<bool>,
+}
+
+impl Instrumentation {
+    pub fn new() -> Self {
+        Instrumentation {
+            callback: RefCell::new(None),
+            replaced: Cell::new(false),
+        }
+    }
+
+    pub fn set(&self, callback: Option<InstrumentationCallback>) {
+        *self.callback.borrow_mut() = callback;
+        self.replaced.set(true);
+    }
+
+    pub fn instrument<F>(&self, sql: &str, bind_count: usize, run: F) -> QueryResult<PgResult> where
+        F: FnOnce() -> QueryResult<PgResult>,
+    {
+        let start = Instant::now();
+        let result = run();
+        self.report(sql, bind_count, start.elapsed(), result.as_ref().map(|r| r.rows_affected()));
+        result
+    }
+
+    /// Like `instrument`, for statements such as `COPY` which return the
+    /// number of rows they affected rather than a `PgResult`.
+    pub fn instrument_rows<F>(&self, sql: &str, bind_count: usize, run: F) -> QueryResult<usize> where
+        F: FnOnce() -> QueryResult<usize>,
+    {
+        let start = Instant::now();
+        let result = run();
+        self.report(sql, bind_count, start.elapsed(), result.as_ref().map(|&rows| rows));
+        result
+    }
+
+    /// Calls the callback for a statement which has finished running.
+    pub fn report(&self, sql: &str, bind_count: usize, duration: Duration, result: Result<usize, &Error>) {
+        // The callback is taken out while it runs, so that it can use the
+        // connection itself without the statements it runs being reported
+        let callback = self.callback.borrow_mut().take();
+        if let Some(callback) = callback {
+            self.replaced.set(false);
+            let mut running = RunningCallback {
+                instrumentation: self,
+                callback: Some(callback),
+            };
+            running.call(&StatementEvent {
+                sql: sql,
+                bind_count: bind_count,
+                duration: duration,
+                result: result,
+            });
+        }
+    }
+}
+
+/// Puts the callback back once it has returned, or if it panics, unless it
+/// replaced or removed itself.
+struct RunningCallback<'a> {
+    instrumentation: &'a Instrumentation,
+    callback: Option<InstrumentationCallback>,
+}
+
+impl<'a> RunningCallback<'a> {
+    fn call(&mut self, event: &StatementEvent) {
+        if let Some(ref mut callback) = self.callback {
+            callback(event);
+        }
+    }
+}
+
+impl<'a> Drop for RunningCallback<'a> {
+    fn drop(&mut self) {
+        if !self.instrumentation.replaced.get() {
+            *self.instrumentation.callback.borrow_mut() = self.callback.take();
+        }
+    }
+}
@@ -0,0 +1,365 @@
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+
+use backend::{Pg, oid_of};
+use connection::{Connection, SimpleConnection};
+use query_builder::{AsQuery, QueryFragment};
+use query_builder::pg::PgQueryBuilder;
+use query_source::Queryable;
+use result::*;
+use row::{Row, deserialize_row};
+use types::{self, FromSql, HasSqlType, ToSql};
+
+/// A `Connection` for unit tests, which never talks to a database. Queries
+/// are built exactly as they would be for a `PgConnection`, and recorded
+/// along with their bind params so that tests can assert on them. Each
+/// query returns the next response given to `push_rows`, `push_row_count`
+/// or `push_error`, in the order they were pushed. When there are none
+/// left, queries return no rows.
+///
+/// Statements run to begin, commit or roll back transactions are recorded,
+/// but don't use up a response.
+///
+/// ```
+/// # #[macro_use] extern crate diesel;
+/// # use diesel::*;
+/// # use diesel::connection::pg::{MockPgConnection, MockValue, RecordedQuery};
+/// #
+/// # table! {
+/// #     users {
+/// #         id -> Serial,
+/// #         name -> VarChar,
+/// #     }
+/// # }
+/// #
+/// # fn main() {
+/// # use self::users::dsl::*;
+/// let connection = MockPgConnection::new();
+/// connection.push_rows(vec![vec![1.into(), "Sean".into()]]);
+///
+/// let sean = users.filter(name.eq("Sean")).first::<(i32, String)>(&connection);
+///
+/// assert_eq!(Ok((1, "Sean".to_string())), sean);
+/// assert_eq!(vec![RecordedQuery {
+///     sql: r#"SELECT "users"."id", "users"."name" FROM "users" WHERE "users"."name" = $1 LIMIT $2"#
+///         .to_string(),
+///     binds: vec![MockValue::Text("Sean".to_string()), MockValue::BigInt(1)],
+/// }], connection.queries());
+/// # }
+/// ```
+pub struct MockPgConnection {
+    queries: RefCell<Vec<RecordedQuery>>,
+    responses: RefCell<VecDeque<Response>>,
+    transaction_depth: Cell<i32>,
+}
+
+/// A query run by a [`MockPgConnection`](struct.MockPgConnection.html).
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedQuery {
+    /// The SQL of the query. Bind params appear as `$1`, `$2`, etc.
+    pub sql: String,
+    /// The values of the query's bind params.
+    pub binds: Vec<MockValue>,
+}
+
+/// A bind param recorded by, or a column returned from, a
+/// [`MockPgConnection`](struct.MockPgConnection.html). Values can be created
+/// from the Rust types they correspond to with `into`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MockValue {
+    Null,
+    Bool(bool),
+    SmallInt(i16),
+    Integer(i32),
+    BigInt(i64),
+    Float(f32),
+    Double(f64),
+    /// A `Text` or `VarChar` value.
+    Text(String),
+    Binary(Vec<u8>),
+    /// A value of any other type, as the OID of its type and its value in
+    /// PostgreSQL's binary format.
+    Other(u32, Vec<u8>),
+}
+
+enum Response {
+    Rows(Vec<Vec<Option<Vec<u8>>>>),
+    RowCount(usize),
+    Error(Error),
+}
+
+impl MockPgConnection {
+    pub fn new() -> Self {
+        MockPgConnection {
+            queries: RefCell::new(Vec::new()),
+            responses: RefCell::new(VecDeque::new()),
+            transaction_depth: Cell::new(0),
+        }
+    }
+
+    /// Makes the next query return `rows`. When the query is run with
+    /// `execute`, it returns the number of rows instead.
+    pub fn push_rows(&self, rows: Vec<Vec<MockValue>>) {
+        let rows = rows.into_iter()
+            .map(|row| row.into_iter().map(MockValue::into_bytes).collect())
+            .collect();
+        self.responses.borrow_mut().push_back(Response::Rows(rows));
+    }
+
+    /// Makes the next query return `count` as the number of rows affected.
+    /// When the query is loaded, it returns no rows instead.
+    pub fn push_row_count(&self, count: usize) {
+        self.responses.borrow_mut().push_back(Response::RowCount(count));
+    }
+
+    /// Makes the next query fail with `error`.
+    pub fn push_error(&self, error: Error) {
+        self.responses.borrow_mut().push_back(Response::Error(error));
+    }
+
+    /// Every query which has been run on this connection, in order.
+    pub fn queries(&self) -> Vec<RecordedQuery> {
+        self.queries.borrow().clone()
+    }
+
+    fn run<T: QueryFragment<Pg>>(&self, source: &T) -> QueryResult<Response> {
+        let mut query_builder = PgQueryBuilder::without_connection();
+        try!(source.to_sql(&mut query_builder)
+            .map_err(|e| Error::QueryBuilderError(e.to_string().into())));
+        let binds = query_builder.binds.into_iter()
+            .zip(query_builder.bind_types)
+            .map(|(bind, oid)| MockValue::from_bytes(oid, bind))
+            .collect();
+        self.run_sql(query_builder.sql, binds)
+    }
+
+    fn run_sql(&self, sql: String, binds: Vec<MockValue>) -> QueryResult<Response> {
+        self.queries.borrow_mut().push(RecordedQuery {
+            sql: sql,
+            binds: binds,
+        });
+        match self.responses.borrow_mut().pop_front() {
+            Some(Response::Error(e)) => Err(e),
+            Some(response) => Ok(response),
+            None => Ok(Response::Rows(Vec::new())),
+        }
+    }
+
+    fn run_transaction_sql(&self, sql: String) {
+        self.queries.borrow_mut().push(RecordedQuery {
+            sql: sql,
+            binds: Vec::new(),
+        });
+    }
+}
+
+impl SimpleConnection for MockPgConnection {
+    fn batch_execute(&self, query: &str) -> QueryResult<()> {
+        self.run_sql(query.to_string(), Vec::new()).map(|_| ())
+    }
+}
+
+impl Connection for MockPgConnection {
+    type Backend = Pg;
+
+    /// Ignores the URL, and returns a new `MockPgConnection`.
+    fn establish(_: &str) -> ConnectionResult<Self> {
+        Ok(MockPgConnection::new())
+    }
+
+    fn execute(&self, query: &str) -> QueryResult<usize> {
+        self.run_sql(query.to_string(), Vec::new()).and_then(rows_affected)
+    }
+
+    fn query_all<'a, T, U: 'a>(&self, source: T) -> QueryResult<Box<Iterator<Item=U> + 'a>> where
+        T: AsQuery,
+        T::Query: QueryFragment<Pg>,
+        Pg: HasSqlType<T::SqlType>,
+        U: Queryable<T::SqlType, Pg>,
+    {
+        let rows = match try!(self.run(&source.as_query())) {
+            Response::Rows(rows) => rows,
+            Response::RowCount(_) => Vec::new(),
+            Response::Error(e) => return Err(e),
+        };
+        let rows = try!(rows.iter()
+            .map(|row| deserialize_row::<T::SqlType, U, Pg, _>(&mut MockRow::new(row)))
+            .collect::<QueryResult<Vec<U>>>());
+        Ok(Box::new(rows.into_iter()))
+    }
+
+    fn execute_returning_count<T>(&self, source: &T) -> QueryResult<usize> where
+        T: QueryFragment<Pg>,
+    {
+        self.run(source).and_then(rows_affected)
+    }
+
+    fn silence_notices<F: FnOnce() -> T, T>(&self, f: F) -> T {
+        f()
+    }
+
+    fn begin_transaction(&self) -> QueryResult<()> {
+        let transaction_depth = self.transaction_depth.get();
+        self.run_transaction_sql(if transaction_depth == 0 {
+            "BEGIN".to_string()
+        } else {
+            format!("SAVEPOINT diesel_savepoint_{}", transaction_depth)
+        });
+        self.transaction_depth.set(transaction_depth + 1);
+        Ok(())
+    }
+
+    fn rollback_transaction(&self) -> QueryResult<()> {
+        let transaction_depth = self.transaction_depth.get();
+        self.run_transaction_sql(if transaction_depth == 1 {
+            "ROLLBACK".to_string()
+        } else {
+            format!("ROLLBACK TO SAVEPOINT diesel_savepoint_{}", transaction_depth - 1)
+        });
+        self.transaction_depth.set(transaction_depth - 1);
+        Ok(())
+    }
+
+    fn commit_transaction(&self) -> QueryResult<()> {
+        let transaction_depth = self.transaction_depth.get();
+        self.run_transaction_sql(if transaction_depth == 1 {
+            "COMMIT".to_string()
+        } else {
+            format!("RELEASE SAVEPOINT diesel_savepoint_{}", transaction_depth - 1)
+        });
+        self.transaction_depth.set(transaction_depth - 1);
+        Ok(())
+    }
+
+    fn get_transaction_depth(&self) -> i32 {
+        self.transaction_depth.get()
+    }
+}
+
+fn rows_affected(response: Response) -> QueryResult<usize> {
+    match response {
+        Response::Rows(rows) => Ok(rows.len()),
+        Response::RowCount(count) => Ok(count),
+        Response::Error(e) => Err(e),
+    }
+}
+
+struct MockRow<'a> {
+    values: &'a [Option<Vec<u8>>],
+    col_idx: usize,
+}
+
+impl<'a> MockRow<'a> {
+    fn new(values: &'a [Option<Vec<u8>>]) -> Self {
+        MockRow {
+            values: values,
+            col_idx: 0,
+        }
+    }
+}
+
+impl<'a> Row<Pg> for MockRow<'a> {
+    fn take(&mut self) -> Option<&[u8]> {
+        let current_idx = self.col_idx;
+        self.col_idx += 1;
+        self.values.get(current_idx).and_then(|value| value.as_ref().map(|v| &**v))
+    }
+
+    fn next_is_null(&self, count: usize) -> bool {
+        (0..count).all(|i| {
+            self.values.get(self.col_idx + i).map_or(true, |value| value.is_none())
+        })
+    }
+}
+
+impl MockValue {
+    fn from_bytes(oid: u32, bytes: Option<Vec<u8>>) -> Self {
+        let bytes = match bytes {
+            Some(bytes) => bytes,
+            None => return MockValue::Null,
+        };
+        let value = if oid == oid_of::<types::Bool>() {
+            from_sql::<types::Bool, _>(&bytes).map(MockValue::Bool)
+        } else if oid == oid_of::<types::SmallInt>() {
+            from_sql::<types::SmallInt, _>(&bytes).map(MockValue::SmallInt)
+        } else if oid == oid_of::<types::Integer>() {
+            from_sql::<types::Integer, _>(&bytes).map(MockValue::Integer)
+        } else if oid == oid_of::<types::BigInt>() {
+            from_sql::<types::BigInt, _>(&bytes).map(MockValue::BigInt)
+        } else if oid == oid_of::<types::Float>() {
+            from_sql::<types::Float, _>(&bytes).map(MockValue::Float)
+        } else if oid == oid_of::<types::Double>() {
+            from_sql::<types::Double, _>(&bytes).map(MockValue::Double)
+        } else if oid == oid_of::<types::Text>() || oid == oid_of::<types::VarChar>() {
+            from_sql::<types::Text, _>(&bytes).map(MockValue::Text)
+        } else if oid == oid_of::<types::Binary>() {
+            return MockValue::Binary(bytes);
+        } else {
+            None
+        };
+        value.unwrap_or(MockValue::Other(oid, bytes))
+    }
+
+    fn into_bytes(self) -> Option<Vec<u8>> {
+        match self {
+            MockValue::Null => None,
+            MockValue::Bool(value) => to_sql::<types::Bool, _>(&value),
+            MockValue::SmallInt(value) => to_sql::<types::SmallInt, _>(&value),
+            MockValue::Integer(value) => to_sql::<types::Integer, _>(&value),
+            MockValue::BigInt(value) => to_sql::<types::BigInt, _>(&value),
+            MockValue::Float(value) => to_sql::<types::Float, _>(&value),
+            MockValue::Double(value) => to_sql::<types::Double, _>(&value),
+            MockValue::Text(value) => Some(value.into_bytes()),
+            MockValue::Binary(value) | MockValue::Other(_, value) => Some(value),
+        }
+    }
+}
+
+fn from_sql<ST, T>(bytes: &[u8]) -> Option<T> where
+    Pg: HasSqlType<ST>,
+    T: FromSql<ST, Pg>,
+{
+    T::from_sql(Some(bytes)).ok()
+}
+
+fn to_sql<ST, T>(value: &T) -> Option<Vec<u8>> where
+    Pg: HasSqlType<ST>,
+    T: ToSql<ST, Pg>,
+{
+    let mut bytes = Vec::new();
+    value.to_sql(&mut bytes).expect("Serializing a primitive value cannot fail");
+    Some(bytes)
+}
+
+macro_rules! mock_value_from {
+    ($($Source:ty => $Variant:ident),+,) => {$(
+        impl From<$Source> for MockValue {
+            fn from(value: $Source) -> Self {
+                MockValue::$Variant(value.into())
+            }
+        }
+    )+}
+}
+
+mock_value_from! {
+    bool => Bool,
+    i16 => SmallInt,
+    i32 => Integer,
+    i64 => BigInt,
+    f32 => Float,
+    f64 => Double,
+    String => Text,
+    Vec<u8> => Binary,
+}
+
+impl<'a> From<&'a str> for MockValue {
+    fn from(value: &'a str) -> Self {
+        MockValue::Text(value.to_string())
+    }
+}
+
+impl<T: Into<MockValue>> From<Option<T>> for MockValue {
+    fn from(value: Option<T>) -> Self {
+        value.map(Into::into).unwrap_or(MockValue::Null)
+    }
+}
@@ -1,42 +1,85 @@
 extern crate libc;
 
+#[cfg(unix)]
+mod async_connection;
+mod cancel;
+mod copy;
 mod cursor;
+mod instrumentation;
+mod mock;
+mod notice;
+mod notification;
+mod options;
 #[doc(hidden)]
 pub mod raw;
+mod row_stream;
+mod stmt_cache;
+mod transaction;
 
 use std::cell::Cell;
-use std::ffi::{CString, CStr};
+use std::cmp;
+use std::ffi::CString;
+use std::io;
 use std::rc::Rc;
 use std::ptr;
+use std::time::{Duration, Instant};
 
 use backend::Pg;
 use db_result::PgResult;
+use persistable::{Insertable, CopyInValues};
 use query_builder::{AsQuery, QueryFragment};
 use query_builder::pg::PgQueryBuilder;
-use query_source::Queryable;
+use query_source::{Queryable, Table};
 use result::*;
 use self::cursor::Cursor;
+use self::instrumentation::Instrumentation;
+use self::notification::{timeout_millis, wait_for_input};
 use self::raw::RawConnection;
+use self::row_stream::RowStream;
+use self::stmt_cache::StatementCache;
 use super::{SimpleConnection, Connection};
-use types::{ToSql, HasSqlType};
+#[cfg(unix)]
+pub use self::async_connection::{Async, AsyncPgConnection, PgFuture};
+pub use self::cancel::CancelHandle;
+pub use self::copy::{CopyFormat, CopyOutOptions};
+pub use self::instrumentation::StatementEvent;
+pub use self::mock::{MockPgConnection, MockValue, RecordedQuery};
+pub use self::notice::PgNotice;
+pub use self::notification::PgNotification;
+pub use self::options::{PgConnectionOptions, SslMode};
+pub use self::transaction::TransactionBuilder;
+use types::{self, ToSql, HasSqlType};
 
 /// The connection string expected by `PgConnection::establish`
 /// should be a PostgreSQL connection string, as documented at
 /// http://www.postgresql.org/docs/9.4/static/libpq-connect.html#LIBPQ-CONNSTRING
+///
+/// Queries built with the query builder are sent as named prepared
+/// statements, which are cached by the connection so that Postgres only has
+/// to parse and plan them once. Up to 256 statements are kept, after which
+/// the least recently used one is deallocated. Queries containing raw SQL
+/// from [`sql`](../expression/dsl/fn.sql.html), and inserts of more than one
+/// record, are not cached.
 pub struct PgConnection {
     raw_connection: Rc<RawConnection>,
     transaction_depth: Cell<i32>,
+    statement_cache: StatementCache,
+    instrumentation: Instrumentation,
+    auto_reconnect: Cell<bool>,
 }
 
 unsafe impl Send for PgConnection {}
 
 impl SimpleConnection for PgConnection {
     fn batch_execute(&self, query: &str) -> QueryResult<()> {
-        let query = try!(CString::new(query));
-        let inner_result = unsafe {
-            self.raw_connection.exec(query.as_ptr())
-        };
-        try!(PgResult::new(self, inner_result));
+        let c_query = try!(CString::new(query));
+        try!(self.reconnect_if_broken());
+        try!(self.instrumentation.instrument(query, 0, || {
+            let inner_result = unsafe {
+                self.raw_connection.exec(c_query.as_ptr())
+            };
+            PgResult::new(self, inner_result)
+        }));
         Ok(())
     }
 }
@@ -45,12 +88,7 @@ impl Connection for PgConnection {
     type Backend = Pg;
 
     fn establish(database_url: &str) -> ConnectionResult<PgConnection> {
-        RawConnection::establish(database_url).map(|raw_conn| {
-            PgConnection {
-                raw_connection: Rc::new(raw_conn),
-                transaction_depth: Cell::new(0),
-            }
-        })
+        RawConnection::establish(database_url).map(PgConnection::new)
     }
 
     fn execute(&self, query: &str) -> QueryResult<usize> {
@@ -64,33 +102,52 @@ impl Connection for PgConnection {
         Pg: HasSqlType<T::SqlType>,
         U: Queryable<T::SqlType, Pg>,
     {
-        let (sql, params, types) = self.prepare_query(&source.as_query());
-        self.exec_sql_params(&sql, &params, &Some(types))
-            .map(|r| Box::new(Cursor::new(r)) as Box<Iterator<Item=U>>)
+        let db_result = try!(self.execute_query(&source.as_query()));
+        let rows = try!(Cursor::new(db_result).collect::<QueryResult<Vec<U>>>());
+        Ok(Box::new(rows.into_iter()))
+    }
+
+    fn stream_query<'a, T, U: 'a>(&'a self, source: T) -> QueryResult<Box<Iterator<Item=QueryResult<U>> + 'a>> where
+        T: AsQuery,
+        T::Query: QueryFragment<Pg>,
+        T::SqlType: 'static,
+        Pg: HasSqlType<T::SqlType>,
+        U: Queryable<T::SqlType, Pg>,
+    {
+        try!(self.reconnect_if_broken());
+        let query_builder = try!(self.prepare_query(&source.as_query()));
+        let bind_count = query_builder.binds.len();
+        let start = Instant::now();
+        let sent = self.send_query(&query_builder).and_then(|_| {
+            self.raw_connection.set_single_row_mode().map_err(|e| {
+                // The query has already been sent, so its results have to be
+                // read before the connection can be used again
+                self.discard_remaining_results();
+                e
+            })
+        });
+        if let Err(e) = sent {
+            self.instrumentation.report(&query_builder.sql, bind_count, start.elapsed(), Err(&e));
+            return Err(e);
+        }
+        Ok(Box::new(RowStream::new(self, query_builder.sql, bind_count, start)))
     }
 
     fn execute_returning_count<T>(&self, source: &T) -> QueryResult<usize> where
         T: QueryFragment<Pg>,
     {
-        let (sql, params, param_types) = self.prepare_query(source);
-        self.exec_sql_params(&sql, &params, &Some(param_types))
-            .map(|r| r.rows_affected())
+        self.execute_query(source).map(|r| r.rows_affected())
     }
 
     fn silence_notices<F: FnOnce() -> T, T>(&self, f: F) -> T {
-        self.raw_connection.set_notice_processor(noop_notice_processor);
+        let handler = self.raw_connection.set_notice_handler(Box::new(|_: &PgNotice| {}));
         let result = f();
-        self.raw_connection.set_notice_processor(default_notice_processor);
+        let _ = self.raw_connection.set_notice_handler(handler);
         result
     }
 
     fn begin_transaction(&self) -> QueryResult<()> {
-        let transaction_depth = self.transaction_depth.get();
-        self.change_transaction_depth(1, if transaction_depth == 0 {
-            self.execute("BEGIN")
-        } else {
-            self.execute(&format!("SAVEPOINT diesel_savepoint_{}", transaction_depth))
-        })
+        self.begin_transaction_with("BEGIN")
     }
 
     fn rollback_transaction(&self) -> QueryResult<()> {
@@ -105,12 +162,16 @@ impl Connection for PgConnection {
 
     fn commit_transaction(&self) -> QueryResult<()> {
         let transaction_depth = self.transaction_depth.get();
-        self.change_transaction_depth(-1, if transaction_depth <= 1 {
-            self.execute("COMMIT")
+        if transaction_depth <= 1 {
+            // PostgreSQL ends the transaction even when `COMMIT` fails, for
+            // example with a serialization failure
+            let result = self.execute("COMMIT");
+            self.transaction_depth.set(0);
+            result.map(|_| ())
         } else {
-            self.execute(&format!("RELEASE SAVEPOINT diesel_savepoint_{}",
-                                  transaction_depth - 1))
-        })
+            self.change_transaction_depth(-1, self.execute(&format!(
+                "RELEASE SAVEPOINT diesel_savepoint_{}", transaction_depth - 1)))
+        }
     }
 
     fn get_transaction_depth(&self) -> i32 {
@@ -119,42 +180,421 @@ impl Connection for PgConnection {
 }
 
 impl PgConnection {
+    /// Establishes a new connection using the given options, rather than a
+    /// connection string. No escaping is needed for any of the options.
+    ///
+    /// ```no_run
+    /// # use diesel::connection::PgConnection;
+    /// # use diesel::connection::pg::PgConnectionOptions;
+    /// # use std::time::Duration;
+    /// let options = PgConnectionOptions::new()
+    ///     .host("localhost")
+    ///     .dbname("diesel_demo")
+    ///     .application_name("diesel_demo")
+    ///     .connect_timeout(Duration::from_secs(5));
+    /// let connection = PgConnection::establish_with_options(&options).unwrap();
+    /// ```
+    pub fn establish_with_options(options: &PgConnectionOptions) -> ConnectionResult<Self> {
+        RawConnection::establish_with_params(&options.keywords()).map(PgConnection::new)
+    }
+
+    /// Creates a [`TransactionBuilder`](struct.TransactionBuilder.html), used
+    /// to run a transaction with a specific isolation level, or which is read
+    /// only or deferrable.
+    pub fn build_transaction(&self) -> TransactionBuilder {
+        TransactionBuilder::new(self)
+    }
+
+    /// Inserts `records` into `table` using `COPY ... FROM STDIN` in the
+    /// binary format, returning the number of rows inserted. This is much
+    /// faster than `insert` for large numbers of rows, and isn't limited by
+    /// the maximum number of bind params in a query. The records are encoded
+    /// as they are read from the iterator, so they don't all need to be held
+    /// in memory.
+    ///
+    /// Either all of the records are inserted, or none of them are. Unlike
+    /// `insert`, `None` values are inserted as `NULL`, rather than the
+    /// column's default.
+    ///
+    /// The values are sent without being evaluated by the server, so they
+    /// must all be bound values, rather than SQL expressions.
+    pub fn copy_in<T, I, U>(&self, table: T, records: I) -> QueryResult<usize> where
+        T: Table,
+        T::FromClause: QueryFragment<Pg>,
+        I: IntoIterator<Item=U>,
+        U: Insertable<T>,
+        U::Values: CopyInValues,
+    {
+        copy::copy_in(self, table, records)
+    }
+
+    /// Runs `query` with `COPY (query) TO STDOUT`, writing the results to
+    /// `out` in the format given by `options` as they are received. Returns
+    /// the number of rows exported. If writing to `out` fails, the rest of
+    /// the data is discarded and `Error::IoError` is returned.
+    ///
+    /// The values of any bind params are inlined into the query as literals,
+    /// as `COPY` doesn't support them. This is done in the same way as
+    /// [`pg_sql_with_binds`](../query_builder/fn.pg_sql_with_binds.html),
+    /// so values of types which it can't write as literals are an error. No
+    /// other statements are run.
+    pub fn copy_out<T, W>(&self, query: T, options: CopyOutOptions, out: W) -> QueryResult<usize> where
+        T: AsQuery,
+        T::Query: QueryFragment<Pg>,
+        W: io::Write,
+    {
+        copy::copy_out(self, query, options, out)
+    }
+
+    /// Sets a callback which is called after every statement this connection
+    /// runs, with its SQL, how long it took, and its outcome. This includes
+    /// statements run by `batch_execute`, transactions, `copy_in` and
+    /// `copy_out`. Queries run with `load_iter` are reported once their last
+    /// row has been read. Preparing and deallocating the statements in the
+    /// statement cache is reported as `PREPARE` and `DEALLOCATE`. Replaces any
+    /// callback which was previously set.
+    ///
+    /// Statements run by the callback itself are not reported. If the
+    /// callback panics, it is kept, and called for the next statement.
+    ///
+    /// ```no_run
+    /// # use diesel::connection::PgConnection;
+    /// # use diesel::Connection;
+    /// # let connection = PgConnection::establish("postgres://localhost/diesel_demo").unwrap();
+    /// connection.set_instrumentation(|event| {
+    ///     if event.duration.as_secs() >= 1 {
+    ///         println!("Slow query: {}", event.sql);
+    ///     }
+    /// });
+    /// ```
+    pub fn set_instrumentation<F>(&self, callback: F) where
+        F: FnMut(&StatementEvent) + Send + 'static,
+    {
+        self.instrumentation.set(Some(Box::new(callback)));
+    }
+
+    /// Removes the callback set by `set_instrumentation`.
+    pub fn clear_instrumentation(&self) {
+        self.instrumentation.set(None);
+    }
+
+    /// Sets how long any statement run by this connection may take before
+    /// it is cancelled, failing with `DatabaseErrorKind::QueryCanceled`.
+    /// `None` removes the timeout. The timeout is rounded up to whole
+    /// milliseconds.
+    ///
+    /// Like any other setting, this is reverted if it is set inside of a
+    /// transaction which is rolled back.
+    pub fn set_statement_timeout(&self, timeout: Option<Duration>) -> QueryResult<()> {
+        let millis = timeout.map(|t| cmp::max(timeout_millis(t), 1)).unwrap_or(0);
+        self.execute(&format!("SET statement_timeout = {}", millis)).map(|_| ())
+    }
+
+    /// Runs `f` with a statement timeout of `timeout`, which applies to each
+    /// statement `f` runs. The previous timeout is restored afterwards.
+    ///
+    /// ```no_run
+    /// # use diesel::connection::PgConnection;
+    /// # use diesel::Connection;
+    /// # use std::time::Duration;
+    /// # let connection = PgConnection::establish("postgres://localhost/diesel_demo").unwrap();
+    /// let result = connection.with_statement_timeout(Duration::from_secs(5), || {
+    ///     connection.execute("SELECT pg_sleep(10)")
+    /// });
+    /// assert!(result.is_err());
+    /// ```
+    pub fn with_statement_timeout<T, F>(&self, timeout: Duration, f: F) -> QueryResult<T> where
+        F: FnOnce() -> QueryResult<T>,
+    {
+        let previous = try!(self.execute_inner("SHOW statement_timeout"));
+        let previous = String::from_utf8_lossy(previous.get(0, 0).unwrap_or(b"0")).into_owned();
+        try!(self.set_statement_timeout(Some(timeout)));
+        let result = f();
+        // If `f` failed inside of a transaction, the transaction is aborted
+        // and its rollback will restore the timeout instead
+        let restored = self.execute(&format!("SET statement_timeout = '{}'", previous.replace('\'', "''")));
+        result.and_then(|value| restored.map(|_| value))
+    }
+
+    /// Creates a [`CancelHandle`](struct.CancelHandle.html), which can be
+    /// sent to another thread to cancel the query this connection is
+    /// running.
+    ///
+    /// The handle only works for the session it was created in. If the
+    /// connection is reestablished by automatic reconnection, handles created
+    /// before then no longer cancel anything, and a new one must be created.
+    pub fn cancel_handle(&self) -> QueryResult<CancelHandle> {
+        self.raw_connection.cancel_handle()
+    }
+
+    /// Whether the connection to the server has been lost. This is only
+    /// noticed once the connection is used, so a connection which the server
+    /// has just closed isn't reported as broken until a statement fails. Use
+    /// [`ping`](#method.ping) to find out for sure.
+    pub fn is_broken(&self) -> bool {
+        self.raw_connection.is_broken()
+    }
+
+    /// Checks that the connection still works by running a trivial query.
+    /// Fails with `Error::ConnectionLost` if the connection has been lost,
+    /// unless it could be reestablished by automatic reconnection.
+    pub fn ping(&self) -> QueryResult<()> {
+        self.execute("SELECT 1").map(|_| ())
+    }
+
+    /// Enables or disables automatic reconnection, which is disabled by
+    /// default. When enabled, a connection which was lost outside of a
+    /// transaction is reestablished before the next statement is run.
+    ///
+    /// The new connection starts a new session, so settings changed with
+    /// `SET`, channels being listened on and temporary tables are gone.
+    /// Statements which were running when the connection was lost are never
+    /// retried, as they may have already been run, and fail with
+    /// `Error::ConnectionLost`. So does everything else in a transaction
+    /// which was open when the connection was lost. Any
+    /// [`CancelHandle`](struct.CancelHandle.html) created for the old
+    /// connection can't cancel queries run on the new one.
+    ///
+    /// ```no_run
+    /// # use diesel::connection::PgConnection;
+    /// # use diesel::Connection;
+    /// let connection = PgConnection::establish("postgres://localhost/diesel_demo").unwrap();
+    /// connection.set_auto_reconnect(true);
+    /// // The database server is restarted
+    /// connection.execute("SELECT 1").unwrap();
+    /// ```
+    pub fn set_auto_reconnect(&self, enabled: bool) {
+        self.auto_reconnect.set(enabled);
+    }
+
+    /// Sets a handler which is called with each notice or warning the server
+    /// sends, such as the `WARNING` sent by `COMMIT` outside of a
+    /// transaction. Replaces any handler which was previously set. By
+    /// default, notices are written to stderr.
+    ///
+    /// The handler is called from inside of libpq, so it must not unwind
+    /// into it. If the handler panics, the panic is caught and the notice is
+    /// dropped.
+    ///
+    /// ```no_run
+    /// # use diesel::connection::PgConnection;
+    /// # use diesel::Connection;
+    /// # let connection = PgConnection::establish("postgres://localhost/diesel_demo").unwrap();
+    /// connection.set_notice_handler(|notice| {
+    ///     println!("{} ({:?}): {}", notice.severity, notice.sql_state, notice.message);
+    /// });
+    /// ```
+    pub fn set_notice_handler<F>(&self, handler: F) where
+        F: FnMut(&PgNotice) + Send + 'static,
+    {
+        let _ = self.raw_connection.set_notice_handler(Box::new(handler));
+    }
+
+    /// Removes the handler set by `set_notice_handler`, so that notices are
+    /// written to stderr again.
+    pub fn reset_notice_handler(&self) {
+        let _ = self.raw_connection.set_notice_handler(notice::default_notice_handler());
+    }
+
+    /// Starts listening for notifications sent to `channel`. Like any other
+    /// statement, this only takes effect once the current transaction (if
+    /// any) has been committed.
+    pub fn listen(&self, channel: &str) -> QueryResult<()> {
+        let channel = try!(self.raw_connection.escape_identifier(channel));
+        self.execute(&format!("LISTEN {}", &*channel)).map(|_| ())
+    }
+
+    /// Stops listening for notifications sent to `channel`.
+    pub fn unlisten(&self, channel: &str) -> QueryResult<()> {
+        let channel = try!(self.raw_connection.escape_identifier(channel));
+        self.execute(&format!("UNLISTEN {}", &*channel)).map(|_| ())
+    }
+
+    /// Sends a notification to every connection listening on `channel`. If
+    /// called inside of a transaction, the notification is only delivered
+    /// once it has been committed.
+    pub fn notify(&self, channel: &str, payload: &str) -> QueryResult<()> {
+        let text_oid = <Pg as HasSqlType<types::Text>>::metadata().oid;
+        let binds = vec![Some(channel.as_bytes().to_vec()), Some(payload.as_bytes().to_vec())];
+        self.exec_sql_params("SELECT pg_notify($1, $2)", &binds, &Some(vec![text_oid, text_oid]))
+            .map(|_| ())
+    }
+
+    /// Returns the next notification sent to a channel this connection is
+    /// listening on, or `None` if there aren't any pending. Never blocks.
+    pub fn poll_notification(&self) -> QueryResult<Option<PgNotification>> {
+        try!(self.raw_connection.consume_input());
+        Ok(self.raw_connection.next_notification())
+    }
+
+    /// Returns the next notification sent to a channel this connection is
+    /// listening on, blocking until one arrives. Returns `None` if none have
+    /// arrived by the time `timeout` has elapsed.
+    ///
+    /// Waiting is only supported on unix platforms. Elsewhere, and if waiting
+    /// on the connection's socket fails, this returns `Error::IoError`.
+    pub fn wait_for_notification(&self, timeout: Duration) -> QueryResult<Option<PgNotification>> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(notification) = try!(self.poll_notification()) {
+                return Ok(Some(notification));
+            }
+            let now = Instant::now();
+            if now >= deadline || !try!(wait_for_input(self.raw_connection.socket(), deadline - now)) {
+                return Ok(None);
+            }
+        }
+    }
+
     fn exec_sql_params(&self, query: &str, param_data: &Vec<Option<Vec<u8>>>, param_types: &Option<Vec<u32>>) -> QueryResult<PgResult> {
-        let query = try!(CString::new(query));
-        let params_pointer = param_data.iter()
-            .map(|data| data.as_ref().map(|d| d.as_ptr() as *const libc::c_char)
-                 .unwrap_or(ptr::null()))
-            .collect::<Vec<_>>();
+        let c_query = try!(CString::new(query));
+        try!(self.reconnect_if_broken());
+        let params = BindParams::new(param_data);
         let param_types_ptr = param_types.as_ref()
             .map(|types| types.as_ptr())
             .unwrap_or(ptr::null());
-        let param_lengths = param_data.iter()
-            .map(|data| data.as_ref().map(|d| d.len() as libc::c_int)
-                 .unwrap_or(0))
-            .collect::<Vec<_>>();
-        let param_formats = vec![1; param_data.len()];
-
-        let internal_res = unsafe {
-            self.raw_connection.exec_params(
-                query.as_ptr(),
-                params_pointer.len() as libc::c_int,
-                param_types_ptr,
-                params_pointer.as_ptr(),
-                param_lengths.as_ptr(),
-                param_formats.as_ptr(),
-                1,
-            )
-        };
-
-        PgResult::new(self, internal_res)
-    }
-
-    fn prepare_query<T: QueryFragment<Pg>>(&self, source: &T)
-        -> (String, Vec<Option<Vec<u8>>>, Vec<u32>)
-    {
+
+        self.instrumentation.instrument(query, param_data.len(), || {
+            let internal_res = unsafe {
+                self.raw_connection.exec_params(
+                    c_query.as_ptr(),
+                    params.values.len() as libc::c_int,
+                    param_types_ptr,
+                    params.values.as_ptr(),
+                    params.lengths.as_ptr(),
+                    params.formats.as_ptr(),
+                    1,
+                )
+            };
+            PgResult::new(self, internal_res)
+        })
+    }
+
+    fn exec_prepared(&self, stmt_name: &CString, query: &str, param_data: &Vec<Option<Vec<u8>>>) -> QueryResult<PgResult> {
+        let params = BindParams::new(param_data);
+
+        self.instrumentation.instrument(query, param_data.len(), || {
+            let internal_res = unsafe {
+                self.raw_connection.exec_prepared(
+                    stmt_name.as_ptr(),
+                    params.values.len() as libc::c_int,
+                    params.values.as_ptr(),
+                    params.lengths.as_ptr(),
+                    params.formats.as_ptr(),
+                    1,
+                )
+            };
+            PgResult::new(self, internal_res)
+        })
+    }
+
+    fn prepare_statement(&self, stmt_name: &CString, query: &str, param_types: &[u32]) -> QueryResult<()> {
+        let c_query = try!(CString::new(query));
+        // Reported as the equivalent SQL, as the statement is prepared with
+        // the protocol rather than by running `PREPARE`
+        let sql = format!("PREPARE \"{}\" AS {}", stmt_name.to_string_lossy(), query);
+        self.instrumentation.instrument(&sql, 0, || {
+            let internal_res = unsafe {
+                self.raw_connection.prepare(
+                    stmt_name.as_ptr(),
+                    c_query.as_ptr(),
+                    param_types.len() as libc::c_int,
+                    param_types.as_ptr(),
+                )
+            };
+            PgResult::new(self, internal_res)
+        }).map(|_| ())
+    }
+
+    fn execute_query<T: QueryFragment<Pg>>(&self, source: &T) -> QueryResult<PgResult> {
+        // Before looking up the prepared statement, as reconnecting clears
+        // the cache
+        try!(self.reconnect_if_broken());
+        let query_builder = try!(self.prepare_query(source));
+        if query_builder.safe_to_cache_prepared {
+            let stmt_name = try!(self.cached_statement_name(&query_builder));
+            self.exec_prepared(&stmt_name, &query_builder.sql, &query_builder.binds)
+        } else {
+            self.exec_sql_params(&query_builder.sql, &query_builder.binds, &Some(query_builder.bind_types))
+        }
+    }
+
+    /// Sends the query without waiting for its results, which must be read
+    /// with `RawConnection::get_result`.
+    fn send_query(&self, query_builder: &PgQueryBuilder) -> QueryResult<()> {
+        let params = BindParams::new(&query_builder.binds);
+        if query_builder.safe_to_cache_prepared {
+            let stmt_name = try!(self.cached_statement_name(query_builder));
+            unsafe {
+                self.raw_connection.send_query_prepared(
+                    stmt_name.as_ptr(),
+                    params.values.len() as libc::c_int,
+                    params.values.as_ptr(),
+                    params.lengths.as_ptr(),
+                    params.formats.as_ptr(),
+                    1,
+                )
+            }
+        } else {
+            let query = try!(CString::new(&*query_builder.sql));
+            unsafe {
+                self.raw_connection.send_query_params(
+                    query.as_ptr(),
+                    params.values.len() as libc::c_int,
+                    query_builder.bind_types.as_ptr(),
+                    params.values.as_ptr(),
+                    params.lengths.as_ptr(),
+                    params.formats.as_ptr(),
+                    1,
+                )
+            }
+        }
+    }
+
+    /// Reads and discards the results of a query sent with `send_query`, so
+    /// that the connection can be used for the next one.
+    fn discard_remaining_results(&self) {
+        loop {
+            let internal_result = unsafe { self.raw_connection.get_result() };
+            if internal_result.is_null() {
+                break;
+            }
+            let _ = PgResult::new(self, internal_result);
+        }
+    }
+
+    fn cached_statement_name(&self, query_builder: &PgQueryBuilder) -> QueryResult<CString> {
+        self.statement_cache.cached_statement_name(
+            &query_builder.sql,
+            &query_builder.bind_types,
+            |name| self.prepare_statement(name, &query_builder.sql, &query_builder.bind_types),
+            |name| self.deallocate_statement(name),
+        )
+    }
+
+    fn deallocate_statement(&self, stmt_name: &CString) -> QueryResult<()> {
+        let sql = format!("DEALLOCATE \"{}\"", stmt_name.to_string_lossy());
+        let c_sql = try!(CString::new(&*sql));
+        self.instrumentation.instrument(&sql, 0, || {
+            PgResult::new(self, unsafe { self.raw_connection.exec(c_sql.as_ptr()) })
+        }).map(|_| ())
+    }
+
+    fn new(raw_connection: RawConnection) -> Self {
+        PgConnection {
+            raw_connection: Rc::new(raw_connection),
+            transaction_depth: Cell::new(0),
+            statement_cache: StatementCache::new(),
+            instrumentation: Instrumentation::new(),
+            auto_reconnect: Cell::new(false),
+        }
+    }
+
+    fn prepare_query<T: QueryFragment<Pg>>(&self, source: &T) -> QueryResult<PgQueryBuilder> {
         let mut query_builder = PgQueryBuilder::new(&self.raw_connection);
-        source.to_sql(&mut query_builder).unwrap();
-        (query_builder.sql, query_builder.binds, query_builder.bind_types)
+        try!(source.to_sql(&mut query_builder)
+            .map_err(|e| Error::QueryBuilderError(e.to_string().into())));
+        Ok(query_builder)
     }
 
     fn execute_inner(&self, query: &str) -> QueryResult<PgResult> {
@@ -166,19 +606,73 @@ impl PgConnection {
         self.raw_connection.last_error_message()
     }
 
+    #[doc(hidden)]
+    pub fn end_unexpected_copy(&self, copy_in: bool) {
+        copy::end_unexpected_copy(self, copy_in)
+    }
+
+    /// Begins a transaction with the given SQL, or creates a savepoint if a
+    /// transaction is already open.
+    fn begin_transaction_with(&self, begin_sql: &str) -> QueryResult<()> {
+        let transaction_depth = self.transaction_depth.get();
+        self.change_transaction_depth(1, if transaction_depth == 0 {
+            self.execute(begin_sql)
+        } else {
+            self.execute(&format!("SAVEPOINT diesel_savepoint_{}", transaction_depth))
+        })
+    }
+
     fn change_transaction_depth(&self, by: i32, query: QueryResult<usize>) -> QueryResult<()> {
-        if query.is_ok() {
-            self.transaction_depth.set(self.transaction_depth.get() + by);
+        match query {
+            Ok(_) => self.transaction_depth.set(self.transaction_depth.get() + by),
+            // The server has already rolled back the whole transaction
+            Err(Error::ConnectionLost) => self.transaction_depth.set(0),
+            Err(_) => {},
         }
         query.map(|_| ())
     }
+
+    /// Resets the connection if automatic reconnection is enabled, and the
+    /// connection was lost outside of a transaction. Anything the server has
+    /// sent is read first, so that a connection which the server closed while
+    /// it was idle is noticed before the next statement is sent.
+    fn reconnect_if_broken(&self) -> QueryResult<()> {
+        if !self.auto_reconnect.get() || self.transaction_depth.get() != 0 {
+            return Ok(());
+        }
+        let _ = self.raw_connection.consume_input();
+        if self.raw_connection.is_broken() {
+            // This also invalidates any cancel handles, which refer to the
+            // old session's process
+            if !self.raw_connection.reset() {
+                return Err(Error::ConnectionLost);
+            }
+            self.statement_cache.clear();
+        }
+        Ok(())
+    }
 }
 
-extern "C" fn noop_notice_processor(_: *mut libc::c_void, _message: *const libc::c_char) {
+/// The pointers to bind params in the layout expected by `PQexecParams` and
+/// `PQexecPrepared`. All params are sent in binary format.
+struct BindParams {
+    values: Vec<*const libc::c_char>,
+    lengths: Vec<libc::c_int>,
+    formats: Vec<libc::c_int>,
 }
 
-extern "C" fn default_notice_processor(_: *mut libc::c_void, message: *const libc::c_char) {
-    use std::io::Write;
-    let c_str = unsafe { CStr::from_ptr(message) };
-    ::std::io::stderr().write(c_str.to_bytes()).unwrap();
+impl BindParams {
+    fn new(param_data: &Vec<Option<Vec<u8>>>) -> Self {
+        BindParams {
+            values: param_data.iter()
+                .map(|data| data.as_ref().map(|d| d.as_ptr() as *const libc::c_char)
+                     .unwrap_or(ptr::null()))
+                .collect(),
+            lengths: param_data.iter()
+                .map(|data| data.as_ref().map(|d| d.len() as libc::c_int)
+                     .unwrap_or(0))
+                .collect(),
+            formats: vec![1; param_data.len()],
+        }
+    }
 }
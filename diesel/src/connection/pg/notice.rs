@@ -0,0 +1,76 @@
+extern crate pq_sys;
+extern crate libc;
+
+use std::cell::RefCell;
+use std::io::{self, Write};
+use std::panic::{self, AssertUnwindSafe};
+
+use self::pq_sys::PGresult;
+use db_result::*;
+
+/// A notice or warning sent by the server while running a statement, such as
+/// the `NOTICE` sent by `CREATE TABLE IF NOT EXISTS` when the table already
+/// exists. Passed to the handler given to
+/// [`PgConnection::set_notice_handler`](struct.PgConnection.html#method.set_notice_handler).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgNotice {
+    /// The severity of the notice, such as `WARNING`, `NOTICE`, `INFO`,
+    /// `LOG` or `DEBUG`. Servers older than PostgreSQL 9.6 send this in the
+    /// language of the server's locale.
+    pub severity: String,
+    /// The SQLSTATE code of the notice. Notices generated by libpq itself
+    /// rather than the server don't have one.
+    pub sql_state: Option<String>,
+    /// The primary message of the notice.
+    pub message: String,
+    /// An optional secondary message with more details.
+    pub details: Option<String>,
+    /// An optional suggestion of what to do about the notice.
+    pub hint: Option<String>,
+}
+
+impl PgNotice {
+    fn new(result: *const PGresult) -> Self {
+        let field = |field_code| error_field(result, field_code);
+        PgNotice {
+            severity: field(PG_DIAG_SEVERITY_NONLOCALIZED)
+                .or_else(|| field(PG_DIAG_SEVERITY))
+                .unwrap_or_else(String::new),
+            sql_state: field(PG_DIAG_SQLSTATE),
+            message: field(PG_DIAG_MESSAGE_PRIMARY).unwrap_or_else(String::new),
+            details: field(PG_DIAG_MESSAGE_DETAIL),
+            hint: field(PG_DIAG_MESSAGE_HINT),
+        }
+    }
+}
+
+pub type NoticeHandler = Box<FnMut(&PgNotice) + Send>;
+
+/// Writes notices to stderr, in the same format as libpq's default notice
+/// processor.
+pub fn default_notice_handler() -> NoticeHandler {
+    Box::new(|notice: &PgNotice| {
+        let mut formatted = format!("{}:  {}\n", notice.severity, notice.message);
+        if let Some(ref details) = notice.details {
+            formatted.push_str(&format!("DETAIL:  {}\n", details));
+        }
+        if let Some(ref hint) = notice.hint {
+            formatted.push_str(&format!("HINT:  {}\n", hint));
+        }
+        let _ = io::stderr().write_all(formatted.as_bytes());
+    })
+}
+
+/// Registered with libpq by `RawConnection`, with `arg` pointing to the
+/// connection's `RefCell<NoticeHandler>`.
+pub unsafe extern "C" fn notice_receiver(arg: *mut libc::c_void, result: *const PGresult) {
+    let handler = &*(arg as *const RefCell<NoticeHandler>);
+    let notice = PgNotice::new(result);
+    // The handler can't reach the connection, so it is never already
+    // borrowed, but we shouldn't panic across the FFI boundary if it is.
+    // For the same reason, a panic in the handler is caught and the notice
+    // dropped. The panic message has already been printed by then.
+    if let Ok(mut handler) = handler.try_borrow_mut() {
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| (&mut *handler)(&notice)));
+    }
+}
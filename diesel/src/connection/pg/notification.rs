@@ -0,0 +1,81 @@
+extern crate libc;
+
+use std::io;
+use std::time::Duration;
+
+use result::{Error, QueryResult};
+
+/// A notification sent with `NOTIFY` or `pg_notify` to a channel the
+/// connection is listening on. Returned by
+/// [`PgConnection::poll_notification`](struct.PgConnection.html#method.poll_notification)
+/// and
+/// [`PgConnection::wait_for_notification`](struct.PgConnection.html#method.wait_for_notification).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgNotification {
+    /// The channel the notification was sent to.
+    pub channel: String,
+    /// The payload of the notification, which is empty if none was given.
+    pub payload: String,
+    /// The process ID of the server process which sent the notification.
+    pub process_id: i32,
+}
+
+/// Blocks until there is data to be read from `socket`, or `timeout` has
+/// elapsed. Returns `false` if the timeout elapsed first.
+pub fn wait_for_input(socket: libc::c_int, timeout: Duration) -> QueryResult<bool> {
+    wait_for_socket(socket, false, timeout_millis(timeout)).map_err(Error::IoError)
+}
+
+/// Blocks until `socket` is readable (or writable, if `write` is `true`), or
+/// `timeout` milliseconds have elapsed. A negative timeout waits forever.
+/// Returns `false` if the timeout elapsed first.
+#[cfg(unix)]
+pub fn wait_for_socket(socket: libc::c_int, write: bool, timeout: libc::c_int) -> io::Result<bool> {
+    let mut events = libc::POLLIN;
+    if write {
+        events |= libc::POLLOUT;
+    }
+    let mut poll_fd = libc::pollfd {
+        fd: socket,
+        events: events,
+        revents: 0,
+    };
+    loop {
+        match unsafe { libc::poll(&mut poll_fd, 1, timeout) } {
+            -1 => {
+                let error = io::Error::last_os_error();
+                if error.kind() != io::ErrorKind::Interrupted {
+                    return Err(error);
+                }
+            }
+            0 => return Ok(false),
+            _ => return Ok(true),
+        }
+    }
+}
+
+#[cfg(not(unix))]
+pub fn wait_for_socket(_socket: libc::c_int, _write: bool, _timeout: libc::c_int) -> io::Result<bool> {
+    Err(io::Error::new(io::ErrorKind::Other,
+        "Waiting on the connection's socket is only supported on unix platforms"))
+}
+
+// Rounded up, so we never wake up before the timeout and spin
+pub fn timeout_millis(timeout: Duration) -> libc::c_int {
+    let millis = timeout.as_secs()
+        .saturating_mul(1000)
+        .saturating_add((timeout.subsec_nanos() as u64 + 999_999) / 1_000_000);
+    if millis > libc::c_int::max_value() as u64 {
+        libc::c_int::max_value()
+    } else {
+        millis as libc::c_int
+    }
+}
+
+#[test]
+fn timeout_millis_rounds_up_and_saturates() {
+    assert_eq!(0, timeout_millis(Duration::from_secs(0)));
+    assert_eq!(1, timeout_millis(Duration::new(0, 1)));
+    assert_eq!(1500, timeout_millis(Duration::from_millis(1500)));
+    assert_eq!(libc::c_int::max_value(), timeout_millis(Duration::from_secs(u64::max_value())));
+}
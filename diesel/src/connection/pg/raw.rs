@@ -2,29 +2,65 @@ extern crate pq_sys;
 extern crate libc;
 
 use self::pq_sys::*;
+use std::cell::RefCell;
 use std::ffi::{CString, CStr};
-use std::{str, ptr};
+use std::{str, mem, ptr, slice};
 
 use result::*;
+use super::cancel::{CancelHandle, new_cancel_handle};
+use super::notice::{NoticeHandler, default_notice_handler, notice_receiver};
+use super::notification::PgNotification;
 
 pub struct RawConnection {
     internal_connection: *mut PGconn,
+    // Boxed so that its address, which libpq holds on to, never changes
+    notice_handler: Box<RefCell<NoticeHandler>>,
 }
 
 impl RawConnection {
     pub fn establish(database_url: &str) -> ConnectionResult<Self> {
         let connection_string = try!(CString::new(database_url));
         let connection_ptr = unsafe { PQconnectdb(connection_string.as_ptr()) };
+        RawConnection::new(connection_ptr)
+    }
+
+    pub fn establish_with_params(params: &[(&str, String)]) -> ConnectionResult<Self> {
+        let keywords = try!(params.iter()
+            .map(|&(keyword, _)| CString::new(keyword))
+            .collect::<Result<Vec<_>, _>>());
+        let values = try!(params.iter()
+            .map(|&(_, ref value)| CString::new(&**value))
+            .collect::<Result<Vec<_>, _>>());
+        // Both arrays are terminated by a null pointer
+        let keyword_ptrs = keywords.iter().map(|k| k.as_ptr())
+            .chain(Some(ptr::null()))
+            .collect::<Vec<_>>();
+        let value_ptrs = values.iter().map(|v| v.as_ptr())
+            .chain(Some(ptr::null()))
+            .collect::<Vec<_>>();
+        let connection_ptr = unsafe { PQconnectdbParams(keyword_ptrs.as_ptr(), value_ptrs.as_ptr(), 0) };
+        RawConnection::new(connection_ptr)
+    }
+
+    fn new(connection_ptr: *mut PGconn) -> ConnectionResult<Self> {
         let connection_status = unsafe { PQstatus(connection_ptr) };
 
         match connection_status {
             CONNECTION_OK => {
+                let notice_handler = Box::new(RefCell::new(default_notice_handler()));
+                unsafe {
+                    let handler_ptr = &*notice_handler as *const RefCell<NoticeHandler>;
+                    PQsetNoticeReceiver(connection_ptr, Some(notice_receiver), handler_ptr as *mut libc::c_void);
+                }
                 Ok(RawConnection {
                     internal_connection: connection_ptr,
+                    notice_handler: notice_handler,
                 })
             }
             _ => {
                 let message = last_error_message(connection_ptr);
+                // libpq allocates the connection even when it fails
+                unsafe { PQfinish(connection_ptr) };
                 Err(ConnectionError::BadConnection(message))
             }
         }
@@ -34,6 +70,29 @@ impl RawConnection {
         last_error_message(self.internal_connection)
     }
 
+    /// The error for a libpq call which just failed.
+    pub fn last_error(&self) -> Error {
+        if self.is_broken() {
+            Error::ConnectionLost
+        } else {
+            Error::DatabaseError(DatabaseErrorKind::__Unknown, Box::new(self.last_error_message()))
+        }
+    }
+
+    /// Whether libpq has noticed that the connection to the server was lost.
+    /// This only happens when the connection is used.
+    pub fn is_broken(&self) -> bool {
+        unsafe { PQstatus(self.internal_connection) == CONNECTION_BAD }
+    }
+
+    /// Closes the connection and opens a new one with the same parameters,
+    /// returning whether that succeeded. Everything about the session, such
+    /// as prepared statements and settings, is lost.
+    pub fn reset(&self) -> bool {
+        unsafe { PQreset(self.internal_connection) };
+        !self.is_broken()
+    }
+
     pub fn escape_identifier(&self, identifier: &str) -> QueryResult<PgString> {
         let result_ptr = unsafe { PQescapeIdentifier(
             self.internal_connection,
@@ -42,7 +101,8 @@ impl RawConnection {
         ) };
 
         if result_ptr.is_null() {
-            Err(Error::DatabaseError(last_error_message(self.internal_connection)))
+            let message = last_error_message(self.internal_connection);
+            Err(Error::DatabaseError(DatabaseErrorKind::__Unknown, Box::new(message)))
         } else {
             unsafe {
                 Ok(PgString::new(result_ptr))
@@ -50,10 +110,10 @@ impl RawConnection {
         }
     }
 
-    pub fn set_notice_processor(&self, notice_processor: NoticeProcessor) {
-        unsafe {
-            PQsetNoticeProcessor(self.internal_connection, Some(notice_processor), ptr::null_mut());
-        }
+    /// Replaces the handler called for each notice the server sends,
+    /// returning the previous one.
+    pub fn set_notice_handler(&self, handler: NoticeHandler) -> NoticeHandler {
+        mem::replace(&mut *self.notice_handler.borrow_mut(), handler)
     }
 
     pub unsafe fn exec(&self, query: *const libc::c_char) -> *mut PGresult {
@@ -81,9 +141,219 @@ impl RawConnection {
             result_format,
         )
     }
-}
 
-pub type NoticeProcessor = extern "C" fn(arg: *mut libc::c_void, message: *const libc::c_char);
+    pub unsafe fn send_query_params(
+        &self,
+        query: *const libc::c_char,
+        param_count: libc::c_int,
+        param_types: *const Oid,
+        param_values: *const *const libc::c_char,
+        param_lengths: *const libc::c_int,
+        param_formats: *const libc::c_int,
+        result_format: libc::c_int,
+    ) -> QueryResult<()> {
+        let success = PQsendQueryParams(
+            self.internal_connection,
+            query,
+            param_count,
+            param_types,
+            param_values,
+            param_lengths,
+            param_formats,
+            result_format,
+        );
+        self.check_dispatched(success)
+    }
+
+    pub unsafe fn send_query_prepared(
+        &self,
+        stmt_name: *const libc::c_char,
+        param_count: libc::c_int,
+        param_values: *const *const libc::c_char,
+        param_lengths: *const libc::c_int,
+        param_formats: *const libc::c_int,
+        result_format: libc::c_int,
+    ) -> QueryResult<()> {
+        let success = PQsendQueryPrepared(
+            self.internal_connection,
+            stmt_name,
+            param_count,
+            param_values,
+            param_lengths,
+            param_formats,
+            result_format,
+        );
+        self.check_dispatched(success)
+    }
+
+    /// Must be called immediately after a query has been sent, before any
+    /// results have been read.
+    pub fn set_single_row_mode(&self) -> QueryResult<()> {
+        let success = unsafe { PQsetSingleRowMode(self.internal_connection) };
+        self.check_dispatched(success)
+    }
+
+    /// Returns the next result of a query sent with one of the `send_*`
+    /// functions, or null once all results have been read.
+    pub unsafe fn get_result(&self) -> *mut PGresult {
+        PQgetResult(self.internal_connection)
+    }
+
+    pub fn cancel_handle(&self) -> QueryResult<CancelHandle> {
+        let internal_cancel = unsafe { PQgetCancel(self.internal_connection) };
+        if internal_cancel.is_null() {
+            Err(Error::CancelFailed(self.last_error_message()))
+        } else {
+            Ok(new_cancel_handle(internal_cancel))
+        }
+    }
+
+    /// Asks the server to abandon the query currently being processed. This
+    /// is a best effort, and any results must still be read afterwards.
+    pub fn cancel(&self) {
+        if let Ok(cancel_handle) = self.cancel_handle() {
+            let _ = cancel_handle.cancel();
+        }
+    }
+
+    /// Sends data to the server during `COPY FROM STDIN`.
+    pub fn put_copy_data(&self, data: &[u8]) -> QueryResult<()> {
+        let success = unsafe { PQputCopyData(
+            self.internal_connection,
+            data.as_ptr() as *const libc::c_char,
+            data.len() as libc::c_int,
+        ) };
+        self.check_dispatched(success)
+    }
+
+    /// Finishes `COPY FROM STDIN`. If an error message is given, the copy
+    /// fails with that message. The outcome has to be read with
+    /// `get_result`.
+    pub fn put_copy_end(&self, error_message: Option<&CStr>) -> QueryResult<()> {
+        let error_message = error_message.map(|m| m.as_ptr()).unwrap_or(ptr::null());
+        let success = unsafe { PQputCopyEnd(self.internal_connection, error_message) };
+        self.check_dispatched(success)
+    }
+
+    /// Reads the next row of data during `COPY TO STDOUT`, blocking until it
+    /// arrives. Returns `None` once all of the data has been read, after
+    /// which the outcome has to be read with `get_result`.
+    pub fn get_copy_data(&self) -> QueryResult<Option<Vec<u8>>> {
+        let mut buffer = ptr::null_mut();
+        let length = unsafe { PQgetCopyData(self.internal_connection, &mut buffer, 0) };
+        match length {
+            -1 => Ok(None),
+            -2 => Err(self.last_error()),
+            _ => unsafe {
+                let data = slice::from_raw_parts(buffer as *const u8, length as usize).to_vec();
+                PQfreemem(buffer as *mut libc::c_void);
+                Ok(Some(data))
+            },
+        }
+    }
+
+    /// Reads any data the server has sent without blocking, which makes
+    /// newly received notifications available to `next_notification`.
+    pub fn consume_input(&self) -> QueryResult<()> {
+        let success = unsafe { PQconsumeInput(self.internal_connection) };
+        self.check_dispatched(success)
+    }
+
+    /// Returns the next notification which has already been received, if
+    /// any.
+    pub fn next_notification(&self) -> Option<PgNotification> {
+        unsafe {
+            let notify = PQnotifies(self.internal_connection);
+            if notify.is_null() {
+                return None;
+            }
+            let notification = PgNotification {
+                channel: CStr::from_ptr((*notify).relname).to_string_lossy().into_owned(),
+                payload: CStr::from_ptr((*notify).extra).to_string_lossy().into_owned(),
+                process_id: (*notify).be_pid,
+            };
+            PQfreemem(notify as *mut libc::c_void);
+            Some(notification)
+        }
+    }
+
+    /// Puts the connection into nonblocking mode, so that sending a query
+    /// never waits for the socket to become writable.
+    pub fn set_nonblocking(&self) -> QueryResult<()> {
+        match unsafe { PQsetnonblocking(self.internal_connection, 1) } {
+            0 => Ok(()),
+            _ => Err(self.last_error()),
+        }
+    }
+
+    pub unsafe fn send_query(&self, query: *const libc::c_char) -> QueryResult<()> {
+        let success = PQsendQuery(self.internal_connection, query);
+        self.check_dispatched(success)
+    }
+
+    /// Sends as much buffered data to the server as possible without
+    /// blocking. Returns `false` if some of it couldn't be sent yet.
+    pub fn flush(&self) -> QueryResult<bool> {
+        match unsafe { PQflush(self.internal_connection) } {
+            0 => Ok(true),
+            1 => Ok(false),
+            _ => Err(self.last_error()),
+        }
+    }
+
+    /// Whether `get_result` would block waiting for more input.
+    pub fn is_busy(&self) -> bool {
+        unsafe { PQisBusy(self.internal_connection) != 0 }
+    }
+
+    pub fn socket(&self) -> libc::c_int {
+        unsafe { PQsocket(self.internal_connection) }
+    }
+
+    fn check_dispatched(&self, success: libc::c_int) -> QueryResult<()> {
+        if success == 1 {
+            Ok(())
+        } else {
+            Err(self.last_error())
+        }
+    }
+
+    pub unsafe fn prepare(
+        &self,
+        stmt_name: *const libc::c_char,
+        query: *const libc::c_char,
+        param_count: libc::c_int,
+        param_types: *const Oid,
+    ) -> *mut PGresult {
+        PQprepare(
+            self.internal_connection,
+            stmt_name,
+            query,
+            param_count,
+            param_types,
+        )
+    }
+
+    pub unsafe fn exec_prepared(
+        &self,
+        stmt_name: *const libc::c_char,
+        param_count: libc::c_int,
+        param_values: *const *const libc::c_char,
+        param_lengths: *const libc::c_int,
+        param_formats: *const libc::c_int,
+        result_format: libc::c_int,
+    ) -> *mut PGresult {
+        PQexecPrepared(
+            self.internal_connection,
+            stmt_name,
+            param_count,
+            param_values,
+            param_lengths,
+            param_formats,
+            result_format,
+        )
+    }
+}
 
 impl Drop for RawConnection {
     fn drop(&mut self) {
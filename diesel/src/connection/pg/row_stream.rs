@@ -0,0 +1,126 @@
+use std::marker::PhantomData;
+use std::time::Instant;
+
+use backend::Pg;
+use connection::Connection;
+use db_result::PgResult;
+use query_source::Queryable;
+use result::{Error, QueryResult};
+use row::deserialize_row;
+use super::PgConnection;
+use types::HasSqlType;
+
+/// The iterator returned by `PgConnection::stream_query`. The query is run
+/// in single row mode, so only one row is held in memory at a time.
+///
+/// The connection can't be used for anything else until every row has been
+/// read. Dropping the iterator early cancels the query, unless a transaction
+/// is open, in which case the remaining rows are read and discarded. That
+/// blocks until the server has sent every row.
+///
+/// The query is reported to the connection's instrumentation callback once
+/// the last row has been read, or the iterator is dropped.
+pub struct RowStream<'a, ST, T> {
+    conn: &'a PgConnection,
+    done: bool,
+    sql: String,
+    bind_count: usize,
+    start: Instant,
+    rows_read: usize,
+    _marker: PhantomData<(ST, T)>,
+}
+
+impl<'a, ST, T> RowStream<'a, ST, T> {
+    #[doc(hidden)]
+    pub fn new(conn: &'a PgConnection, sql: String, bind_count: usize, start: Instant) -> Self {
+        RowStream {
+            conn: conn,
+            done: false,
+            sql: sql,
+            bind_count: bind_count,
+            start: start,
+            rows_read: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    fn report(&self, result: Result<usize, &Error>) {
+        self.conn.instrumentation.report(&self.sql, self.bind_count, self.start.elapsed(), result);
+    }
+
+    fn next_result(&self) -> Option<QueryResult<PgResult>> {
+        let internal_result = unsafe { self.conn.raw_connection.get_result() };
+        if internal_result.is_null() {
+            None
+        } else {
+            Some(PgResult::new(self.conn, internal_result))
+        }
+    }
+
+    /// Reads the rest of the results, returning how many rows there were, or
+    /// the first error.
+    fn discard_remaining_results(&mut self) -> QueryResult<usize> {
+        let mut rows = 0;
+        let mut error = None;
+        while let Some(result) = self.next_result() {
+            match result {
+                Ok(result) => rows += result.num_rows(),
+                Err(e) => if error.is_none() {
+                    error = Some(e);
+                },
+            }
+        }
+        self.done = true;
+        match error {
+            Some(e) => Err(e),
+            None => Ok(rows),
+        }
+    }
+}
+
+impl<'a, ST, T> Iterator for RowStream<'a, ST, T> where
+    Pg: HasSqlType<ST>,
+    T: Queryable<ST, Pg>,
+{
+    type Item = QueryResult<T>;
+
+    fn next(&mut self) -> Option<QueryResult<T>> {
+        while !self.done {
+            match self.next_result() {
+                // The final result of a query in single row mode has no rows
+                Some(Ok(ref result)) if result.num_rows() == 0 => continue,
+                Some(Ok(result)) => {
+                    self.rows_read += 1;
+                    return Some(deserialize_row(&mut result.get_row(0)));
+                }
+                Some(Err(e)) => {
+                    let _ = self.discard_remaining_results();
+                    self.report(Err(&e));
+                    return Some(Err(e));
+                }
+                None => {
+                    self.done = true;
+                    self.report(Ok(self.rows_read));
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<'a, ST, T> Drop for RowStream<'a, ST, T> {
+    fn drop(&mut self) {
+        if !self.done {
+            // Cancelling the query inside of a transaction would abort the
+            // whole transaction, so we have to read the rest of the rows
+            if self.conn.get_transaction_depth() == 0 {
+                self.conn.raw_connection.cancel();
+            }
+            let rows_read = self.rows_read;
+            match self.discard_remaining_results() {
+                Ok(rows) => self.report(Ok(rows_read + rows)),
+                Err(e) => self.report(Err(&e)),
+            }
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::ffi::CString;
+
+use result::QueryResult;
+
+// How many prepared statements are kept on the server for each connection
+const DEFAULT_CAPACITY: usize = 256;
+
+/// Maps the SQL and bind types of a query to the name of a prepared statement
+/// which has already been created for it on the server. Once the cache is
+/// full, the least recently used statement is deallocated to make room.
+pub struct StatementCache {
+    cache: RefCell<HashMap<StatementCacheKey, CachedStatement>>,
+    capacity: usize,
+    next_statement_id: Cell<usize>,
+    // Incremented on every lookup, to track how recently statements were used
+    clock: Cell<u64>,
+}
+
+#[derive(Hash, PartialEq, Eq)]
+struct StatementCacheKey {
+    sql: String,
+    bind_types: Vec<u32>,
+}
+
+struct CachedStatement {
+    name: CString,
+    last_used: u64,
+}
+
+impl StatementCache {
+    pub fn new() -> Self {
+        StatementCache::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        StatementCache {
+            cache: RefCell::new(HashMap::new()),
+            capacity: capacity,
+            next_statement_id: Cell::new(0),
+            clock: Cell::new(0),
+        }
+    }
+
+    /// Returns the name of the prepared statement for the given query,
+    /// calling `prepare` with a newly generated name if the query has not
+    /// been seen before. Nothing is cached if `prepare` fails.
+    ///
+    /// If the cache is full, `deallocate` is called with the name of the
+    /// least recently used statement first. The statement is only forgotten
+    /// if it was deallocated successfully.
+    pub fn cached_statement_name<F, D>(&self, sql: &str, bind_types: &[u32], prepare: F, deallocate: D)
+        -> QueryResult<CString> where
+        F: FnOnce(&CString) -> QueryResult<()>,
+        D: FnOnce(&CString) -> QueryResult<()>,
+    {
+        let now = self.clock.get() + 1;
+        self.clock.set(now);
+        let key = StatementCacheKey {
+            sql: sql.to_string(),
+            bind_types: bind_types.to_vec(),
+        };
+        if let Some(statement) = self.cache.borrow_mut().get_mut(&key) {
+            statement.last_used = now;
+            return Ok(statement.name.clone());
+        }
+
+        if self.cache.borrow().len() >= self.capacity {
+            try!(self.evict_least_recently_used(deallocate));
+        }
+
+        let statement_id = self.next_statement_id.get();
+        let name = try!(CString::new(format!("__diesel_stmt_{}", statement_id)));
+        try!(prepare(&name));
+        self.next_statement_id.set(statement_id + 1);
+        self.cache.borrow_mut().insert(key, CachedStatement {
+            name: name.clone(),
+            last_used: now,
+        });
+        Ok(name)
+    }
+
+    /// Forgets every statement, for when the session they were prepared in
+    /// has ended.
+    pub fn clear(&self) {
+        self.cache.borrow_mut().clear();
+    }
+
+    fn evict_least_recently_used<D>(&self, deallocate: D) -> QueryResult<()> where
+        D: FnOnce(&CString) -> QueryResult<()>,
+    {
+        let mut cache = self.cache.borrow_mut();
+        let oldest_key = cache.iter()
+            .min_by_key(|&(_, statement)| statement.last_used)
+            .map(|(key, _)| StatementCacheKey {
+                sql: key.sql.clone(),
+                bind_types: key.bind_types.clone(),
+            });
+        if let Some(key) = oldest_key {
+            try!(deallocate(&cache[&key].name));
+            cache.remove(&key);
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn statements_are_only_prepared_once_per_sql_and_bind_types() {
+    let cache = StatementCache::new();
+    let prepared = RefCell::new(Vec::new());
+    let prepare = |name: &CString| {
+        prepared.borrow_mut().push(name.clone());
+        Ok(())
+    };
+
+    let first = cache.cached_statement_name("SELECT $1", &[23], &prepare, |_| Ok(())).unwrap();
+    let second = cache.cached_statement_name("SELECT $1", &[23], &prepare, |_| Ok(())).unwrap();
+    let different_types = cache.cached_statement_name("SELECT $1", &[25], &prepare, |_| Ok(())).unwrap();
+
+    assert_eq!(first, second);
+    assert!(first != different_types);
+    assert_eq!(2, prepared.borrow().len());
+}
+
+#[test]
+fn failed_preparations_are_not_cached() {
+    use result::{Error, DatabaseErrorKind};
+
+    let cache = StatementCache::new();
+    let failed = cache.cached_statement_name("SELECT 1", &[], |_| {
+        Err(Error::DatabaseError(DatabaseErrorKind::__Unknown, Box::new("connection lost".to_string())))
+    }, |_| Ok(()));
+    let mut prepared_again = false;
+    let succeeded = cache.cached_statement_name("SELECT 1", &[], |_| {
+        prepared_again = true;
+        Ok(())
+    }, |_| Ok(()));
+
+    assert!(failed.is_err());
+    assert!(succeeded.is_ok());
+    assert!(prepared_again);
+}
+
+#[test]
+fn least_recently_used_statements_are_deallocated_when_full() {
+    let cache = StatementCache::with_capacity(2);
+    let deallocated = RefCell::new(Vec::new());
+    let prepare = |_: &CString| Ok(());
+    let deallocate = |name: &CString| {
+        deallocated.borrow_mut().push(name.clone());
+        Ok(())
+    };
+
+    let first = cache.cached_statement_name("SELECT 1", &[], &prepare, &deallocate).unwrap();
+    let second = cache.cached_statement_name("SELECT 2", &[], &prepare, &deallocate).unwrap();
+    cache.cached_statement_name("SELECT 1", &[], &prepare, &deallocate).unwrap();
+    cache.cached_statement_name("SELECT 3", &[], &prepare, &deallocate).unwrap();
+
+    assert_eq!(vec![second], *deallocated.borrow());
+    assert_eq!(first, cache.cached_statement_name("SELECT 1", &[], &prepare, &deallocate).unwrap());
+    assert_eq!(2, cache.cache.borrow().len());
+}
+
+#[test]
+fn statements_which_fail_to_deallocate_are_kept() {
+    use result::{Error, DatabaseErrorKind};
+
+    let cache = StatementCache::with_capacity(1);
+    let first = cache.cached_statement_name("SELECT 1", &[], |_| Ok(()), |_| Ok(())).unwrap();
+    let failed = cache.cached_statement_name("SELECT 2", &[], |_| Ok(()), |_| {
+        Err(Error::DatabaseError(DatabaseErrorKind::__Unknown, Box::new("connection lost".to_string())))
+    });
+
+    assert!(failed.is_err());
+    assert_eq!(first, cache.cached_statement_name("SELECT 1", &[], |_| Ok(()), |_| Ok(())).unwrap());
+}
@@ -0,0 +1,203 @@
+use connection::{Connection, RetryPolicy};
+use connection::retry::retry_transaction;
+use result::{Error, TransactionError, TransactionResult};
+use super::PgConnection;
+
+/// Used to build a transaction, specifying additional details such as
+/// isolation level or whether it should be read only. Created with
+/// [`PgConnection::build_transaction`](struct.PgConnection.html#method.build_transaction).
+///
+/// The options can only be set on the outermost transaction, as PostgreSQL
+/// doesn't allow them to be changed part way through a transaction. When this
+/// is run inside of another transaction, a savepoint is used just like
+/// `Connection::transaction`, and `Error::InvalidTransactionOptions` is
+/// returned if any options were given.
+///
+/// ```no_run
+/// # use diesel::connection::PgConnection;
+/// # use diesel::Connection;
+/// # let connection = PgConnection::establish("postgres://localhost/diesel_demo").unwrap();
+/// connection.build_transaction()
+///     .repeatable_read()
+///     .read_only()
+///     .run(|| {
+///         // Every query run here sees the same snapshot of the database
+///         Ok::<_, diesel::result::Error>(())
+///     })
+///     .unwrap();
+/// ```
+#[derive(Clone, Copy)]
+pub struct TransactionBuilder<'a> {
+    connection: &'a PgConnection,
+    isolation_level: Option<IsolationLevel>,
+    read_mode: Option<ReadMode>,
+    deferrable: Option<Deferrable>,
+}
+
+impl<'a> TransactionBuilder<'a> {
+    #[doc(hidden)]
+    pub fn new(connection: &'a PgConnection) -> Self {
+        TransactionBuilder {
+            connection: connection,
+            isolation_level: None,
+            read_mode: None,
+            deferrable: None,
+        }
+    }
+
+    /// Makes the transaction `READ ONLY`. Any statements which write to the
+    /// database will fail.
+    pub fn read_only(mut self) -> Self {
+        self.read_mode = Some(ReadMode::ReadOnly);
+        self
+    }
+
+    /// Makes the transaction `READ WRITE`. This is the default, unless
+    /// `default_transaction_read_only` has been set on the server.
+    pub fn read_write(mut self) -> Self {
+        self.read_mode = Some(ReadMode::ReadWrite);
+        self
+    }
+
+    /// Makes the transaction `DEFERRABLE`. This only has an effect for
+    /// transactions which are both `SERIALIZABLE` and `READ ONLY`. They will
+    /// wait for a snapshot which is guaranteed not to cause a serialization
+    /// failure, rather than being able to fail with one part way through.
+    pub fn deferrable(mut self) -> Self {
+        self.deferrable = Some(Deferrable::Deferrable);
+        self
+    }
+
+    /// Makes the transaction `NOT DEFERRABLE`. This is the default, unless
+    /// `default_transaction_deferrable` has been set on the server.
+    pub fn not_deferrable(mut self) -> Self {
+        self.deferrable = Some(Deferrable::NotDeferrable);
+        self
+    }
+
+    /// Runs the transaction with the `READ COMMITTED` isolation level. This
+    /// is the default, unless `default_transaction_isolation` has been set on
+    /// the server.
+    pub fn read_committed(mut self) -> Self {
+        self.isolation_level = Some(IsolationLevel::ReadCommitted);
+        self
+    }
+
+    /// Runs the transaction with the `REPEATABLE READ` isolation level. Every
+    /// statement in the transaction sees the same snapshot of the database.
+    pub fn repeatable_read(mut self) -> Self {
+        self.isolation_level = Some(IsolationLevel::RepeatableRead);
+        self
+    }
+
+    /// Runs the transaction with the `SERIALIZABLE` isolation level. The
+    /// transaction may fail with a serialization failure if it conflicts with
+    /// another one.
+    pub fn serializable(mut self) -> Self {
+        self.isolation_level = Some(IsolationLevel::Serializable);
+        self
+    }
+
+    /// Runs the given function inside of the transaction, with the same
+    /// behavior as `Connection::transaction`. The transaction is committed if
+    /// the function returns `Ok`, and rolled back if it returns `Err`.
+    ///
+    /// Returns `Error::InvalidTransactionOptions` without running the
+    /// function if any options were given and a transaction is already open.
+    pub fn run<T, E, F>(&self, f: F) -> TransactionResult<T, E> where
+        F: FnOnce() -> Result<T, E>,
+    {
+        if self.has_options() && self.connection.get_transaction_depth() > 0 {
+            return Err(TransactionError::CouldntCreateTransaction(Error::InvalidTransactionOptions));
+        }
+        try!(self.connection.begin_transaction_with(&self.begin_sql()));
+        match f() {
+            Ok(value) => {
+                try!(self.connection.commit_transaction());
+                Ok(value)
+            }
+            Err(e) => {
+                try!(self.connection.rollback_transaction());
+                Err(TransactionError::UserReturnedError(e))
+            }
+        }
+    }
+
+    /// Runs the given function inside of the transaction like `run`, retrying
+    /// it if it fails with a serialization failure or deadlock. See
+    /// [`Connection::transaction_with_retry`](../trait.Connection.html#method.transaction_with_retry)
+    /// for details.
+    pub fn run_with_retry<T, F>(&self, policy: RetryPolicy, mut f: F) -> TransactionResult<T, Error> where
+        F: FnMut() -> Result<T, Error>,
+    {
+        let transaction_depth = self.connection.get_transaction_depth();
+        retry_transaction(policy, transaction_depth, || self.run(&mut f))
+    }
+
+    fn has_options(&self) -> bool {
+        self.isolation_level.is_some() || self.read_mode.is_some() || self.deferrable.is_some()
+    }
+
+    fn begin_sql(&self) -> String {
+        let modes = [
+            self.isolation_level.map(IsolationLevel::to_sql),
+            self.read_mode.map(ReadMode::to_sql),
+            self.deferrable.map(Deferrable::to_sql),
+        ];
+        let modes = modes.iter()
+            .filter_map(|mode| *mode)
+            .collect::<Vec<_>>();
+        if modes.is_empty() {
+            "BEGIN".into()
+        } else {
+            format!("BEGIN TRANSACTION {}", modes.join(", "))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IsolationLevel {
+    ReadCommitted,
+    RepeatableRead,
+    Serializable,
+}
+
+impl IsolationLevel {
+    fn to_sql(self) -> &'static str {
+        match self {
+            IsolationLevel::ReadCommitted => "ISOLATION LEVEL READ COMMITTED",
+            IsolationLevel::RepeatableRead => "ISOLATION LEVEL REPEATABLE READ",
+            IsolationLevel::Serializable => "ISOLATION LEVEL SERIALIZABLE",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReadMode {
+    ReadWrite,
+    ReadOnly,
+}
+
+impl ReadMode {
+    fn to_sql(self) -> &'static str {
+        match self {
+            ReadMode::ReadWrite => "READ WRITE",
+            ReadMode::ReadOnly => "READ ONLY",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Deferrable {
+    Deferrable,
+    NotDeferrable,
+}
+
+impl Deferrable {
+    fn to_sql(self) -> &'static str {
+        match self {
+            Deferrable::Deferrable => "DEFERRABLE",
+            Deferrable::NotDeferrable => "NOT DEFERRABLE",
+        }
+    }
+}
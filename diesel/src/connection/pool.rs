@@ -0,0 +1,414 @@
+//! A thread safe pool of connections, which can be used with any backend.
+//!
+//! ```no_run
+//! # use diesel::connection::PgConnection;
+//! use diesel::connection::pool::Pool;
+//! use std::time::Duration;
+//!
+//! let pool: Pool<PgConnection> = Pool::builder()
+//!     .max_size(15)
+//!     .checkout_timeout(Duration::from_secs(5))
+//!     .build("postgres://localhost/diesel_demo")
+//!     .unwrap();
+//! let connection = pool.get().unwrap();
+//! // `connection` derefs to a `PgConnection`, and is returned to the pool
+//! // when dropped.
+//! ```
+use std::cmp;
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Weak, Mutex, Condvar};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use connection::Connection;
+use result::{ConnectionResult, PoolError, PoolResult};
+
+// The reaper never checks for idle connections more often than this, so that
+// a very short idle timeout doesn't make it spin
+const MIN_REAP_INTERVAL_MS: u64 = 100;
+
+/// Configures and creates a [`Pool`](struct.Pool.html). Created with
+/// `Pool::builder()`.
+pub struct PoolBuilder<C> {
+    config: PoolConfig,
+    _marker: PhantomData<C>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PoolConfig {
+    min_size: usize,
+    max_size: usize,
+    checkout_timeout: Duration,
+    idle_timeout: Option<Duration>,
+}
+
+impl<C> PoolBuilder<C> where
+    C: Connection + Send + 'static,
+{
+    /// The number of connections which are established when the pool is
+    /// created, and which will never be closed for being idle. Defaults to 1.
+    pub fn min_size(mut self, min_size: usize) -> Self {
+        self.config.min_size = min_size;
+        self
+    }
+
+    /// The maximum number of connections the pool will have open at once.
+    /// Defaults to 10.
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.config.max_size = max_size;
+        self
+    }
+
+    /// How long `Pool::get` will wait for a connection to become available,
+    /// including the time taken to establish a new one, before returning
+    /// `PoolError::Timeout`. A connection which is still being established
+    /// when the timeout elapses is added to the pool once it has been.
+    /// Defaults to 30 seconds.
+    pub fn checkout_timeout(mut self, timeout: Duration) -> Self {
+        self.config.checkout_timeout = timeout;
+        self
+    }
+
+    /// Connections which have been sitting in the pool unused for longer than
+    /// this will be closed, as long as more than `min_size` connections are
+    /// open. `None` keeps connections open forever. Defaults to 10 minutes.
+    ///
+    /// Idle connections are checked for every half of the timeout, but no
+    /// more often than every 100 milliseconds.
+    pub fn idle_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.config.idle_timeout = timeout;
+        self
+    }
+
+    /// Creates the pool, establishing `min_size` connections to the database
+    /// at `database_url` up front.
+    ///
+    /// # Panics
+    ///
+    /// If `max_size` is 0, or `min_size` is greater than `max_size`.
+    pub fn build(self, database_url: &str) -> ConnectionResult<Pool<C>> {
+        let config = self.config;
+        assert!(config.max_size > 0, "max_size must be greater than 0");
+        assert!(config.min_size <= config.max_size, "min_size must not be greater than max_size");
+
+        let mut idle = Vec::with_capacity(config.max_size);
+        for _ in 0..config.min_size {
+            idle.push(IdleConnection::new(try!(C::establish(database_url))));
+        }
+        let inner = Arc::new(PoolInner {
+            database_url: database_url.to_string(),
+            config: config,
+            state: Mutex::new(PoolInternalState {
+                idle: idle,
+                num_connections: config.min_size,
+            }),
+            connection_returned: Condvar::new(),
+            reaper_signal: Arc::new(ReaperSignal {
+                pool_dropped: Mutex::new(false),
+                wake: Condvar::new(),
+            }),
+        });
+
+        if let Some(idle_timeout) = config.idle_timeout {
+            spawn_reaper(Arc::downgrade(&inner), inner.reaper_signal.clone(), idle_timeout);
+        }
+
+        Ok(Pool { inner: inner })
+    }
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        PoolConfig {
+            min_size: 1,
+            max_size: 10,
+            checkout_timeout: Duration::from_secs(30),
+            idle_timeout: Some(Duration::from_secs(600)),
+        }
+    }
+}
+
+/// A pool of connections to a single database. Cloning the pool is cheap,
+/// and the clones share the same connections.
+///
+/// Connections are health checked with a trivial query when they are checked
+/// out, and are replaced transparently if they have stopped working.
+pub struct Pool<C> {
+    inner: Arc<PoolInner<C>>,
+}
+
+/// A snapshot of the number of connections in a pool, as returned by
+/// `Pool::state`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolState {
+    /// The number of connections which are currently open, including ones
+    /// which are checked out.
+    pub connections: usize,
+    /// The number of open connections which are not checked out.
+    pub idle_connections: usize,
+}
+
+struct PoolInner<C> {
+    database_url: String,
+    config: PoolConfig,
+    state: Mutex<PoolInternalState<C>>,
+    connection_returned: Condvar,
+    reaper_signal: Arc<ReaperSignal>,
+}
+
+/// Wakes the reaper thread up when the pool is dropped, so that it exits
+/// right away rather than after its next sleep.
+struct ReaperSignal {
+    pool_dropped: Mutex<bool>,
+    wake: Condvar,
+}
+
+/// A connection being established for `Pool::get` on another thread.
+enum Establishing<C> {
+    Waiting,
+    Done(ConnectionResult<C>),
+    // `Pool::get` timed out, so the connection is added to the pool instead
+    Abandoned,
+}
+
+struct PoolInternalState<C> {
+    // Connections are checked out from the end, so the ones which have been
+    // idle the longest are at the front.
+    idle: Vec<IdleConnection<C>>,
+    num_connections: usize,
+}
+
+struct IdleConnection<C> {
+    conn: C,
+    idle_since: Instant,
+}
+
+impl<C> IdleConnection<C> {
+    fn new(conn: C) -> Self {
+        IdleConnection {
+            conn: conn,
+            idle_since: Instant::now(),
+        }
+    }
+}
+
+impl<C> Clone for Pool<C> {
+    fn clone(&self) -> Self {
+        Pool { inner: self.inner.clone() }
+    }
+}
+
+impl<C> Pool<C> where
+    C: Connection + Send + 'static,
+{
+    /// Creates a pool with the default configuration.
+    pub fn new(database_url: &str) -> ConnectionResult<Self> {
+        Self::builder().build(database_url)
+    }
+
+    /// Returns a builder with the default configuration, used to create a
+    /// pool with custom settings.
+    pub fn builder() -> PoolBuilder<C> {
+        PoolBuilder {
+            config: PoolConfig::default(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Checks a connection out of the pool, establishing a new one if none
+    /// are idle and the pool is not at its maximum size. Otherwise waits for
+    /// another thread to return a connection, until the checkout timeout has
+    /// elapsed.
+    pub fn get(&self) -> PoolResult<PooledConnection<C>> {
+        let deadline = Instant::now() + self.inner.config.checkout_timeout;
+        let mut state = self.inner.state.lock().unwrap();
+        loop {
+            if let Some(idle) = state.idle.pop() {
+                drop(state);
+                if idle.conn.batch_execute("SELECT 1").is_ok() {
+                    return Ok(PooledConnection::new(self.inner.clone(), idle.conn));
+                }
+                drop(idle);
+                state = self.inner.state.lock().unwrap();
+                state.num_connections -= 1;
+                continue;
+            }
+
+            if state.num_connections < self.inner.config.max_size {
+                state.num_connections += 1;
+                drop(state);
+                return self.establish(deadline)
+                    .map(|conn| PooledConnection::new(self.inner.clone(), conn));
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(PoolError::Timeout);
+            }
+            state = self.inner.connection_returned.wait_timeout(state, deadline - now)
+                .unwrap().0;
+        }
+    }
+
+    /// Establishes a new connection, which has already been counted towards
+    /// the size of the pool. This happens on another thread, so that it can
+    /// be given up on once `deadline` has passed.
+    fn establish(&self, deadline: Instant) -> PoolResult<C> {
+        let pending = Arc::new((Mutex::new(Establishing::Waiting), Condvar::new()));
+        let inner = self.inner.clone();
+        let establishing = pending.clone();
+        thread::spawn(move || {
+            let result = C::establish(&inner.database_url);
+            let mut state = establishing.0.lock().unwrap();
+            match *state {
+                Establishing::Abandoned => match result {
+                    Ok(conn) => inner.return_connection(conn),
+                    Err(_) => inner.forget_connection(),
+                },
+                _ => {
+                    *state = Establishing::Done(result);
+                    establishing.1.notify_one();
+                }
+            }
+        });
+
+        let mut state = pending.0.lock().unwrap();
+        loop {
+            match mem::replace(&mut *state, Establishing::Waiting) {
+                Establishing::Done(Ok(conn)) => return Ok(conn),
+                Establishing::Done(Err(e)) => {
+                    self.inner.forget_connection();
+                    return Err(PoolError::ConnectionError(e));
+                }
+                _ => {},
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                *state = Establishing::Abandoned;
+                return Err(PoolError::Timeout);
+            }
+            state = pending.1.wait_timeout(state, deadline - now).unwrap().0;
+        }
+    }
+
+    /// Returns the number of open and idle connections in the pool.
+    pub fn state(&self) -> PoolState {
+        let state = self.inner.state.lock().unwrap();
+        PoolState {
+            connections: state.num_connections,
+            idle_connections: state.idle.len(),
+        }
+    }
+}
+
+impl<C> PoolInner<C> {
+    fn return_connection(&self, conn: C) {
+        self.state.lock().unwrap().idle.push(IdleConnection::new(conn));
+        self.connection_returned.notify_one();
+    }
+
+    /// Stops counting a connection which has been closed, or which failed to
+    /// be established.
+    fn forget_connection(&self) {
+        self.state.lock().unwrap().num_connections -= 1;
+        self.connection_returned.notify_one();
+    }
+
+    fn reap_idle_connections(&self, idle_timeout: Duration) {
+        let reaped = {
+            let mut state = self.state.lock().unwrap();
+            let reapable = state.num_connections.saturating_sub(self.config.min_size);
+            let expired = state.idle.iter()
+                .take(reapable)
+                .take_while(|idle| idle.idle_since.elapsed() >= idle_timeout)
+                .count();
+            state.num_connections -= expired;
+            state.idle.drain(..expired).collect::<Vec<_>>()
+        };
+        // Closing connections can block, so it happens after the lock is
+        // released
+        drop(reaped);
+    }
+}
+
+impl<C> Drop for PoolInner<C> {
+    fn drop(&mut self) {
+        *self.reaper_signal.pool_dropped.lock().unwrap() = true;
+        self.reaper_signal.wake.notify_one();
+    }
+}
+
+fn spawn_reaper<C>(pool: Weak<PoolInner<C>>, signal: Arc<ReaperSignal>, idle_timeout: Duration) where
+    C: Send + 'static,
+{
+    let interval = cmp::max(idle_timeout / 2, Duration::from_millis(MIN_REAP_INTERVAL_MS));
+    thread::spawn(move || {
+        loop {
+            // The lock is released before reaping, as the pool is dropped on
+            // this thread if it goes away while it is being reaped
+            {
+                let pool_dropped = signal.pool_dropped.lock().unwrap();
+                let pool_dropped = if *pool_dropped {
+                    pool_dropped
+                } else {
+                    signal.wake.wait_timeout(pool_dropped, interval).unwrap().0
+                };
+                if *pool_dropped {
+                    break;
+                }
+            }
+            match pool.upgrade() {
+                Some(pool) => pool.reap_idle_connections(idle_timeout),
+                None => break,
+            }
+        }
+    });
+}
+
+/// A connection checked out from a [`Pool`](struct.Pool.html). Derefs to the
+/// underlying connection, and returns it to the pool when dropped.
+///
+/// Connections which are dropped while a transaction is still open (for
+/// example, because a thread panicked part way through one) are closed rather
+/// than returned, as they can't safely be handed to anyone else.
+pub struct PooledConnection<C: Connection> {
+    pool: Arc<PoolInner<C>>,
+    conn: Option<C>,
+}
+
+impl<C: Connection> PooledConnection<C> {
+    fn new(pool: Arc<PoolInner<C>>, conn: C) -> Self {
+        PooledConnection {
+            pool: pool,
+            conn: Some(conn),
+        }
+    }
+}
+
+impl<C: Connection> Deref for PooledConnection<C> {
+    type Target = C;
+
+    fn deref(&self) -> &C {
+        self.conn.as_ref().unwrap()
+    }
+}
+
+impl<C: Connection> DerefMut for PooledConnection<C> {
+    fn deref_mut(&mut self) -> &mut C {
+        self.conn.as_mut().unwrap()
+    }
+}
+
+impl<C: Connection> Drop for PooledConnection<C> {
+    fn drop(&mut self) {
+        let conn = self.conn.take().unwrap();
+        if conn.get_transaction_depth() == 0 {
+            self.pool.return_connection(conn);
+        } else {
+            drop(conn);
+            self.pool.forget_connection();
+        }
+    }
+}
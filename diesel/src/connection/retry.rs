@@ -0,0 +1,89 @@
+use std::cmp;
+use std::thread;
+use std::time::Duration;
+
+use result::{Error, DatabaseErrorKind, TransactionError, TransactionResult};
+
+/// Determines how often
+/// [`Connection::transaction_with_retry`](trait.Connection.html#method.transaction_with_retry)
+/// will run a transaction which failed with a serialization failure or
+/// deadlock, and how long it waits in between.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Runs the transaction at most `max_attempts` times, including the first
+    /// attempt.
+    ///
+    /// # Panics
+    ///
+    /// If `max_attempts` is 0.
+    pub fn new(max_attempts: u32) -> Self {
+        assert!(max_attempts > 0, "max_attempts must be greater than 0");
+        RetryPolicy {
+            max_attempts: max_attempts,
+            backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_secs(1),
+        }
+    }
+
+    /// How long to wait before the first retry. The wait doubles after each
+    /// subsequent failed attempt, up to [`max_backoff`](#method.max_backoff).
+    /// Defaults to 10 milliseconds.
+    pub fn backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// The longest time to wait between two attempts, no matter how many
+    /// have failed. Defaults to 1 second.
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+}
+
+impl Default for RetryPolicy {
+    /// Runs the transaction at most 3 times.
+    fn default() -> Self {
+        RetryPolicy::new(3)
+    }
+}
+
+pub fn retry_transaction<T, F>(policy: RetryPolicy, transaction_depth: i32, mut run: F)
+    -> TransactionResult<T, Error> where
+        F: FnMut() -> TransactionResult<T, Error>,
+{
+    // Inside of a savepoint, the outer transaction has already failed, so
+    // only the outermost transaction can be retried
+    let max_attempts = if transaction_depth == 0 { policy.max_attempts } else { 1 };
+    let mut backoff = cmp::min(policy.backoff, policy.max_backoff);
+    let mut attempt = 1;
+    loop {
+        match run() {
+            Err(ref e) if attempt < max_attempts && is_retryable(e) => {},
+            result => return result,
+        }
+        thread::sleep(backoff);
+        backoff = backoff.checked_mul(2)
+            .map(|doubled| cmp::min(doubled, policy.max_backoff))
+            .unwrap_or(policy.max_backoff);
+        attempt += 1;
+    }
+}
+
+fn is_retryable(error: &TransactionError<Error>) -> bool {
+    let error = match *error {
+        TransactionError::CouldntCreateTransaction(ref e) => e,
+        TransactionError::UserReturnedError(ref e) => e,
+    };
+    match *error {
+        Error::DatabaseError(DatabaseErrorKind::SerializationFailure, _) => true,
+        Error::DatabaseError(DatabaseErrorKind::Deadlock, _) => true,
+        _ => false,
+    }
+}
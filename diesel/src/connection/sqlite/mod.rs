@@ -0,0 +1,141 @@
+mod raw;
+mod sqlite_value;
+mod statement_iterator;
+mod stmt;
+
+pub use self::sqlite_value::{SqliteValue, SqliteRow};
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use backend::Sqlite;
+use query_builder::{AsQuery, QueryFragment};
+use query_builder::sqlite::SqliteQueryBuilder;
+use query_source::Queryable;
+use result::*;
+use self::raw::RawConnection;
+use self::statement_iterator::StatementIterator;
+use self::stmt::Statement;
+use super::{SimpleConnection, Connection};
+use types::HasSqlType;
+
+/// The connection string expected by `SqliteConnection::establish` should be
+/// a path to the database file, which will be created if it does not exist.
+/// `":memory:"` can be given to use a private, in-memory database.
+pub struct SqliteConnection {
+    raw_connection: Rc<RawConnection>,
+    transaction_depth: Cell<i32>,
+}
+
+impl SimpleConnection for SqliteConnection {
+    fn batch_execute(&self, query: &str) -> QueryResult<()> {
+        self.raw_connection.exec(query)
+    }
+}
+
+impl Connection for SqliteConnection {
+    type Backend = Sqlite;
+
+    fn establish(database_url: &str) -> ConnectionResult<Self> {
+        RawConnection::establish(database_url).map(|conn| {
+            SqliteConnection {
+                raw_connection: Rc::new(conn),
+                transaction_depth: Cell::new(0),
+            }
+        })
+    }
+
+    fn execute(&self, query: &str) -> QueryResult<usize> {
+        try!(self.batch_execute(query));
+        Ok(self.raw_connection.rows_affected_by_last_query())
+    }
+
+    fn query_all<'a, T, U: 'a>(&self, source: T) -> QueryResult<Box<Iterator<Item=U> + 'a>> where
+        T: AsQuery,
+        T::Query: QueryFragment<Sqlite>,
+        T::SqlType: 'static,
+        Sqlite: HasSqlType<T::SqlType>,
+        U: Queryable<T::SqlType, Sqlite>,
+    {
+        let stmt = try!(self.prepare_query(&source.as_query()));
+        let rows = try!(StatementIterator::new(stmt).collect::<QueryResult<Vec<U>>>());
+        Ok(Box::new(rows.into_iter()))
+    }
+
+    fn stream_query<'a, T, U: 'a>(&'a self, source: T) -> QueryResult<Box<Iterator<Item=QueryResult<U>> + 'a>> where
+        T: AsQuery,
+        T::Query: QueryFragment<Sqlite>,
+        T::SqlType: 'static,
+        Sqlite: HasSqlType<T::SqlType>,
+        U: Queryable<T::SqlType, Sqlite>,
+    {
+        self.prepare_query(&source.as_query())
+            .map(|stmt| Box::new(StatementIterator::new(stmt)) as Box<Iterator<Item=QueryResult<U>>>)
+    }
+
+    fn execute_returning_count<T>(&self, source: &T) -> QueryResult<usize> where
+        T: QueryFragment<Sqlite>,
+    {
+        let mut statement = try!(self.prepare_query(source));
+        try!(statement.run());
+        Ok(statement.rows_affected())
+    }
+
+    fn silence_notices<F: FnOnce() -> T, T>(&self, f: F) -> T {
+        f()
+    }
+
+    fn begin_transaction(&self) -> QueryResult<()> {
+        let transaction_depth = self.transaction_depth.get();
+        self.change_transaction_depth(1, if transaction_depth == 0 {
+            self.execute("BEGIN")
+        } else {
+            self.execute(&format!("SAVEPOINT diesel_savepoint_{}", transaction_depth))
+        })
+    }
+
+    fn rollback_transaction(&self) -> QueryResult<()> {
+        let transaction_depth = self.transaction_depth.get();
+        self.change_transaction_depth(-1, if transaction_depth == 1 {
+            self.execute("ROLLBACK")
+        } else {
+            self.execute(&format!("ROLLBACK TO SAVEPOINT diesel_savepoint_{}",
+                                  transaction_depth - 1))
+        })
+    }
+
+    fn commit_transaction(&self) -> QueryResult<()> {
+        let transaction_depth = self.transaction_depth.get();
+        self.change_transaction_depth(-1, if transaction_depth <= 1 {
+            self.execute("COMMIT")
+        } else {
+            self.execute(&format!("RELEASE SAVEPOINT diesel_savepoint_{}",
+                                  transaction_depth - 1))
+        })
+    }
+
+    fn get_transaction_depth(&self) -> i32 {
+        self.transaction_depth.get()
+    }
+}
+
+impl SqliteConnection {
+    fn prepare_query<T: QueryFragment<Sqlite>>(&self, source: &T) -> QueryResult<Statement> {
+        let mut query_builder = SqliteQueryBuilder::new();
+        try!(source.to_sql(&mut query_builder).map_err(|e| {
+            Error::QueryBuilderError(e.to_string().into())
+        }));
+        let mut statement = try!(Statement::prepare(&self.raw_connection, &query_builder.sql));
+        for (tpe, value) in query_builder.binds.into_iter() {
+            try!(statement.bind(tpe, value));
+        }
+        Ok(statement)
+    }
+
+    fn change_transaction_depth(&self, by: i32, query: QueryResult<usize>) -> QueryResult<()> {
+        if query.is_ok() {
+            self.transaction_depth.set(self.transaction_depth.get() + by);
+        }
+        query.map(|_| ())
+    }
+}
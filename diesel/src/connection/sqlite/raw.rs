@@ -0,0 +1,118 @@
+extern crate libsqlite3_sys as ffi;
+extern crate libc;
+
+use std::ffi::{CString, CStr};
+use std::{ptr, str};
+
+use result::*;
+
+// Extended result codes, from sqlite3.h
+const SQLITE_CONSTRAINT_FOREIGNKEY: libc::c_int = ffi::SQLITE_CONSTRAINT | (3 << 8);
+const SQLITE_CONSTRAINT_NOTNULL: libc::c_int = ffi::SQLITE_CONSTRAINT | (5 << 8);
+const SQLITE_CONSTRAINT_PRIMARYKEY: libc::c_int = ffi::SQLITE_CONSTRAINT | (6 << 8);
+const SQLITE_CONSTRAINT_UNIQUE: libc::c_int = ffi::SQLITE_CONSTRAINT | (8 << 8);
+
+pub struct RawConnection {
+    pub internal_connection: *mut ffi::sqlite3,
+}
+
+impl RawConnection {
+    pub fn establish(database_url: &str) -> ConnectionResult<Self> {
+        let mut conn_pointer = ptr::null_mut();
+        let database_url = try!(CString::new(database_url));
+        let connection_status = unsafe {
+            ffi::sqlite3_open_v2(
+                database_url.as_ptr(),
+                &mut conn_pointer,
+                ffi::SQLITE_OPEN_READWRITE | ffi::SQLITE_OPEN_CREATE,
+                ptr::null(),
+            )
+        };
+
+        match connection_status {
+            ffi::SQLITE_OK => Ok(RawConnection {
+                internal_connection: conn_pointer,
+            }),
+            err_code => {
+                let message = if conn_pointer.is_null() {
+                    format!("Unable to open SQLite database (error code {})", err_code)
+                } else {
+                    let message = last_error_message(conn_pointer);
+                    unsafe { ffi::sqlite3_close(conn_pointer) };
+                    message
+                };
+                Err(ConnectionError::BadConnection(message))
+            }
+        }
+    }
+
+    pub fn exec(&self, query: &str) -> QueryResult<()> {
+        let mut err_msg = ptr::null_mut();
+        let query = try!(CString::new(query));
+        let callback_fn = None;
+        let callback_arg = ptr::null_mut();
+        let result = unsafe {
+            ffi::sqlite3_exec(
+                self.internal_connection,
+                query.as_ptr(),
+                callback_fn,
+                callback_arg,
+                &mut err_msg,
+            )
+        };
+
+        if result == ffi::SQLITE_OK {
+            Ok(())
+        } else if err_msg.is_null() {
+            Err(self.last_error())
+        } else {
+            let msg = convert_to_string_and_free(err_msg);
+            Err(Error::DatabaseError(self.last_error_kind(), Box::new(msg)))
+        }
+    }
+
+    pub fn rows_affected_by_last_query(&self) -> usize {
+        unsafe { ffi::sqlite3_changes(self.internal_connection) as usize }
+    }
+
+    pub fn last_error_message(&self) -> String {
+        last_error_message(self.internal_connection)
+    }
+
+    pub fn last_error(&self) -> Error {
+        Error::DatabaseError(self.last_error_kind(), Box::new(self.last_error_message()))
+    }
+
+    fn last_error_kind(&self) -> DatabaseErrorKind {
+        match unsafe { ffi::sqlite3_extended_errcode(self.internal_connection) } {
+            SQLITE_CONSTRAINT_UNIQUE | SQLITE_CONSTRAINT_PRIMARYKEY =>
+                DatabaseErrorKind::UniqueViolation,
+            SQLITE_CONSTRAINT_FOREIGNKEY => DatabaseErrorKind::ForeignKeyViolation,
+            SQLITE_CONSTRAINT_NOTNULL => DatabaseErrorKind::NotNullViolation,
+            _ => DatabaseErrorKind::__Unknown,
+        }
+    }
+}
+
+impl Drop for RawConnection {
+    fn drop(&mut self) {
+        unsafe { ffi::sqlite3_close(self.internal_connection) };
+    }
+}
+
+pub fn last_error_message(conn: *mut ffi::sqlite3) -> String {
+    unsafe {
+        let error_ptr = ffi::sqlite3_errmsg(conn);
+        let bytes = CStr::from_ptr(error_ptr).to_bytes();
+        str::from_utf8_unchecked(bytes).to_string()
+    }
+}
+
+fn convert_to_string_and_free(err_msg: *const libc::c_char) -> String {
+    let msg = unsafe {
+        let bytes = CStr::from_ptr(err_msg).to_bytes();
+        str::from_utf8_unchecked(bytes).into()
+    };
+    unsafe { ffi::sqlite3_free(err_msg as *mut libc::c_void) };
+    msg
+}
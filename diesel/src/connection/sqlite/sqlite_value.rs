@@ -0,0 +1,95 @@
+extern crate libsqlite3_sys as ffi;
+extern crate libc;
+
+use std::{slice, str};
+
+use backend::Sqlite;
+use row::Row;
+
+/// The raw value of a single column, as handed to `FromSql` for
+/// [`Sqlite`](../../backend/struct.Sqlite.html). This type is only ever
+/// accessed by reference, and wraps an `sqlite3_value` owned by the statement
+/// that produced it.
+pub struct SqliteValue {
+    inner_value: ffi::sqlite3_value,
+}
+
+pub struct SqliteRow {
+    stmt: *mut ffi::sqlite3_stmt,
+    next_col_index: libc::c_int,
+}
+
+impl SqliteValue {
+    #[doc(hidden)]
+    pub unsafe fn new<'a>(inner: *mut ffi::sqlite3_value) -> &'a Self {
+        &*(inner as *const _ as *const Self)
+    }
+
+    pub fn read_text(&self) -> &str {
+        unsafe {
+            let ptr = ffi::sqlite3_value_text(self.value());
+            let len = ffi::sqlite3_value_bytes(self.value());
+            let bytes = slice::from_raw_parts(ptr as *const u8, len as usize);
+            str::from_utf8_unchecked(bytes)
+        }
+    }
+
+    pub fn read_blob(&self) -> &[u8] {
+        unsafe {
+            let ptr = ffi::sqlite3_value_blob(self.value());
+            let len = ffi::sqlite3_value_bytes(self.value());
+            slice::from_raw_parts(ptr as *const u8, len as usize)
+        }
+    }
+
+    pub fn read_integer(&self) -> i32 {
+        unsafe { ffi::sqlite3_value_int(self.value()) as i32 }
+    }
+
+    pub fn read_long(&self) -> i64 {
+        unsafe { ffi::sqlite3_value_int64(self.value()) as i64 }
+    }
+
+    pub fn read_double(&self) -> f64 {
+        unsafe { ffi::sqlite3_value_double(self.value()) as f64 }
+    }
+
+    fn value(&self) -> *mut ffi::sqlite3_value {
+        &self.inner_value as *const _ as *mut _
+    }
+}
+
+impl SqliteRow {
+    #[doc(hidden)]
+    pub fn new(inner_statement: *mut ffi::sqlite3_stmt) -> Self {
+        SqliteRow {
+            stmt: inner_statement,
+            next_col_index: 0,
+        }
+    }
+
+    fn column_is_null(&self, idx: libc::c_int) -> bool {
+        unsafe { ffi::sqlite3_column_type(self.stmt, idx) == ffi::SQLITE_NULL }
+    }
+}
+
+impl Row<Sqlite> for SqliteRow {
+    fn take(&mut self) -> Option<&SqliteValue> {
+        let current_idx = self.next_col_index;
+        self.next_col_index += 1;
+        if self.column_is_null(current_idx) {
+            None
+        } else {
+            unsafe {
+                let ptr = ffi::sqlite3_column_value(self.stmt, current_idx);
+                Some(SqliteValue::new(ptr))
+            }
+        }
+    }
+
+    fn next_is_null(&self, count: usize) -> bool {
+        (0..count as libc::c_int).all(|i| {
+            self.column_is_null(self.next_col_index + i)
+        })
+    }
+}
@@ -0,0 +1,39 @@
+use std::marker::PhantomData;
+
+use backend::Sqlite;
+use query_source::Queryable;
+use result::QueryResult;
+use row::deserialize_row;
+use super::stmt::Statement;
+use types::HasSqlType;
+
+/// Reads rows from the statement lazily, deserializing each one as the
+/// iterator is advanced.
+pub struct StatementIterator<ST, T> {
+    stmt: Statement,
+    _marker: PhantomData<(ST, T)>,
+}
+
+impl<ST, T> StatementIterator<ST, T> {
+    pub fn new(stmt: Statement) -> Self {
+        StatementIterator {
+            stmt: stmt,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<ST, T> Iterator for StatementIterator<ST, T> where
+    Sqlite: HasSqlType<ST>,
+    T: Queryable<ST, Sqlite>,
+{
+    type Item = QueryResult<T>;
+
+    fn next(&mut self) -> Option<QueryResult<T>> {
+        match self.stmt.step() {
+            Ok(Some(mut row)) => Some(deserialize_row(&mut row)),
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
@@ -0,0 +1,128 @@
+extern crate libsqlite3_sys as ffi;
+extern crate libc;
+extern crate byteorder;
+
+use self::byteorder::{ReadBytesExt, BigEndian};
+use std::error::Error as StdError;
+use std::ffi::CString;
+use std::ptr;
+use std::rc::Rc;
+
+use backend::SqliteType;
+use result::*;
+use super::raw::RawConnection;
+use super::sqlite_value::SqliteRow;
+
+pub struct Statement {
+    raw_connection: Rc<RawConnection>,
+    inner_statement: *mut ffi::sqlite3_stmt,
+    bind_index: libc::c_int,
+}
+
+impl Statement {
+    pub fn prepare(raw_connection: &Rc<RawConnection>, sql: &str) -> QueryResult<Self> {
+        let mut stmt = ptr::null_mut();
+        let mut unused_portion = ptr::null();
+        let sql = try!(CString::new(sql));
+        let prepare_result = unsafe {
+            ffi::sqlite3_prepare_v2(
+                raw_connection.internal_connection,
+                sql.as_ptr(),
+                -1,
+                &mut stmt,
+                &mut unused_portion,
+            )
+        };
+
+        if prepare_result == ffi::SQLITE_OK {
+            Ok(Statement {
+                raw_connection: raw_connection.clone(),
+                inner_statement: stmt,
+                bind_index: 0,
+            })
+        } else {
+            Err(raw_connection.last_error())
+        }
+    }
+
+    pub fn bind(&mut self, tpe: SqliteType, value: Option<Vec<u8>>) -> QueryResult<()> {
+        self.bind_index += 1;
+        let result = match value {
+            Some(bytes) => try!(self.bind_bytes(tpe, bytes)),
+            None => unsafe { ffi::sqlite3_bind_null(self.inner_statement, self.bind_index) },
+        };
+
+        if result == ffi::SQLITE_OK {
+            Ok(())
+        } else {
+            Err(self.raw_connection.last_error())
+        }
+    }
+
+    /// Binds are serialized by `ToSql` in the same binary representation we
+    /// send to PG, so we read them back out here to hand them to the
+    /// appropriate `sqlite3_bind_*` function.
+    fn bind_bytes(&self, tpe: SqliteType, bytes: Vec<u8>) -> QueryResult<libc::c_int> {
+        let stmt = self.inner_statement;
+        let idx = self.bind_index;
+        let mut slice = &bytes[..];
+        let result = match tpe {
+            SqliteType::Binary => unsafe {
+                ffi::sqlite3_bind_blob(stmt, idx, bytes.as_ptr() as *const libc::c_void,
+                    bytes.len() as libc::c_int, ffi::SQLITE_TRANSIENT())
+            },
+            SqliteType::Text => unsafe {
+                ffi::sqlite3_bind_text(stmt, idx, bytes.as_ptr() as *const libc::c_char,
+                    bytes.len() as libc::c_int, ffi::SQLITE_TRANSIENT())
+            },
+            SqliteType::Float => {
+                let value = try!(slice.read_f32::<BigEndian>().map_err(bind_error));
+                unsafe { ffi::sqlite3_bind_double(stmt, idx, value as libc::c_double) }
+            }
+            SqliteType::Double => {
+                let value = try!(slice.read_f64::<BigEndian>().map_err(bind_error));
+                unsafe { ffi::sqlite3_bind_double(stmt, idx, value as libc::c_double) }
+            }
+            SqliteType::SmallInt => {
+                let value = try!(slice.read_i16::<BigEndian>().map_err(bind_error));
+                unsafe { ffi::sqlite3_bind_int(stmt, idx, value as libc::c_int) }
+            }
+            SqliteType::Integer => {
+                let value = try!(slice.read_i32::<BigEndian>().map_err(bind_error));
+                unsafe { ffi::sqlite3_bind_int(stmt, idx, value as libc::c_int) }
+            }
+            SqliteType::Long => {
+                let value = try!(slice.read_i64::<BigEndian>().map_err(bind_error));
+                unsafe { ffi::sqlite3_bind_int64(stmt, idx, value) }
+            }
+        };
+        Ok(result)
+    }
+
+    pub fn step(&mut self) -> QueryResult<Option<SqliteRow>> {
+        match unsafe { ffi::sqlite3_step(self.inner_statement) } {
+            ffi::SQLITE_DONE => Ok(None),
+            ffi::SQLITE_ROW => Ok(Some(SqliteRow::new(self.inner_statement))),
+            _ => Err(self.raw_connection.last_error()),
+        }
+    }
+
+    pub fn run(&mut self) -> QueryResult<()> {
+        while try!(self.step()).is_some() {}
+        Ok(())
+    }
+
+    pub fn rows_affected(&self) -> usize {
+        self.raw_connection.rows_affected_by_last_query()
+    }
+}
+
+impl Drop for Statement {
+    fn drop(&mut self) {
+        unsafe { ffi::sqlite3_finalize(self.inner_statement) };
+    }
+}
+
+fn bind_error<E: StdError>(e: E) -> Error {
+    Error::QueryBuilderError(format!("Invalid bind parameter: {}", e).into())
+}
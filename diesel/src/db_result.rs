@@ -2,7 +2,7 @@ extern crate pq_sys;
 extern crate libc;
 
 use connection::pg::PgConnection;
-use result::{Error, QueryResult};
+use result::{Error, QueryResult, DatabaseErrorKind, DatabaseErrorInformation};
 use row::PgRow;
 
 use self::pq_sys::*;
@@ -17,12 +17,39 @@ impl PgResult {
     pub fn new(conn: &PgConnection, internal_result: *mut PGresult) -> QueryResult<Self> {
         let result_status = unsafe { PQresultStatus(internal_result) };
         match result_status {
-            PGRES_COMMAND_OK | PGRES_TUPLES_OK => {
+            PGRES_COMMAND_OK | PGRES_TUPLES_OK | PGRES_SINGLE_TUPLE => {
                 Ok(PgResult {
                     internal_result: internal_result,
                 })
             },
-            _ => Err(Error::DatabaseError(conn.last_error_message())),
+            PGRES_COPY_IN | PGRES_COPY_OUT => {
+                unsafe { PQclear(internal_result) };
+                conn.end_unexpected_copy(result_status == PGRES_COPY_IN);
+                Err(Error::UnexpectedCopy)
+            }
+            _ if conn.is_broken() => {
+                unsafe { PQclear(internal_result) };
+                Err(Error::ConnectionLost)
+            }
+            _ => {
+                let error_information = PgErrorInformation::new(conn, internal_result);
+                unsafe { PQclear(internal_result) };
+                let kind = error_information.sql_state.as_ref()
+                    .map(|state| DatabaseErrorKind::from_sql_state(state))
+                    .unwrap_or(DatabaseErrorKind::__Unknown);
+                Err(Error::DatabaseError(kind, Box::new(error_information)))
+            }
+        }
+    }
+
+    /// Like `new`, but also accepts the result of starting a `COPY`. Only
+    /// used by `copy_in` and `copy_out`, which go on to transfer the data.
+    pub fn new_copy(conn: &PgConnection, internal_result: *mut PGresult) -> QueryResult<Self> {
+        match unsafe { PQresultStatus(internal_result) } {
+            PGRES_COPY_IN | PGRES_COPY_OUT => Ok(PgResult {
+                internal_result: internal_result,
+            }),
+            _ => Self::new(conn, internal_result),
         }
     }
 
@@ -77,3 +104,87 @@ impl Drop for PgResult {
         unsafe { PQclear(self.internal_result) };
     }
 }
+
+/// The fields of an error reported by PG, copied out of the `PGresult` so
+/// that it can be freed.
+struct PgErrorInformation {
+    message: String,
+    sql_state: Option<String>,
+    details: Option<String>,
+    hint: Option<String>,
+    table_name: Option<String>,
+    column_name: Option<String>,
+    constraint_name: Option<String>,
+}
+
+// Field codes from postgres_ext.h
+pub const PG_DIAG_SEVERITY: libc::c_int = 'S' as libc::c_int;
+pub const PG_DIAG_SEVERITY_NONLOCALIZED: libc::c_int = 'V' as libc::c_int;
+pub const PG_DIAG_SQLSTATE: libc::c_int = 'C' as libc::c_int;
+pub const PG_DIAG_MESSAGE_PRIMARY: libc::c_int = 'M' as libc::c_int;
+pub const PG_DIAG_MESSAGE_DETAIL: libc::c_int = 'D' as libc::c_int;
+pub const PG_DIAG_MESSAGE_HINT: libc::c_int = 'H' as libc::c_int;
+const PG_DIAG_TABLE_NAME: libc::c_int = 't' as libc::c_int;
+const PG_DIAG_COLUMN_NAME: libc::c_int = 'c' as libc::c_int;
+const PG_DIAG_CONSTRAINT_NAME: libc::c_int = 'n' as libc::c_int;
+
+impl PgErrorInformation {
+    fn new(conn: &PgConnection, internal_result: *mut PGresult) -> Self {
+        let field = |field_code| error_field(internal_result, field_code);
+        PgErrorInformation {
+            // There is no result to read the message from if the connection
+            // was lost, or libpq ran out of memory
+            message: field(PG_DIAG_MESSAGE_PRIMARY)
+                .unwrap_or_else(|| conn.last_error_message()),
+            sql_state: field(PG_DIAG_SQLSTATE),
+            details: field(PG_DIAG_MESSAGE_DETAIL),
+            hint: field(PG_DIAG_MESSAGE_HINT),
+            table_name: field(PG_DIAG_TABLE_NAME),
+            column_name: field(PG_DIAG_COLUMN_NAME),
+            constraint_name: field(PG_DIAG_CONSTRAINT_NAME),
+        }
+    }
+}
+
+pub fn error_field(internal_result: *const PGresult, field_code: libc::c_int) -> Option<String> {
+    if internal_result.is_null() {
+        return None;
+    }
+    let ptr = unsafe { PQresultErrorField(internal_result, field_code) };
+    if ptr.is_null() {
+        None
+    } else {
+        let c_str = unsafe { CStr::from_ptr(ptr) };
+        Some(String::from_utf8_lossy(c_str.to_bytes()).into_owned())
+    }
+}
+
+impl DatabaseErrorInformation for PgErrorInformation {
+    fn message(&self) -> &str {
+        &self.message
+    }
+
+    fn sql_state(&self) -> Option<&str> {
+        self.sql_state.as_ref().map(|s| &**s)
+    }
+
+    fn details(&self) -> Option<&str> {
+        self.details.as_ref().map(|s| &**s)
+    }
+
+    fn hint(&self) -> Option<&str> {
+        self.hint.as_ref().map(|s| &**s)
+    }
+
+    fn table_name(&self) -> Option<&str> {
+        self.table_name.as_ref().map(|s| &**s)
+    }
+
+    fn column_name(&self) -> Option<&str> {
+        self.column_name.as_ref().map(|s| &**s)
+    }
+
+    fn constraint_name(&self) -> Option<&str> {
+        self.constraint_name.as_ref().map(|s| &**s)
+    }
+}
@@ -1,5 +1,5 @@
 use backend::Backend;
-use expression::{Expression, NonAggregate, SelectableExpression};
+use expression::{Expression, NonAggregate, SelectableExpression, ValidGrouping};
 use query_builder::*;
 use query_builder::nodes::{Identifier, InfixNode};
 use query_source::*;
@@ -52,3 +52,6 @@ impl<'a, T: Expression + Copy> QuerySource for Aliased<'a, T> {
 
 impl<'a, T> NonAggregate for Aliased<'a, T> where Aliased<'a, T>: Expression {
 }
+
+impl<'a, T, GB> ValidGrouping<GB> for Aliased<'a, T> {
+}
@@ -2,7 +2,7 @@ use std::marker::PhantomData;
 
 use backend::{Backend, Pg};
 use query_builder::*;
-use super::{AsExpression, Expression, SelectableExpression, NonAggregate};
+use super::{AsExpression, Expression, SelectableExpression, NonAggregate, ValidGrouping};
 use types::{Array, HasSqlType};
 
 /// Creates a PostgreSQL `ANY` expression.
@@ -88,3 +88,8 @@ impl<Expr, ST> NonAggregate for Any<Expr, ST> where
     Any<Expr, ST>: Expression,
 {
 }
+
+impl<Expr, ST, GB> ValidGrouping<GB> for Any<Expr, ST> where
+    Expr: ValidGrouping<GB>,
+{
+}
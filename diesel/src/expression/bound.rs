@@ -1,8 +1,9 @@
 use std::marker::PhantomData;
 
-use backend::Backend;
+use backend::{Backend, Pg};
+use persistable::CopyInValues;
 use query_builder::*;
-use super::{Expression, SelectableExpression, NonAggregate};
+use super::{Expression, SelectableExpression, NonAggregate, ValidGrouping};
 use types::{HasSqlType, ToSql, IsNull};
 
 #[derive(Debug, Clone, Copy)]
@@ -43,6 +44,20 @@ impl<T, U, DB> QueryFragment<DB> for Bound<T, U> where
     }
 }
 
+impl<T, U> CopyInValues for Bound<T, U> where
+    Pg: HasSqlType<T>,
+    U: ToSql<T, Pg>,
+{
+    fn push_copy_fields(&self, fields: &mut Vec<Option<Vec<u8>>>) -> BuildQueryResult {
+        let mut bytes = Vec::new();
+        match try!(self.item.to_sql(&mut bytes)) {
+            IsNull::Yes => fields.push(None),
+            IsNull::No => fields.push(Some(bytes)),
+        }
+        Ok(())
+    }
+}
+
 impl<T, U, QS> SelectableExpression<QS> for Bound<T, U> where
     Bound<T, U>: Expression,
 {
@@ -52,3 +67,6 @@ impl<T, U> NonAggregate for Bound<T, U> where
     Bound<T, U>: Expression,
 {
 }
+
+impl<T, U, GB> ValidGrouping<GB> for Bound<T, U> {
+}
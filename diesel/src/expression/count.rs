@@ -1,6 +1,6 @@
 use backend::Backend;
 use query_builder::*;
-use super::{Expression, SelectableExpression};
+use super::{Expression, SelectableExpression, ValidGrouping};
 use types::BigInt;
 
 /// Creates a SQL `COUNT` expression
@@ -67,6 +67,9 @@ impl<T: QueryFragment<DB>, DB: Backend> QueryFragment<DB> for Count<T> {
 impl<T: Expression, QS> SelectableExpression<QS> for Count<T> {
 }
 
+impl<T, G> ValidGrouping<G> for Count<T> {
+}
+
 #[derive(Debug, Clone, Copy)]
 #[doc(hidden)]
 pub struct CountStar;
@@ -84,3 +87,6 @@ impl<DB: Backend> QueryFragment<DB> for CountStar {
 
 impl<QS> SelectableExpression<QS> for CountStar {
 }
+
+impl<G> ValidGrouping<G> for CountStar {
+}
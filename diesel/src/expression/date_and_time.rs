@@ -1,5 +1,5 @@
 use backend::Backend;
-use expression::{Expression, SelectableExpression};
+use expression::{Expression, SelectableExpression, NonAggregate, ValidGrouping};
 use query_builder::*;
 use types::{Timestamp, VarChar};
 
@@ -42,3 +42,16 @@ impl<Ts, Tz, Qs> SelectableExpression<Qs> for AtTimeZone<Ts, Tz> where
     Ts: SelectableExpression<Qs>,
     Tz: SelectableExpression<Tz>,
 {}
+
+impl<Ts, Tz> NonAggregate for AtTimeZone<Ts, Tz> where
+    AtTimeZone<Ts, Tz>: Expression,
+    Ts: NonAggregate,
+    Tz: NonAggregate,
+{
+}
+
+impl<Ts, Tz, GB> ValidGrouping<GB> for AtTimeZone<Ts, Tz> where
+    Ts: ValidGrouping<GB>,
+    Tz: ValidGrouping<GB>,
+{
+}
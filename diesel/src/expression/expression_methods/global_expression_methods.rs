@@ -1,6 +1,9 @@
+use backend::Pg;
 use expression::{Expression, AsExpression, nullable};
 use expression::aliased::Aliased;
+use expression::array_comparison::{any, Any};
 use expression::predicates::*;
+use types::{Array, HasSqlType};
 
 pub trait ExpressionMethods: Expression + Sized {
     /// Alias an expression for use alongside
@@ -83,6 +86,42 @@ pub trait ExpressionMethods: Expression + Sized {
         NotEq::new(self, other.as_expression())
     }
 
+    /// Creates a PostgreSQL `= ANY(...)` expression, which is true when the
+    /// value is equal to any element of the given array. Instead of a Rust
+    /// collection, a query selecting a single column of the same type can be
+    /// given, in which case this is equivalent to `IN (SELECT ...)`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate diesel;
+    /// # include!("src/doctest_setup.rs");
+    /// #
+    /// # table! {
+    /// #     users {
+    /// #         id -> Serial,
+    /// #         name -> VarChar,
+    /// #     }
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     use self::users::dsl::*;
+    /// #     let connection = establish_connection();
+    /// let data = users.select(id).filter(name.eq_any(vec!["Sean", "Jim"]));
+    /// assert_eq!(Ok(vec![1]), data.load(&connection).map(Iterator::collect));
+    ///
+    /// let tess = users.select(name).filter(id.eq(2));
+    /// let data = users.select(id).filter(name.eq_any(tess));
+    /// assert_eq!(Ok(vec![2]), data.load(&connection).map(Iterator::collect));
+    /// # }
+    /// ```
+    fn eq_any<T>(self, values: T) -> Eq<Self, Any<T::Expression, Self::SqlType>> where
+        Pg: HasSqlType<Self::SqlType>,
+        T: AsExpression<Array<Self::SqlType>>,
+    {
+        Eq::new(self, any(values))
+    }
+
     /// Creates a SQL `IS NULL` expression.
     fn is_null(self) -> IsNull<Self> {
        IsNull::new(self)
@@ -1,5 +1,5 @@
 use backend::Backend;
-use expression::{Expression, SelectableExpression};
+use expression::{Expression, SelectableExpression, ValidGrouping};
 use query_builder::*;
 use types::{Foldable, HasSqlType};
 
@@ -44,6 +44,11 @@ macro_rules! fold_function {
             T: Expression<SqlType=ST>,
         {
         }
+
+        impl<T, G> ValidGrouping<G> for $type_name<T> {
+        }
+
+        generic_numeric_expr!($type_name, T);
     }
 }
 
@@ -1,5 +1,5 @@
 use backend::Backend;
-use expression::{Expression, SelectableExpression};
+use expression::{Expression, SelectableExpression, ValidGrouping};
 use query_builder::*;
 use types::{SqlOrd, HasSqlType};
 
@@ -38,6 +38,11 @@ macro_rules! ord_function {
 
         impl<T: Expression, QS> SelectableExpression<QS> for $type_name<T> {
         }
+
+        impl<T, G> ValidGrouping<G> for $type_name<T> {
+        }
+
+        generic_numeric_expr!($type_name, T);
     }
 }
 
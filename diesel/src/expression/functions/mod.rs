@@ -62,6 +62,13 @@ macro_rules! sql_function_body {
             $struct_name<$($arg_name),*>: $crate::expression::Expression,
         {
         }
+
+        #[allow(non_camel_case_types)]
+        impl<$($arg_name),*, GB> $crate::expression::ValidGrouping<GB>
+            for $struct_name<$($arg_name),*> where
+            $($arg_name: $crate::expression::ValidGrouping<GB>,)*
+        {
+        }
     }
 }
 
@@ -132,6 +139,9 @@ macro_rules! no_arg_sql_function_body {
 
         impl $crate::expression::NonAggregate for $type_name {
         }
+
+        impl<GB> $crate::expression::ValidGrouping<GB> for $type_name {
+        }
     }
 }
 
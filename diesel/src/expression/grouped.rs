@@ -1,5 +1,6 @@
 use backend::Backend;
-use expression::{Expression, SelectableExpression, NonAggregate};
+use expression::{Expression, SelectableExpression, NonAggregate, ValidGrouping};
+use persistable::CopyInValues;
 use query_builder::*;
 
 pub struct Grouped<T>(pub T);
@@ -17,6 +18,12 @@ impl<T: QueryFragment<DB>, DB: Backend> QueryFragment<DB> for Grouped<T> {
     }
 }
 
+impl<T: CopyInValues> CopyInValues for Grouped<T> {
+    fn push_copy_fields(&self, fields: &mut Vec<Option<Vec<u8>>>) -> BuildQueryResult {
+        self.0.push_copy_fields(fields)
+    }
+}
+
 impl<T, QS> SelectableExpression<QS> for Grouped<T> where
     T: SelectableExpression<QS>,
     Grouped<T>: Expression,
@@ -27,3 +34,8 @@ impl<T: NonAggregate> NonAggregate for Grouped<T> where
     Grouped<T>: Expression,
 {
 }
+
+impl<T, GB> ValidGrouping<GB> for Grouped<T> where
+    T: ValidGrouping<GB>,
+{
+}
@@ -36,4 +36,9 @@ pub type Between<Lhs, Rhs> = super::predicates::Between<Lhs,
 pub type NotBetween<Lhs, Rhs> = super::predicates::NotBetween<Lhs,
     super::predicates::And<AsExpr<Rhs, Lhs>, AsExpr<Rhs, Lhs>>>;
 
+pub type EqAny<Lhs, Rhs> = super::predicates::Eq<Lhs, super::array_comparison::Any<
+    <Rhs as AsExpression<types::Array<<Lhs as Expression>::SqlType>>>::Expression,
+    <Lhs as Expression>::SqlType,
+>>;
+
 pub use super::predicates::{IsNull, IsNotNull, Asc, Desc};
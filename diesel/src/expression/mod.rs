@@ -39,6 +39,10 @@ pub mod nullable;
 #[doc(hidden)]
 pub mod predicates;
 pub mod sql_literal;
+#[doc(hidden)]
+pub mod subselect;
+#[doc(hidden)]
+pub mod valid_grouping;
 
 /// Reexports various top level functions and core extensions that are too
 /// generic to export by default. This module exists to conveniently glob import
@@ -50,12 +54,14 @@ pub mod dsl {
     #[doc(inline)] pub use super::functions::aggregate_ordering::*;
     #[doc(inline)] pub use super::functions::aggregate_folding::*;
     #[doc(inline)] pub use super::sql_literal::sql;
+    #[doc(inline)] pub use super::subselect::{exists, not_exists, outer};
 
     pub use super::extensions::*;
 }
 
 pub use self::dsl::*;
 pub use self::sql_literal::SqlLiteral;
+pub use self::valid_grouping::ValidGrouping;
 
 use backend::Backend;
 
@@ -1,5 +1,5 @@
 use backend::Backend;
-use expression::{Expression, SelectableExpression, NonAggregate};
+use expression::{Expression, SelectableExpression, NonAggregate, ValidGrouping};
 use query_builder::*;
 use types::IntoNullable;
 
@@ -38,3 +38,8 @@ impl<T> NonAggregate for Nullable<T> where
     Nullable<T>: Expression,
 {
 }
+
+impl<T, GB> ValidGrouping<GB> for Nullable<T> where
+    T: ValidGrouping<GB>,
+{
+}
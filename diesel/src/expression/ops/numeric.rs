@@ -1,5 +1,5 @@
 use backend::Backend;
-use expression::{Expression, SelectableExpression, NonAggregate};
+use expression::{Expression, SelectableExpression, NonAggregate, ValidGrouping};
 use query_builder::*;
 use types;
 
@@ -53,6 +53,12 @@ macro_rules! numeric_operation {
         {
         }
 
+        impl<Lhs, Rhs, GB> ValidGrouping<GB> for $name<Lhs, Rhs> where
+            Lhs: ValidGrouping<GB>,
+            Rhs: ValidGrouping<GB>,
+        {
+        }
+
         generic_numeric_expr!($name, A, B);
     }
 }
@@ -47,6 +47,12 @@ macro_rules! infix_predicate_body {
             U: $crate::expression::NonAggregate,
         {
         }
+
+        impl<T, U, GB> $crate::expression::ValidGrouping<GB> for $name<T, U> where
+            T: $crate::expression::ValidGrouping<GB>,
+            U: $crate::expression::ValidGrouping<GB>,
+        {
+        }
     }
 }
 
@@ -117,6 +123,11 @@ macro_rules! postfix_predicate_body {
             T: $crate::expression::NonAggregate,
         {
         }
+
+        impl<T, GB> $crate::expression::ValidGrouping<GB> for $name<T> where
+            T: $crate::expression::ValidGrouping<GB>,
+        {
+        }
     }
 }
 
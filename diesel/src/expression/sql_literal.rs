@@ -1,7 +1,7 @@
 use backend::Backend;
 use query_builder::*;
 use std::marker::PhantomData;
-use super::{Expression, SelectableExpression, NonAggregate};
+use super::{Expression, SelectableExpression, NonAggregate, ValidGrouping};
 use types::HasSqlType;
 
 #[derive(Debug, Clone)]
@@ -30,6 +30,7 @@ impl<ST, DB> QueryFragment<DB> for SqlLiteral<ST> where
     DB: Backend + HasSqlType<ST>,
 {
     fn to_sql(&self, out: &mut DB::QueryBuilder) -> BuildQueryResult {
+        out.unsafe_to_cache_prepared();
         out.push_sql(&self.sql);
         Ok(())
     }
@@ -41,6 +42,9 @@ impl<QS, ST> SelectableExpression<QS> for SqlLiteral<ST> {
 impl<ST> NonAggregate for SqlLiteral<ST> {
 }
 
+impl<ST, GB> ValidGrouping<GB> for SqlLiteral<ST> {
+}
+
 pub fn sql<ST>(sql: &str) -> SqlLiteral<ST> {
     SqlLiteral::new(sql.into())
 }
@@ -0,0 +1,165 @@
+use backend::Backend;
+use query_builder::*;
+use query_source::Column;
+use super::{Expression, SelectableExpression, NonAggregate, ValidGrouping};
+use types::{Bool, IntoNullable};
+
+/// Creates a SQL `EXISTS` expression, which is true when the given query
+/// returns at least one row.
+///
+/// Columns of the outer query can be referenced in the subquery by wrapping
+/// them in [`outer`](fn.outer.html).
+///
+/// As with most bare functions, this is not exported by default. You can import
+/// it specifically from `diesel::expression::exists`, or glob import
+/// `diesel::expression::dsl::*`
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate diesel;
+/// # include!("src/doctest_setup.rs");
+/// # use diesel::expression::dsl::*;
+/// #
+/// # table! {
+/// #     users {
+/// #         id -> Serial,
+/// #         name -> VarChar,
+/// #     }
+/// # }
+/// #
+/// # fn main() {
+/// #     use self::users::dsl::*;
+/// #     let connection = establish_connection();
+/// let tess_exists = users.select(exists(users.filter(name.eq("Tess"))));
+/// assert_eq!(Ok(true), tess_exists.first(&connection));
+/// # }
+/// ```
+pub fn exists<T: AsQuery>(query: T) -> Exists<T::Query> {
+    Exists(query.as_query())
+}
+
+/// Creates a SQL `NOT EXISTS` expression, which is true when the given query
+/// returns no rows. See [`exists`](fn.exists.html) for details.
+pub fn not_exists<T: AsQuery>(query: T) -> NotExists<T::Query> {
+    NotExists(query.as_query())
+}
+
+/// Allows a column of an outer query to be referenced from a subquery, as in
+/// `posts.filter(user_id.eq(outer(users::id)))`. Whether the column's table
+/// is actually part of the outer query is not checked.
+///
+/// As with most bare functions, this is not exported by default. You can import
+/// it specifically from `diesel::expression::outer`, or glob import
+/// `diesel::expression::dsl::*`
+pub fn outer<C: Column>(column: C) -> Outer<C> {
+    Outer(column)
+}
+
+macro_rules! exists_expression {
+    ($name:ident, $sql:expr) => {
+        #[derive(Debug, Clone, Copy)]
+        #[doc(hidden)]
+        pub struct $name<T>(T);
+
+        impl<T: Query> Expression for $name<T> {
+            type SqlType = Bool;
+        }
+
+        impl<T, DB> QueryFragment<DB> for $name<T> where
+            DB: Backend,
+            T: QueryFragment<DB>,
+        {
+            fn to_sql(&self, out: &mut DB::QueryBuilder) -> BuildQueryResult {
+                out.push_sql($sql);
+                try!(self.0.to_sql(out));
+                out.push_sql(")");
+                Ok(())
+            }
+        }
+
+        impl<T: Query, QS> SelectableExpression<QS> for $name<T> {
+        }
+
+        impl<T: Query> NonAggregate for $name<T> {
+        }
+
+        impl<T, GB> ValidGrouping<GB> for $name<T> {
+        }
+    }
+}
+
+exists_expression!(Exists, "EXISTS (");
+exists_expression!(NotExists, "NOT EXISTS (");
+
+#[derive(Debug, Clone, Copy)]
+#[doc(hidden)]
+pub struct Outer<C>(C);
+
+impl<C: Column> Expression for Outer<C> {
+    type SqlType = C::SqlType;
+}
+
+impl<C, DB> QueryFragment<DB> for Outer<C> where
+    DB: Backend,
+    C: QueryFragment<DB>,
+{
+    fn to_sql(&self, out: &mut DB::QueryBuilder) -> BuildQueryResult {
+        self.0.to_sql(out)
+    }
+}
+
+impl<C: Column, QS> SelectableExpression<QS> for Outer<C> {
+}
+
+impl<C: Column> NonAggregate for Outer<C> {
+}
+
+impl<C, GB> ValidGrouping<GB> for Outer<C> {
+}
+
+/// A query used as a single value, constructed through
+/// [`SingleValueDsl`](../../query_dsl/trait.SingleValueDsl.html). Its SQL type
+/// is the nullable form of the selected column's, since the query may return
+/// no rows.
+#[derive(Debug, Clone, Copy)]
+#[doc(hidden)]
+pub struct Subselect<T>(T);
+
+impl<T> Subselect<T> {
+    pub fn new(query: T) -> Self {
+        Subselect(query)
+    }
+}
+
+impl<T> Expression for Subselect<T> where
+    T: Query,
+    T::SqlType: IntoNullable,
+{
+    type SqlType = <T::SqlType as IntoNullable>::Nullable;
+}
+
+impl<T, DB> QueryFragment<DB> for Subselect<T> where
+    DB: Backend,
+    T: QueryFragment<DB>,
+{
+    fn to_sql(&self, out: &mut DB::QueryBuilder) -> BuildQueryResult {
+        out.push_sql("(");
+        try!(self.0.to_sql(out));
+        out.push_sql(")");
+        Ok(())
+    }
+}
+
+impl<T, QS> SelectableExpression<QS> for Subselect<T> where
+    Subselect<T>: Expression,
+{
+}
+
+impl<T> NonAggregate for Subselect<T> where
+    Subselect<T>: Expression,
+{
+}
+
+impl<T, GB> ValidGrouping<GB> for Subselect<T> {
+}
@@ -0,0 +1,106 @@
+use query_source::Column;
+
+/// Indicates that an expression can be selected from a query with the given
+/// group by clause. With a group by clause, every column which is selected
+/// outside of an aggregate function must appear in the group by clause.
+/// Without one, the elements of a tuple can't mix aggregate and non-aggregate
+/// expressions. Mixing them within a single expression, as in
+/// `name.eq(max(name))`, is not checked.
+///
+/// Columns can only be checked against a group by clause which consists of
+/// columns from the same table as them. Aggregate functions,
+/// [`Bound`](bound/struct.Bound.html) values and raw SQL are valid for any
+/// group by clause. All other expressions inherit this from their children.
+pub trait ValidGrouping<GroupBy> {
+}
+
+impl<T: ValidGrouping<G> + ?Sized, G> ValidGrouping<G> for Box<T> {
+}
+
+impl<'a, T: ValidGrouping<G> + ?Sized, G> ValidGrouping<G> for &'a T {
+}
+
+/// Used to determine whether a column appears in a group by clause. `Output`
+/// will be either `Yes` or `No`. This is implemented for every pair of
+/// columns on the same table, by comparing their `GroupingIndex`, and for
+/// tuples of columns.
+pub trait GroupByContains<Col> {
+    type Output;
+}
+
+impl<Col, Other> GroupByContains<Other> for Col where
+    Col: Column + GroupingIndex,
+    Other: Column<Table=Col::Table> + GroupingIndex,
+    Col::Index: SameIndex<Other::Index>,
+{
+    type Output = <Col::Index as SameIndex<Other::Index>>::Output;
+}
+
+/// The position of a column in its table, as a type. `table!` implements this
+/// for every column, so that columns can be told apart without an impl for
+/// each pair of them.
+pub trait GroupingIndex {
+    type Index;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Zero;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Succ<N>(N);
+
+/// Is `Yes` if both indices are the same number, and `No` otherwise.
+pub trait SameIndex<Other> {
+    type Output;
+}
+
+impl SameIndex<Zero> for Zero {
+    type Output = Yes;
+}
+
+impl<N> SameIndex<Succ<N>> for Zero {
+    type Output = No;
+}
+
+impl<N> SameIndex<Zero> for Succ<N> {
+    type Output = No;
+}
+
+impl<N: SameIndex<M>, M> SameIndex<Succ<M>> for Succ<N> {
+    type Output = N::Output;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Yes;
+
+#[derive(Debug, Clone, Copy)]
+pub struct No;
+
+/// Is `Yes` if any element of a tuple of `Yes` and `No` is `Yes`.
+pub trait AnyYes {
+    type Output;
+}
+
+macro_rules! any_yes_impls {
+    () => {
+        impl AnyYes for () {
+            type Output = No;
+        }
+    };
+
+    ($first:ident, $($rest:ident,)*) => {
+        impl<$($rest),*> AnyYes for (Yes, $($rest,)*) {
+            type Output = Yes;
+        }
+
+        impl<$($rest),*> AnyYes for (No, $($rest,)*) where
+            ($($rest,)*): AnyYes,
+        {
+            type Output = <($($rest,)*) as AnyYes>::Output;
+        }
+
+        any_yes_impls!($($rest,)*);
+    };
+}
+
+any_yes_impls!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z,);
@@ -34,7 +34,10 @@ pub mod helper_types {
     //! `users.filter(first_name.eq("John")).order(last_name.asc()).limit(10)` would
     //! be `Limit<Order<FindBy<users, first_name, &str>, Asc<last_name>>>`
     use super::query_dsl::*;
+    use super::query_builder::AsQuery;
+    use super::query_builder::combination::{self, CombinedQuery};
     use super::expression::helper_types::Eq;
+    use super::expression::subselect::Subselect;
 
     /// Represents the return type of `.select(selection)`
     pub type Select<Source, Selection, Type = <Selection as super::Expression>::SqlType> =
@@ -52,14 +55,48 @@ pub mod helper_types {
     pub type Order<Source, Ordering> =
         <Source as OrderDsl<Ordering>>::Output;
 
+    /// Represents the return type of `.distinct()`
+    pub type Distinct<Source> = <Source as DistinctDsl>::Output;
+
+    /// Represents the return type of `.distinct_on(expr)`
+    pub type DistinctOn<Source, Expr> = <Source as DistinctOnDsl<Expr>>::Output;
+
+    /// Represents the return type of `.group_by(expr)`
+    pub type GroupBy<Source, Expr> = <Source as GroupByDsl<Expr>>::Output;
+
+    /// Represents the return type of `.having(predicate)`
+    pub type Having<Source, Predicate> = <Source as HavingDsl<Predicate>>::Output;
+
     /// Represents the return type of `.limit()`
     pub type Limit<Source> = <Source as LimitDsl>::Output;
 
     /// Represents the return type of `.offset()`
     pub type Offset<Source> = <Source as OffsetDsl>::Output;
 
+    /// Represents the return type of `.into_boxed()`
+    pub type IntoBoxed<'a, Source> = <Source as BoxedDsl<'a>>::Output;
+
+    /// Represents the return type of `.single_value()`
+    pub type SingleValue<Source> = Subselect<Limit<Source>>;
+
     /// Represents the return type of `.with(aliased_expr)`
     pub type With<'a, Source, Other> = <Source as WithDsl<'a, Other>>::Output;
+
+    /// Represents the return type of `.union(rhs)`
+    pub type Union<Lhs, Rhs> =
+        CombinedQuery<<Lhs as AsQuery>::Query, <Rhs as AsQuery>::Query, combination::Union>;
+
+    /// Represents the return type of `.union_all(rhs)`
+    pub type UnionAll<Lhs, Rhs> =
+        CombinedQuery<<Lhs as AsQuery>::Query, <Rhs as AsQuery>::Query, combination::UnionAll>;
+
+    /// Represents the return type of `.intersect(rhs)`
+    pub type Intersect<Lhs, Rhs> =
+        CombinedQuery<<Lhs as AsQuery>::Query, <Rhs as AsQuery>::Query, combination::Intersect>;
+
+    /// Represents the return type of `.except(rhs)`
+    pub type Except<Lhs, Rhs> =
+        CombinedQuery<<Lhs as AsQuery>::Query, <Rhs as AsQuery>::Query, combination::Except>;
 }
 
 pub mod prelude {
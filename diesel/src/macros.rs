@@ -37,6 +37,16 @@ macro_rules! column {
 
         impl $crate::expression::NonAggregate for $column_name {}
 
+        impl $crate::expression::ValidGrouping<
+            $crate::query_builder::group_by_clause::NoGroupByClause> for $column_name {}
+
+        impl<GB> $crate::expression::ValidGrouping<
+            $crate::query_builder::group_by_clause::GroupByClause<GB>> for $column_name where
+            GB: $crate::expression::valid_grouping::GroupByContains<
+                $column_name, Output=$crate::expression::valid_grouping::Yes>,
+        {
+        }
+
         impl $crate::query_source::Column for $column_name {
             type Table = $($table)::*;
 
@@ -47,6 +57,23 @@ macro_rules! column {
     }
 }
 
+#[macro_export]
+#[doc(hidden)]
+macro_rules! grouping_indices {
+    (index = $index:ty, columns = []) => {};
+
+    (index = $index:ty, columns = [$first:ident, $($rest:ident,)*]) => {
+        impl $crate::expression::valid_grouping::GroupingIndex for $first {
+            type Index = $index;
+        }
+
+        grouping_indices! {
+            index = $crate::expression::valid_grouping::Succ<$index>,
+            columns = [$($rest,)*]
+        }
+    };
+}
+
 /// Specifies that a table exists, and what columns it has. This will create a
 /// new public module, with the same name, as the name of the table. In this
 /// module, you'll find a unit struct named `table`, and a unit struct with the
@@ -247,6 +274,7 @@ macro_rules! table_body {
             pub mod columns {
                 use super::table;
                 use $crate::{Table, Column, Expression, SelectableExpression};
+                use $crate::expression::{NonAggregate, ValidGrouping};
                 use $crate::backend::Backend;
                 use $crate::query_builder::{QueryBuilder, BuildQueryResult, QueryFragment};
                 use $crate::types::*;
@@ -269,8 +297,18 @@ macro_rules! table_body {
 
                 impl SelectableExpression<table> for star {}
 
+                impl NonAggregate for star {}
+
+                impl ValidGrouping<$crate::query_builder::group_by_clause::NoGroupByClause>
+                    for star {}
+
                 $(column!(table, $column_name -> $Type);)+
                 $(column!(table, $no_select_column_name -> $no_select_type);)*
+
+                grouping_indices! {
+                    index = $crate::expression::valid_grouping::Zero,
+                    columns = [$($column_name,)+ $($no_select_column_name,)*]
+                }
             }
         }
     }
@@ -147,7 +147,7 @@ pub fn create_schema_migrations_table_if_needed<Conn: Connection>(conn: &Conn) -
     conn.silence_notices(|| {
         conn.execute("CREATE TABLE IF NOT EXISTS __diesel_schema_migrations (
             version VARCHAR PRIMARY KEY NOT NULL,
-            run_on TIMESTAMP NOT NULL DEFAULT NOW()
+            run_on TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
         )")
     })
 }
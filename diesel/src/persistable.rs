@@ -25,6 +25,15 @@ pub trait InsertableColumns<T: Table> {
     fn names(&self) -> String;
 }
 
+/// Encodes the values of a record given to
+/// [`PgConnection::copy_in`](../connection/pg/struct.PgConnection.html#method.copy_in)
+/// as the fields of a row in the binary `COPY` format. Only bound values can
+/// be encoded, since anything else would have to be evaluated by the server.
+#[doc(hidden)]
+pub trait CopyInValues {
+    fn push_copy_fields(&self, fields: &mut Vec<Option<Vec<u8>>>) -> BuildQueryResult;
+}
+
 impl<'a, T, U> Insertable<T> for &'a [U] where
     T: Table,
     &'a U: Insertable<T>,
@@ -83,6 +92,10 @@ impl<'a, T, U, DB> QueryFragment<DB> for InsertValues<'a, T, U> where
     <&'a U as Insertable<T>>::Values: QueryFragment<DB>,
 {
     fn to_sql(&self, out: &mut DB::QueryBuilder) -> BuildQueryResult {
+        if self.values.len() > 1 {
+            // The SQL differs for every number of records
+            out.unsafe_to_cache_prepared();
+        }
         for (i, record) in self.values.into_iter().enumerate() {
             if i != 0 {
                 out.push_sql(", ");
@@ -0,0 +1,190 @@
+use backend::{Backend, CombinationSyntax};
+use expression::{AsExpression, Expression};
+use expression::predicates::{Asc, Desc};
+use query_dsl::{LimitDsl, OrderDsl};
+use query_source::Column;
+use super::{Query, QueryBuilder, QueryFragment, BuildQueryResult};
+use super::limit_clause::{LimitClause, NoLimitClause};
+use super::order_clause::{NoOrderClause, OrderClause};
+use types::BigInt;
+
+/// Two queries combined with `UNION`, `UNION ALL`, `INTERSECT` or `EXCEPT`,
+/// which is the `Kind` of the combination. Constructed through
+/// [`CombineDsl`](../trait.CombineDsl.html).
+#[derive(Debug, Clone, Copy)]
+#[doc(hidden)]
+pub struct CombinedQuery<Lhs, Rhs, Kind, Order = NoOrderClause, Limit = NoLimitClause> {
+    lhs: Lhs,
+    rhs: Rhs,
+    kind: Kind,
+    order: Order,
+    limit: Limit,
+}
+
+impl<Lhs, Rhs, Kind> CombinedQuery<Lhs, Rhs, Kind> {
+    pub fn new(lhs: Lhs, rhs: Rhs, kind: Kind) -> Self {
+        CombinedQuery {
+            lhs: lhs,
+            rhs: rhs,
+            kind: kind,
+            order: NoOrderClause,
+            limit: NoLimitClause,
+        }
+    }
+}
+
+impl<Lhs, Rhs, Kind, O, L> Query for CombinedQuery<Lhs, Rhs, Kind, O, L> where
+    Lhs: Query,
+    Rhs: Query<SqlType=Lhs::SqlType>,
+{
+    type SqlType = Lhs::SqlType;
+}
+
+impl<Lhs, Rhs, Kind, O, L, DB> QueryFragment<DB> for CombinedQuery<Lhs, Rhs, Kind, O, L> where
+    DB: Backend + CombinationSyntax,
+    Lhs: QueryFragment<DB>,
+    Rhs: QueryFragment<DB>,
+    Kind: QueryFragment<DB>,
+    O: QueryFragment<DB>,
+    L: QueryFragment<DB>,
+{
+    fn to_sql(&self, out: &mut DB::QueryBuilder) -> BuildQueryResult {
+        try!(combined_query_to_sql::<_, DB>(&self.lhs, out));
+        try!(self.kind.to_sql(out));
+        try!(combined_query_to_sql::<_, DB>(&self.rhs, out));
+        try!(self.order.to_sql(out));
+        self.limit.to_sql(out)
+    }
+}
+
+/// The queries are wrapped in parentheses, so that they can have their own
+/// order and limit, except on SQLite which doesn't allow that.
+fn combined_query_to_sql<T, DB>(query: &T, out: &mut DB::QueryBuilder) -> BuildQueryResult where
+    DB: Backend + CombinationSyntax,
+    T: QueryFragment<DB>,
+{
+    if DB::parenthesize_combined_queries() {
+        out.push_sql("(");
+        try!(query.to_sql(out));
+        out.push_sql(")");
+        Ok(())
+    } else {
+        query.to_sql(out)
+    }
+}
+
+macro_rules! combination_kind {
+    ($name:ident, $sql:expr) => {
+        #[derive(Debug, Clone, Copy)]
+        pub struct $name;
+
+        impl<DB: Backend> QueryFragment<DB> for $name {
+            fn to_sql(&self, out: &mut DB::QueryBuilder) -> BuildQueryResult {
+                out.push_sql($sql);
+                Ok(())
+            }
+        }
+    }
+}
+
+combination_kind!(Union, " UNION ");
+combination_kind!(UnionAll, " UNION ALL ");
+combination_kind!(Intersect, " INTERSECT ");
+combination_kind!(Except, " EXCEPT ");
+
+/// The order clause of a combined query can only refer to the columns of its
+/// result by their names, so columns are written without their table name.
+/// Columns can be ordered by directly, with `.asc()` or `.desc()` applied,
+/// or in tuples of those. Whether the columns are part of the result is not
+/// checked.
+pub trait CombinationOrdering {
+    fn to_unqualified_sql<DB: Backend>(&self, out: &mut DB::QueryBuilder) -> BuildQueryResult;
+}
+
+impl<C: Column> CombinationOrdering for C {
+    fn to_unqualified_sql<DB: Backend>(&self, out: &mut DB::QueryBuilder) -> BuildQueryResult {
+        out.push_identifier(C::name())
+    }
+}
+
+impl<C: Column> CombinationOrdering for Asc<C> {
+    fn to_unqualified_sql<DB: Backend>(&self, out: &mut DB::QueryBuilder) -> BuildQueryResult {
+        try!(out.push_identifier(C::name()));
+        out.push_sql(" ASC");
+        Ok(())
+    }
+}
+
+impl<C: Column> CombinationOrdering for Desc<C> {
+    fn to_unqualified_sql<DB: Backend>(&self, out: &mut DB::QueryBuilder) -> BuildQueryResult {
+        try!(out.push_identifier(C::name()));
+        out.push_sql(" DESC");
+        Ok(())
+    }
+}
+
+macro_rules! tuple_combination_orderings {
+    ($($T:ident: $idx:tt),+) => {
+        impl<$($T: CombinationOrdering),+> CombinationOrdering for ($($T,)+) {
+            fn to_unqualified_sql<DB: Backend>(&self, out: &mut DB::QueryBuilder)
+                -> BuildQueryResult
+            {
+                $(
+                    if $idx != 0 {
+                        out.push_sql(", ");
+                    }
+                    try!(self.$idx.to_unqualified_sql::<DB>(out));
+                )+
+                Ok(())
+            }
+        }
+    }
+}
+
+tuple_combination_orderings!(A: 0, B: 1);
+tuple_combination_orderings!(A: 0, B: 1, C: 2);
+tuple_combination_orderings!(A: 0, B: 1, C: 2, D: 3);
+
+#[derive(Debug, Clone, Copy)]
+pub struct Unqualified<T>(T);
+
+impl<T: CombinationOrdering, DB: Backend> QueryFragment<DB> for Unqualified<T> {
+    fn to_sql(&self, out: &mut DB::QueryBuilder) -> BuildQueryResult {
+        self.0.to_unqualified_sql::<DB>(out)
+    }
+}
+
+impl<Lhs, Rhs, Kind, O, L, Expr> OrderDsl<Expr> for CombinedQuery<Lhs, Rhs, Kind, O, L> where
+    Expr: Expression + CombinationOrdering,
+    CombinedQuery<Lhs, Rhs, Kind, OrderClause<Unqualified<Expr>>, L>: Query,
+{
+    type Output = CombinedQuery<Lhs, Rhs, Kind, OrderClause<Unqualified<Expr>>, L>;
+
+    fn order(self, expr: Expr) -> Self::Output {
+        CombinedQuery {
+            lhs: self.lhs,
+            rhs: self.rhs,
+            kind: self.kind,
+            order: OrderClause(Unqualified(expr)),
+            limit: self.limit,
+        }
+    }
+}
+
+type Limit = <i64 as AsExpression<BigInt>>::Expression;
+
+impl<Lhs, Rhs, Kind, O, L> LimitDsl for CombinedQuery<Lhs, Rhs, Kind, O, L> where
+    CombinedQuery<Lhs, Rhs, Kind, O, LimitClause<Limit>>: Query,
+{
+    type Output = CombinedQuery<Lhs, Rhs, Kind, O, LimitClause<Limit>>;
+
+    fn limit(self, limit: i64) -> Self::Output {
+        CombinedQuery {
+            lhs: self.lhs,
+            rhs: self.rhs,
+            kind: self.kind,
+            order: self.order,
+            limit: LimitClause(AsExpression::<BigInt>::as_expression(limit)),
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use backend::{Backend, Pg};
+use expression::predicates::{Asc, Desc};
+use super::{QueryFragment, QueryBuilder, BuildQueryResult};
+use super::order_clause::{NoOrderClause, OrderClause};
+
+#[derive(Debug, Clone, Copy)]
+pub struct NoDistinctClause;
+
+impl<DB: Backend> QueryFragment<DB> for NoDistinctClause {
+    fn to_sql(&self, _out: &mut DB::QueryBuilder) -> BuildQueryResult {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DistinctClause;
+
+impl<DB: Backend> QueryFragment<DB> for DistinctClause {
+    fn to_sql(&self, out: &mut DB::QueryBuilder) -> BuildQueryResult {
+        out.push_sql("DISTINCT ");
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DistinctOnClause<Expr>(pub Expr);
+
+impl<Expr: QueryFragment<Pg>> QueryFragment<Pg> for DistinctOnClause<Expr> {
+    fn to_sql(&self, out: &mut <Pg as Backend>::QueryBuilder) -> BuildQueryResult {
+        out.push_sql("DISTINCT ON (");
+        try!(self.0.to_sql(out));
+        out.push_sql(") ");
+        Ok(())
+    }
+}
+
+/// PostgreSQL requires the expressions of `DISTINCT ON` to match the leftmost
+/// expressions of the order clause. This is checked when the order clause
+/// starts with the distinct expression, optionally with `.asc()` or
+/// `.desc()` applied. Distinct expressions which are tuples can't be checked
+/// this way, and have to be ordered by exactly the same tuple.
+pub trait ValidOrderingForDistinct<Distinct> {
+}
+
+impl<D> ValidOrderingForDistinct<D> for NoOrderClause {}
+impl<O> ValidOrderingForDistinct<NoDistinctClause> for OrderClause<O> {}
+impl<O> ValidOrderingForDistinct<DistinctClause> for OrderClause<O> {}
+impl<T> ValidOrderingForDistinct<DistinctOnClause<T>> for OrderClause<T> {}
+impl<T> ValidOrderingForDistinct<DistinctOnClause<T>> for OrderClause<Asc<T>> {}
+impl<T> ValidOrderingForDistinct<DistinctOnClause<T>> for OrderClause<Desc<T>> {}
+
+macro_rules! valid_tuple_orderings_for_distinct {
+    ($($T:ident),+) => {
+        impl<Expr, $($T),+> ValidOrderingForDistinct<DistinctOnClause<Expr>>
+            for OrderClause<(Expr, $($T),+)> {}
+        impl<Expr, $($T),+> ValidOrderingForDistinct<DistinctOnClause<Expr>>
+            for OrderClause<(Asc<Expr>, $($T),+)> {}
+        impl<Expr, $($T),+> ValidOrderingForDistinct<DistinctOnClause<Expr>>
+            for OrderClause<(Desc<Expr>, $($T),+)> {}
+    }
+}
+
+valid_tuple_orderings_for_distinct!(B);
+valid_tuple_orderings_for_distinct!(B, C);
+valid_tuple_orderings_for_distinct!(B, C, D);
+valid_tuple_orderings_for_distinct!(B, C, D, E);
@@ -0,0 +1 @@
+simple_clause!(NoGroupByClause, GroupByClause, " GROUP BY ");
@@ -0,0 +1 @@
+simple_clause!(NoHavingClause, HavingClause, " HAVING ");
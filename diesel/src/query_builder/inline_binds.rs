@@ -0,0 +1,510 @@
+extern crate byteorder;
+
+use std::error::Error;
+
+use self::byteorder::{ReadBytesExt, BigEndian};
+
+use backend::{Pg, oid_of, array_oid_of};
+use data_types::{PgDate, PgInterval, PgNumeric, PgTime, PgTimestamp};
+use super::QueryFragment;
+use super::pg::PgQueryBuilder;
+use types::{self, FromSql};
+
+/// Renders a PostgreSQL query with its bind params written out as SQL
+/// literals, so that it can be pasted into `psql` (for example to run
+/// `EXPLAIN` on it). No connection is needed, the values are decoded and
+/// quoted on the client.
+///
+/// Unlike [`debug_sql!`](../macro.debug_sql.html), which leaves placeholders
+/// where the values go, the result is the complete query. Values of types
+/// which diesel doesn't know how to write as a literal, such as types from
+/// other crates, are an error.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate diesel;
+/// # include!("src/doctest_setup.rs");
+/// # use diesel::query_builder::pg_sql_with_binds;
+/// #
+/// # table! {
+/// #     users {
+/// #         id -> Integer,
+/// #         name -> VarChar,
+/// #     }
+/// # }
+/// #
+/// # fn main() {
+/// #     use self::users::dsl::*;
+/// let query = users.select(id).filter(name.eq("O'Brien"));
+/// assert_eq!(
+///     "SELECT \"users\".\"id\" FROM \"users\" WHERE \"users\".\"name\" = 'O''Brien'::varchar",
+///     pg_sql_with_binds(&query).unwrap()
+/// );
+/// # }
+/// ```
+pub fn pg_sql_with_binds<T>(query: &T) -> Result<String, Box<Error>> where
+    T: QueryFragment<Pg>,
+{
+    let mut query_builder = PgQueryBuilder::inlining_binds();
+    try!(query.to_sql(&mut query_builder));
+    match query_builder.inline_error {
+        Some(e) => Err(e as Box<Error>),
+        None => Ok(query_builder.sql),
+    }
+}
+
+/// The same as [`pg_sql_with_binds`](fn.pg_sql_with_binds.html), but with
+/// each clause of the query on its own line. Subselects are indented.
+pub fn pretty_pg_sql_with_binds<T>(query: &T) -> Result<String, Box<Error>> where
+    T: QueryFragment<Pg>,
+{
+    pg_sql_with_binds(query).map(|sql| pretty_print(&sql))
+}
+
+const CLAUSE_KEYWORDS: &'static [&'static str] = &[
+    "FROM", "WHERE", "INNER JOIN", "LEFT OUTER JOIN", "GROUP BY", "HAVING",
+    "ORDER BY", "LIMIT", "OFFSET", "VALUES", "SET", "RETURNING", "UNION",
+    "INTERSECT", "EXCEPT",
+];
+
+/// Starts a new line before each clause, outside of quoted strings and
+/// identifiers, dollar quoted strings and comments. Clauses nested in
+/// parentheses are indented by two spaces per level.
+fn pretty_print(sql: &str) -> String {
+    let mut result = String::with_capacity(sql.len());
+    let mut depth = 0;
+    let mut rest = sql;
+    while let Some(c) = rest.chars().next() {
+        let after_identifier = result.chars().next_back().map_or(false, is_identifier_char);
+        let len = match c {
+            '\'' => quoted_len(rest, after_identifier && is_escape_string_prefix(&result)),
+            '"' => quoted_len(rest, false),
+            '-' if rest.starts_with("--") => rest.find('\n').map_or(rest.len(), |i| i + 1),
+            '/' if rest.starts_with("/*") => block_comment_len(rest),
+            '$' if !after_identifier => dollar_quoted_len(rest).unwrap_or(1),
+            _ => {
+                match c {
+                    '(' => depth += 1,
+                    ')' => depth -= 1,
+                    _ if result.ends_with(' ') && starts_with_clause(rest) => {
+                        result.pop();
+                        result.push('\n');
+                        for _ in 0..depth {
+                            result.push_str("  ");
+                        }
+                    }
+                    _ => {},
+                }
+                c.len_utf8()
+            }
+        };
+        result.push_str(&rest[..len]);
+        rest = &rest[len..];
+    }
+    result
+}
+
+fn starts_with_clause(sql: &str) -> bool {
+    CLAUSE_KEYWORDS.iter().any(|keyword| {
+        sql.starts_with(keyword) &&
+            sql[keyword.len()..].chars().next().map_or(true, |c| !c.is_alphanumeric() && c != '_')
+    })
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+/// Whether a string constant following `sql` is an escape string, which is
+/// the case when `sql` ends with a standalone `E`.
+fn is_escape_string_prefix(sql: &str) -> bool {
+    let mut chars = sql.chars().rev();
+    match (chars.next(), chars.next()) {
+        (Some('E'), before) | (Some('e'), before) => !before.map_or(false, is_identifier_char),
+        _ => false,
+    }
+}
+
+/// The length of the quoted string or identifier at the start of `sql`,
+/// including the quotes. Doubled quotes are part of the quoted text, as is
+/// any character following a backslash in an escape string.
+fn quoted_len(sql: &str, escape_string: bool) -> usize {
+    let bytes = sql.as_bytes();
+    let quote = bytes[0];
+    let mut i = 1;
+    while i < bytes.len() {
+        if escape_string && bytes[i] == b'\\' {
+            i += 2;
+        } else if bytes[i] == quote {
+            if bytes.get(i + 1) == Some(&quote) {
+                i += 2;
+            } else {
+                return i + 1;
+            }
+        } else {
+            i += 1;
+        }
+    }
+    sql.len()
+}
+
+/// The length of the comment starting with `/*` at the start of `sql`.
+/// Comments can be nested.
+fn block_comment_len(sql: &str) -> usize {
+    let mut depth = 0;
+    let mut i = 0;
+    while i < sql.len() {
+        if sql[i..].starts_with("/*") {
+            depth += 1;
+            i += 2;
+        } else if sql[i..].starts_with("*/") {
+            depth -= 1;
+            i += 2;
+            if depth == 0 {
+                return i;
+            }
+        } else {
+            i += sql[i..].chars().next().map_or(1, char::len_utf8);
+        }
+    }
+    sql.len()
+}
+
+/// The length of the dollar quoted string at the start of `sql`, such as
+/// `$$text$$` or `$tag$text$tag$`, or `None` if it doesn't start with one.
+fn dollar_quoted_len(sql: &str) -> Option<usize> {
+    let tag_len = match sql[1..].find('$') {
+        Some(len) => len,
+        None => return None,
+    };
+    let tag = &sql[..tag_len + 2];
+    let valid_tag = tag[1..tag_len + 1].chars().enumerate()
+        .all(|(i, c)| c.is_alphabetic() || c == '_' || (i != 0 && c.is_digit(10)));
+    if !valid_tag {
+        return None;
+    }
+    let body = &sql[tag.len()..];
+    Some(body.find(tag).map_or(sql.len(), |i| tag.len() + i + tag.len()))
+}
+
+/// Writes a value sent in the binary format as a literal of the same type.
+pub fn literal(oid: u32, bytes: Option<&[u8]>) -> Result<String, Box<Error + Send + Sync>> {
+    if bytes.is_none() {
+        return Ok("NULL".to_string());
+    }
+
+    if oid == oid_of::<types::Bool>() {
+        let value = try!(<bool as FromSql<types::Bool, Pg>>::from_sql(bytes));
+        Ok(if value { "TRUE" } else { "FALSE" }.to_string())
+    } else if oid == oid_of::<types::SmallInt>() {
+        <i16 as FromSql<types::SmallInt, Pg>>::from_sql(bytes)
+            .map(|v| cast_number(v.to_string(), "int2"))
+    } else if oid == oid_of::<types::Integer>() {
+        <i32 as FromSql<types::Integer, Pg>>::from_sql(bytes).map(|v| v.to_string())
+    } else if oid == oid_of::<types::BigInt>() {
+        <i64 as FromSql<types::BigInt, Pg>>::from_sql(bytes)
+            .map(|v| cast_number(v.to_string(), "int8"))
+    } else if oid == oid_of::<types::Oid>() {
+        <u32 as FromSql<types::Oid, Pg>>::from_sql(bytes).map(|v| cast_number(v.to_string(), "oid"))
+    } else if oid == oid_of::<types::Float>() {
+        <f32 as FromSql<types::Float, Pg>>::from_sql(bytes)
+            .map(|v| float_literal(format!("{:?}", v), "float4"))
+    } else if oid == oid_of::<types::Double>() {
+        <f64 as FromSql<types::Double, Pg>>::from_sql(bytes)
+            .map(|v| float_literal(format!("{:?}", v), "float8"))
+    } else if oid == oid_of::<types::Numeric>() {
+        <PgNumeric as FromSql<types::Numeric, Pg>>::from_sql(bytes).map(|v| numeric_literal(&v))
+    } else if oid == oid_of::<types::Text>() || oid == oid_of::<types::VarChar>() {
+        <String as FromSql<types::Text, Pg>>::from_sql(bytes)
+            .map(|v| format!("{}::{}", quote_string(&v), type_name(oid)))
+    } else if oid == oid_of::<types::Binary>() {
+        let hex = bytes.unwrap().iter().map(|b| format!("{:02x}", b)).collect::<String>();
+        Ok(format!("'\\x{}'::bytea", hex))
+    } else if oid == oid_of::<types::Date>() {
+        <PgDate as FromSql<types::Date, Pg>>::from_sql(bytes)
+            .map(|v| format!("'{}'::date", format_date(v.0)))
+    } else if oid == oid_of::<types::Time>() {
+        <PgTime as FromSql<types::Time, Pg>>::from_sql(bytes)
+            .map(|v| format!("'{}'::time", format_time(v.0)))
+    } else if oid == oid_of::<types::Timestamp>() {
+        <PgTimestamp as FromSql<types::Timestamp, Pg>>::from_sql(bytes)
+            .map(|v| format!("'{}'::timestamp", format_timestamp(v.0)))
+    } else if oid == oid_of::<types::Interval>() {
+        <PgInterval as FromSql<types::Interval, Pg>>::from_sql(bytes)
+            .map(|v| format!("'{}'::interval", format_interval(v)))
+    } else if let Some(elem_type) = array_element_type(oid) {
+        array_literal(elem_type, bytes.unwrap())
+    } else {
+        Err(format!("Cannot write a bind param with type OID {} as a literal", oid).into())
+    }
+}
+
+/// Returns the OID of the element type, if `oid` is one of the array types
+/// that `literal` supports.
+fn array_element_type(oid: u32) -> Option<u32> {
+    let element_types = [
+        oid_of::<types::Bool>(), oid_of::<types::SmallInt>(), oid_of::<types::Integer>(),
+        oid_of::<types::BigInt>(), oid_of::<types::Oid>(), oid_of::<types::Float>(),
+        oid_of::<types::Double>(), oid_of::<types::Numeric>(), oid_of::<types::Text>(),
+        oid_of::<types::VarChar>(), oid_of::<types::Binary>(), oid_of::<types::Date>(),
+        oid_of::<types::Time>(), oid_of::<types::Timestamp>(), oid_of::<types::Interval>(),
+    ];
+    let array_types = [
+        array_oid_of::<types::Bool>(), array_oid_of::<types::SmallInt>(),
+        array_oid_of::<types::Integer>(), array_oid_of::<types::BigInt>(),
+        array_oid_of::<types::Oid>(), array_oid_of::<types::Float>(),
+        array_oid_of::<types::Double>(), array_oid_of::<types::Numeric>(),
+        array_oid_of::<types::Text>(), array_oid_of::<types::VarChar>(),
+        array_oid_of::<types::Binary>(), array_oid_of::<types::Date>(),
+        array_oid_of::<types::Time>(), array_oid_of::<types::Timestamp>(),
+        array_oid_of::<types::Interval>(),
+    ];
+    array_types.iter().position(|&array_oid| array_oid == oid).map(|i| element_types[i])
+}
+
+fn type_name(oid: u32) -> &'static str {
+    match oid {
+        16 => "bool",
+        21 => "int2",
+        23 => "int4",
+        20 => "int8",
+        26 => "oid",
+        700 => "float4",
+        701 => "float8",
+        1700 => "numeric",
+        25 => "text",
+        1043 => "varchar",
+        17 => "bytea",
+        1082 => "date",
+        1083 => "time",
+        1114 => "timestamp",
+        1186 => "interval",
+        _ => unreachable!("Only called for the element types of supported arrays"),
+    }
+}
+
+fn array_literal(elem_type: u32, mut bytes: &[u8]) -> Result<String, Box<Error + Send + Sync>> {
+    let num_dimensions = try!(bytes.read_i32::<BigEndian>());
+    let _flags = try!(bytes.read_i32::<BigEndian>());
+    let _elem_oid = try!(bytes.read_u32::<BigEndian>());
+    let type_name = type_name(elem_type);
+
+    if num_dimensions == 0 {
+        return Ok(format!("'{{}}'::{}[]", type_name));
+    }
+    if num_dimensions != 1 {
+        return Err("Cannot write a multi-dimensional array as a literal".into());
+    }
+
+    let num_elements = try!(bytes.read_i32::<BigEndian>());
+    let _lower_bound = try!(bytes.read_i32::<BigEndian>());
+    let mut elements = Vec::new();
+    for _ in 0..num_elements {
+        let elem_size = try!(bytes.read_i32::<BigEndian>());
+        if elem_size == -1 {
+            elements.push("NULL".to_string());
+        } else if elem_size < 0 || elem_size as usize > bytes.len() {
+            return Err("An array element is longer than the array".into());
+        } else {
+            let (elem_bytes, rest) = bytes.split_at(elem_size as usize);
+            bytes = rest;
+            elements.push(try!(literal(elem_type, Some(elem_bytes))));
+        }
+    }
+    Ok(format!("ARRAY[{}]::{}[]", elements.join(", "), type_name))
+}
+
+fn quote_string(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+fn float_literal(value: String, type_name: &str) -> String {
+    match &*value {
+        "NaN" => format!("'NaN'::{}", type_name),
+        "inf" => format!("'Infinity'::{}", type_name),
+        "-inf" => format!("'-Infinity'::{}", type_name),
+        _ => cast_number(value, type_name),
+    }
+}
+
+/// Integer literals are `integer` or `numeric` depending on their size, and
+/// other literals are `numeric`, so anything else needs a cast. Negative
+/// numbers are parenthesized, as the cast would otherwise be applied before
+/// the minus sign, which overflows for the smallest value of a type.
+fn cast_number(value: String, type_name: &str) -> String {
+    if value.starts_with('-') {
+        format!("({})::{}", value, type_name)
+    } else {
+        format!("{}::{}", value, type_name)
+    }
+}
+
+fn numeric_literal(numeric: &PgNumeric) -> String {
+    let (sign, weight, scale, digits) = match *numeric {
+        PgNumeric::Positive { weight, scale, ref digits } => ("", weight, scale, digits),
+        PgNumeric::Negative { weight, scale, ref digits } => ("-", weight, scale, digits),
+        PgNumeric::NaN => return "'NaN'::numeric".to_string(),
+    };
+    // Each digit is a base 10000 digit, and `weight` is the power of the
+    // first one
+    let digit_at = |power: i16| {
+        let index = weight as i32 - power as i32;
+        if index >= 0 {
+            digits.get(index as usize).cloned().unwrap_or(0)
+        } else {
+            0
+        }
+    };
+
+    let mut result = sign.to_string();
+    if weight < 0 || digits.is_empty() {
+        result.push('0');
+    } else {
+        result.push_str(&digit_at(weight).to_string());
+        for power in (0..weight).rev() {
+            result.push_str(&format!("{:04}", digit_at(power)));
+        }
+    }
+    if scale > 0 {
+        let fraction_digits = (scale as i16 + 3) / 4;
+        let fraction = (1..fraction_digits + 1)
+            .map(|power| format!("{:04}", digit_at(-power)))
+            .collect::<String>();
+        result.push('.');
+        result.push_str(&fraction[..scale as usize]);
+    }
+    cast_number(result, "numeric")
+}
+
+const MICROSECONDS_PER_DAY: i64 = 86_400_000_000;
+
+/// Dates count days since 2000-01-01. Uses the algorithm from
+/// http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn format_date(days_since_2000: i32) -> String {
+    match days_since_2000 {
+        ::std::i32::MAX => return "infinity".to_string(),
+        ::std::i32::MIN => return "-infinity".to_string(),
+        _ => {},
+    }
+
+    let days_since_0000_03_01 = days_since_2000 as i64 + 730_425;
+    let era = if days_since_0000_03_01 >= 0 {
+        days_since_0000_03_01 / 146_097
+    } else {
+        (days_since_0000_03_01 - 146_096) / 146_097
+    };
+    let day_of_era = days_since_0000_03_01 - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    // There is no year 0, 1 BC comes right before 1 AD
+    if year > 0 {
+        format!("{:04}-{:02}-{:02}", year, month, day)
+    } else {
+        format!("{:04}-{:02}-{:02} BC", 1 - year, month, day)
+    }
+}
+
+fn format_time(microseconds: i64) -> String {
+    let seconds = microseconds / 1_000_000;
+    let mut result = format!("{:02}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60);
+    let fraction = microseconds % 1_000_000;
+    if fraction != 0 {
+        let fraction = format!(".{:06}", fraction);
+        result.push_str(fraction.trim_right_matches('0'));
+    }
+    result
+}
+
+fn format_timestamp(microseconds: i64) -> String {
+    match microseconds {
+        ::std::i64::MAX => return "infinity".to_string(),
+        ::std::i64::MIN => return "-infinity".to_string(),
+        _ => {},
+    }
+
+    let mut days = microseconds / MICROSECONDS_PER_DAY;
+    let mut time = microseconds % MICROSECONDS_PER_DAY;
+    if time < 0 {
+        days -= 1;
+        time += MICROSECONDS_PER_DAY;
+    }
+    let date = format_date(days as i32);
+    // The era goes at the very end of a timestamp
+    if date.ends_with(" BC") {
+        format!("{} {} BC", &date[..date.len() - 3], format_time(time))
+    } else {
+        format!("{} {}", date, format_time(time))
+    }
+}
+
+fn format_interval(interval: PgInterval) -> String {
+    let mut parts = Vec::new();
+    if interval.months != 0 {
+        parts.push(format!("{} months", interval.months));
+    }
+    if interval.days != 0 {
+        parts.push(format!("{} days", interval.days));
+    }
+    if interval.microseconds != 0 || parts.is_empty() {
+        let sign = if interval.microseconds < 0 { "-" } else { "" };
+        parts.push(format!("{}{}", sign, format_time(interval.microseconds.abs())));
+    }
+    parts.join(" ")
+}
+
+#[test]
+fn dates_are_formatted_in_iso_format() {
+    assert_eq!("2000-01-01", format_date(0));
+    assert_eq!("1999-12-31", format_date(-1));
+    assert_eq!("2000-03-01", format_date(60));
+    assert_eq!("1970-01-01", format_date(-10_957));
+    assert_eq!("2016-02-29", format_date(5_903));
+    assert_eq!("0001-12-31 BC", format_date(-730_120));
+    assert_eq!("infinity", format_date(::std::i32::MAX));
+}
+
+#[test]
+fn timestamps_and_times_include_fractional_seconds_only_when_present() {
+    assert_eq!("2000-01-01 00:00:00", format_timestamp(0));
+    assert_eq!("1999-12-31 23:59:59.5", format_timestamp(-500_000));
+    assert_eq!("13:05:09.000001", format_time(47_109_000_001));
+}
+
+#[test]
+fn numerics_are_written_in_decimal() {
+    let numeric = PgNumeric::Positive { weight: 1, scale: 2, digits: vec![1, 2345, 6700] };
+    assert_eq!("12345.67::numeric", numeric_literal(&numeric));
+    let numeric = PgNumeric::Negative { weight: -2, scale: 8, digits: vec![12] };
+    assert_eq!("(-0.00000012)::numeric", numeric_literal(&numeric));
+    let numeric = PgNumeric::Positive { weight: 2, scale: 0, digits: vec![1] };
+    assert_eq!("100000000::numeric", numeric_literal(&numeric));
+}
+
+#[test]
+fn pretty_print_breaks_lines_before_clauses_outside_of_quotes() {
+    let sql = "SELECT \"a\" FROM \"t\" WHERE \"b\" = 'x FROM y' AND \"c\" IN (SELECT \"c\" FROM \"u\") ORDER BY \"a\"";
+    let expected = "SELECT \"a\"\nFROM \"t\"\nWHERE \"b\" = 'x FROM y' AND \"c\" IN (SELECT \"c\"\n  FROM \"u\")\nORDER BY \"a\"";
+    assert_eq!(expected, pretty_print(sql));
+}
+
+#[test]
+fn pretty_print_skips_escape_strings_dollar_quotes_and_comments() {
+    let sql = "SELECT E'\\' FROM (', $tag$ ) FROM $tag$, $$ WHERE ( $$ -- FROM (\n/* /* FROM */ ( */ FROM \"t\"";
+    let expected = "SELECT E'\\' FROM (', $tag$ ) FROM $tag$, $$ WHERE ( $$ -- FROM (\n/* /* FROM */ ( */\nFROM \"t\"";
+    assert_eq!(expected, pretty_print(sql));
+}
+
+#[test]
+fn arrays_with_elements_longer_than_the_data_are_an_error() {
+    // One dimension of one int4, whose length is given as 8 bytes
+    let bytes = [0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 23, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 8, 0, 0, 0, 1];
+    assert!(array_literal(23, &bytes).is_err());
+    let bytes = [0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 23, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 4, 0, 0, 0, 1];
+    assert_eq!("ARRAY[1]::int4[]", array_literal(23, &bytes).unwrap());
+}
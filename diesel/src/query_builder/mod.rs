@@ -1,6 +1,12 @@
 //! Contains traits responsible for the actual construction of SQL statements
 #[doc(hidden)]
 pub mod pg;
+#[doc(hidden)]
+#[cfg(feature = "mysql")]
+pub mod mysql;
+#[doc(hidden)]
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
 pub mod debug;
 
 mod delete_statement;
@@ -10,6 +16,13 @@ pub mod functions;
 pub mod nodes;
 #[macro_use]
 mod clause_macro;
+#[doc(hidden)]
+pub mod combination;
+mod distinct_clause;
+#[doc(hidden)]
+pub mod group_by_clause;
+mod having_clause;
+mod inline_binds;
 mod limit_clause;
 mod offset_clause;
 mod order_clause;
@@ -19,11 +32,12 @@ pub mod insert_statement;
 pub mod update_statement;
 
 #[doc(hidden)]
-pub use self::select_statement::SelectStatement;
+pub use self::select_statement::{SelectStatement, BoxedSelectStatement};
 #[doc(inline)]
 pub use self::update_statement::{IncompleteUpdateStatement, AsChangeset, Changeset, UpdateTarget};
 #[doc(inline)]
 pub use self::insert_statement::IncompleteInsertStatement;
+pub use self::inline_binds::{pg_sql_with_binds, pretty_pg_sql_with_binds};
 
 use std::error::Error;
 
@@ -46,6 +60,13 @@ pub trait QueryBuilder<DB: Backend> {
         DB: HasSqlType<T>;
     fn push_context(&mut self, context: Context);
     fn pop_context(&mut self);
+
+    /// Called by query fragments whose SQL can differ between executions in
+    /// ways which wouldn't be worth preparing separately, such as raw SQL
+    /// strings or inserts of several records at once. Queries containing
+    /// them are never cached as prepared statements.
+    fn unsafe_to_cache_prepared(&mut self) {
+    }
 }
 
 /// Represents the current overall type of query being constructed. Used for
@@ -0,0 +1,52 @@
+use backend::{Mysql, MysqlType};
+use super::{QueryBuilder, BuildQueryResult, Context};
+use types::HasSqlType;
+
+pub struct MysqlQueryBuilder {
+    pub sql: String,
+    pub binds: Vec<(MysqlType, Option<Vec<u8>>)>,
+    context_stack: Vec<Context>,
+}
+
+impl MysqlQueryBuilder {
+    pub fn new() -> Self {
+        MysqlQueryBuilder {
+            sql: String::new(),
+            binds: Vec::new(),
+            context_stack: Vec::new(),
+        }
+    }
+}
+
+impl QueryBuilder<Mysql> for MysqlQueryBuilder {
+    fn push_sql(&mut self, sql: &str) {
+        self.sql.push_str(sql);
+    }
+
+    fn push_identifier(&mut self, identifier: &str) -> BuildQueryResult {
+        self.push_sql("`");
+        self.push_sql(&identifier.replace("`", "``"));
+        self.push_sql("`");
+        Ok(())
+    }
+
+    fn push_bound_value<T>(&mut self, bind: Option<Vec<u8>>) where
+        Mysql: HasSqlType<T>,
+    {
+        match (self.context_stack.first(), bind) {
+            (Some(&Context::Insert), None) => self.push_sql("DEFAULT"),
+            (_, bind) => {
+                self.push_sql("?");
+                self.binds.push((Mysql::metadata(), bind));
+            }
+        }
+    }
+
+    fn push_context(&mut self, context: Context) {
+        self.context_stack.push(context);
+    }
+
+    fn pop_context(&mut self) {
+        self.context_stack.pop();
+    }
+}
@@ -1 +1,9 @@
+use expression::ValidGrouping;
+
 simple_clause!(NoOrderClause, OrderClause, " ORDER BY ");
+
+impl<G> ValidGrouping<G> for NoOrderClause {
+}
+
+impl<Expr: ValidGrouping<G>, G> ValidGrouping<G> for OrderClause<Expr> {
+}
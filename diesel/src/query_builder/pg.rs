@@ -1,28 +1,57 @@
+use std::error::Error;
 use std::rc::Rc;
 
 use backend::Pg;
 use connection::pg::raw::RawConnection;
 use super::{QueryBuilder, Binds, BuildQueryResult, Context};
+use super::inline_binds::literal;
 use types::HasSqlType;
 
 pub struct PgQueryBuilder {
-    conn: Rc<RawConnection>,
+    // `None` when building queries for a `MockPgConnection`
+    conn: Option<Rc<RawConnection>>,
     pub sql: String,
     pub binds: Binds,
     pub bind_types: Vec<u32>,
+    pub safe_to_cache_prepared: bool,
     bind_idx: u32,
     context_stack: Vec<Context>,
+    // Whether bind params are written into the SQL as literals instead of
+    // being sent separately, as done by `pg_sql_with_binds`
+    inline_binds: bool,
+    /// The first bind param which couldn't be written as a literal
+    pub inline_error: Option<Box<Error + Send + Sync>>,
 }
 
 impl PgQueryBuilder {
     pub fn new(conn: &Rc<RawConnection>) -> Self {
+        PgQueryBuilder::with_connection(Some(conn.clone()))
+    }
+
+    pub fn without_connection() -> Self {
+        PgQueryBuilder::with_connection(None)
+    }
+
+    /// A query builder which writes each bind param into the SQL as a
+    /// literal, leaving `binds` empty.
+    pub fn inlining_binds() -> Self {
         PgQueryBuilder {
-            conn: conn.clone(),
+            inline_binds: true,
+            ..PgQueryBuilder::without_connection()
+        }
+    }
+
+    fn with_connection(conn: Option<Rc<RawConnection>>) -> Self {
+        PgQueryBuilder {
+            conn: conn,
             sql: String::new(),
             binds: Vec::new(),
             bind_types: Vec::new(),
+            safe_to_cache_prepared: true,
             bind_idx: 0,
             context_stack: Vec::new(),
+            inline_binds: false,
+            inline_error: None,
         }
     }
 }
@@ -33,8 +62,16 @@ impl QueryBuilder<Pg> for PgQueryBuilder {
     }
 
     fn push_identifier(&mut self, identifier: &str) -> BuildQueryResult {
-        let escaped_identifier = try!(self.conn.escape_identifier(identifier));
-        Ok(self.push_sql(&escaped_identifier))
+        match self.conn {
+            Some(ref conn) => {
+                let escaped_identifier = try!(conn.escape_identifier(identifier));
+                self.sql.push_str(&escaped_identifier);
+            }
+            None => {
+                self.sql.push_str(&format!("\"{}\"", identifier.replace('"', "\"\"")));
+            }
+        }
+        Ok(())
     }
 
     fn push_bound_value<T>(&mut self, bind: Option<Vec<u8>>) where
@@ -42,6 +79,14 @@ impl QueryBuilder<Pg> for PgQueryBuilder {
     {
         match (self.context_stack.first(), bind) {
             (Some(&Context::Insert), None) => self.push_sql("DEFAULT"),
+            (_, bind) if self.inline_binds => {
+                match literal(Pg::metadata().oid, bind.as_ref().map(|b| &**b)) {
+                    Ok(literal) => self.push_sql(&literal),
+                    Err(e) => if self.inline_error.is_none() {
+                        self.inline_error = Some(e);
+                    },
+                }
+            }
             (_, bind) => {
                 self.bind_idx += 1;
                 let sql = format!("${}", self.bind_idx);
@@ -52,6 +97,10 @@ impl QueryBuilder<Pg> for PgQueryBuilder {
         }
     }
 
+    fn unsafe_to_cache_prepared(&mut self) {
+        self.safe_to_cache_prepared = false;
+    }
+
     fn push_context(&mut self, context: Context) {
         self.context_stack.push(context);
     }
@@ -60,3 +109,17 @@ impl QueryBuilder<Pg> for PgQueryBuilder {
         self.context_stack.pop();
     }
 }
+
+#[test]
+fn inlined_binds_are_written_where_they_are_pushed() {
+    use types;
+
+    let mut query_builder = PgQueryBuilder::inlining_binds();
+    query_builder.push_sql("SELECT '$1', $1, ");
+    query_builder.push_bound_value::<types::Text>(Some(b"$1".to_vec()));
+    query_builder.push_sql(", ");
+    query_builder.push_bound_value::<types::Integer>(Some(vec![0, 0, 0, 1]));
+    assert_eq!("SELECT '$1', $1, '$1'::text, 1", query_builder.sql);
+    assert!(query_builder.binds.is_empty());
+    assert!(query_builder.inline_error.is_none());
+}
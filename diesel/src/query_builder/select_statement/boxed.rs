@@ -0,0 +1,145 @@
+use std::marker::PhantomData;
+
+use backend::{Backend, Pg};
+use expression::*;
+use query_builder::*;
+use query_builder::group_by_clause::NoGroupByClause;
+use query_builder::limit_clause::LimitClause;
+use query_builder::offset_clause::OffsetClause;
+use query_builder::order_clause::OrderClause;
+use query_builder::where_clause::{BoxedWhereClause, WhereAnd};
+use query_dsl::*;
+use query_source::QuerySource;
+use super::dsl_impls::{Limit, Offset};
+use types::{self, Bool};
+
+/// A select statement whose clauses have been boxed by
+/// [`into_boxed`](../trait.BoxedDsl.html). Only the SQL type, the query
+/// source and the type of the group by clause remain part of its type, so
+/// filters and orderings can be added conditionally and are still checked
+/// against the group by clause.
+#[doc(hidden)]
+pub struct BoxedSelectStatement<'a, ST, QS, G = NoGroupByClause> {
+    select: Box<QueryFragment<Pg> + 'a>,
+    from: QS,
+    distinct: Box<QueryFragment<Pg> + 'a>,
+    where_clause: BoxedWhereClause<'a, QS, Pg>,
+    order: Box<QueryFragment<Pg> + 'a>,
+    limit: Box<QueryFragment<Pg> + 'a>,
+    offset: Box<QueryFragment<Pg> + 'a>,
+    group_by: Box<QueryFragment<Pg> + 'a>,
+    having: Box<QueryFragment<Pg> + 'a>,
+    _marker: PhantomData<(ST, G)>,
+}
+
+impl<'a, ST, QS, G> BoxedSelectStatement<'a, ST, QS, G> {
+    pub fn new(select: Box<QueryFragment<Pg> + 'a>, from: QS,
+        distinct: Box<QueryFragment<Pg> + 'a>, where_clause: BoxedWhereClause<'a, QS, Pg>,
+        order: Box<QueryFragment<Pg> + 'a>, limit: Box<QueryFragment<Pg> + 'a>,
+        offset: Box<QueryFragment<Pg> + 'a>, group_by: Box<QueryFragment<Pg> + 'a>,
+        having: Box<QueryFragment<Pg> + 'a>) -> Self
+    {
+        BoxedSelectStatement {
+            select: select,
+            from: from,
+            distinct: distinct,
+            where_clause: where_clause,
+            order: order,
+            limit: limit,
+            offset: offset,
+            group_by: group_by,
+            having: having,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, ST, QS, G> Query for BoxedSelectStatement<'a, ST, QS, G> {
+    type SqlType = ST;
+}
+
+impl<'a, ST, QS, G> Expression for BoxedSelectStatement<'a, ST, QS, G> where
+    QS: QuerySource,
+{
+    type SqlType = types::Array<ST>;
+}
+
+impl<'a, ST, QS, G> QueryFragment<Pg> for BoxedSelectStatement<'a, ST, QS, G> where
+    QS: QuerySource,
+    QS::FromClause: QueryFragment<Pg>,
+{
+    fn to_sql(&self, out: &mut <Pg as Backend>::QueryBuilder) -> BuildQueryResult {
+        out.push_context(Context::Select);
+        out.push_sql("SELECT ");
+        try!(self.distinct.to_sql(out));
+        try!(self.select.to_sql(out));
+        out.push_sql(" FROM ");
+        try!(self.from.from_clause().to_sql(out));
+        try!(self.where_clause.to_sql(out));
+        try!(self.group_by.to_sql(out));
+        try!(self.having.to_sql(out));
+        try!(self.order.to_sql(out));
+        try!(self.limit.to_sql(out));
+        try!(self.offset.to_sql(out));
+        out.pop_context();
+        Ok(())
+    }
+}
+
+impl<'a, ST, QS, G, QS2> SelectableExpression<QS2> for BoxedSelectStatement<'a, ST, QS, G> where
+    BoxedSelectStatement<'a, ST, QS, G>: Expression,
+{
+}
+
+impl<'a, ST, QS, G> NonAggregate for BoxedSelectStatement<'a, ST, QS, G> where
+    BoxedSelectStatement<'a, ST, QS, G>: Expression,
+{
+}
+
+impl<'a, ST, QS, G, GB> ValidGrouping<GB> for BoxedSelectStatement<'a, ST, QS, G> where
+    BoxedSelectStatement<'a, ST, QS, G>: Expression,
+{
+}
+
+impl<'a, ST, QS, G, Predicate> FilterDsl<Predicate> for BoxedSelectStatement<'a, ST, QS, G> where
+    BoxedWhereClause<'a, QS, Pg>: WhereAnd<Predicate, Output=BoxedWhereClause<'a, QS, Pg>>,
+    Predicate: SelectableExpression<QS, SqlType=Bool> + NonAggregate,
+{
+    type Output = Self;
+
+    fn filter(mut self, predicate: Predicate) -> Self::Output {
+        self.where_clause = self.where_clause.and(predicate);
+        self
+    }
+}
+
+impl<'a, ST, QS, G, Expr> OrderDsl<Expr> for BoxedSelectStatement<'a, ST, QS, G> where
+    Expr: SelectableExpression<QS> + ValidGrouping<G> + QueryFragment<Pg> + 'a,
+{
+    type Output = Self;
+
+    fn order(mut self, expr: Expr) -> Self::Output {
+        self.order = Box::new(OrderClause(expr));
+        self
+    }
+}
+
+impl<'a, ST, QS, G> LimitDsl for BoxedSelectStatement<'a, ST, QS, G> {
+    type Output = Self;
+
+    fn limit(mut self, limit: i64) -> Self::Output {
+        let limit_expression: Limit = AsExpression::<types::BigInt>::as_expression(limit);
+        self.limit = Box::new(LimitClause(limit_expression));
+        self
+    }
+}
+
+impl<'a, ST, QS, G> OffsetDsl for BoxedSelectStatement<'a, ST, QS, G> {
+    type Output = Self;
+
+    fn offset(mut self, offset: i64) -> Self::Output {
+        let offset_expression: Offset = AsExpression::<types::BigInt>::as_expression(offset);
+        self.offset = Box::new(OffsetClause(offset_expression));
+        self
+    }
+}
@@ -1,6 +1,12 @@
 use expression::*;
 use expression::aliased::Aliased;
-use query_builder::{Query, SelectStatement};
+use expression::expression_methods::*;
+use expression::predicates::And;
+use backend::Pg;
+use query_builder::{Query, QueryFragment, SelectStatement, BoxedSelectStatement};
+use query_builder::distinct_clause::*;
+use query_builder::group_by_clause::*;
+use query_builder::having_clause::*;
 use query_builder::limit_clause::*;
 use query_builder::offset_clause::*;
 use query_builder::order_clause::*;
@@ -8,86 +14,171 @@ use query_builder::where_clause::*;
 use query_dsl::*;
 use types::{self, Bool};
 
-impl<ST, S, F, W, O, L, Of, Selection, Type> SelectDsl<Selection, Type>
-    for SelectStatement<ST, S, F, W, O, L, Of> where
+impl<ST, S, F, W, O, L, Of, G, H, D, Selection, Type> SelectDsl<Selection, Type>
+    for SelectStatement<ST, S, F, W, O, L, Of, G, H, D> where
     Selection: Expression,
-    SelectStatement<Type, Selection, F, W, O, L, Of>: Query<SqlType=Type>,
+    SelectStatement<Type, Selection, F, W, O, L, Of, G, H, D>: Query<SqlType=Type>,
 {
-    type Output = SelectStatement<Type, Selection, F, W, O, L, Of>;
+    type Output = SelectStatement<Type, Selection, F, W, O, L, Of, G, H, D>;
 
     fn select(self, selection: Selection) -> Self::Output {
         SelectStatement::new(selection, self.from, self.where_clause, self.order,
-            self.limit, self.offset)
+            self.limit, self.offset, self.group_by, self.having, self.distinct)
     }
 }
 
-impl<ST, S, F, W, O, L, Of, Predicate> FilterDsl<Predicate>
-    for SelectStatement<ST, S, F, W, O, L, Of> where
+impl<ST, S, F, W, O, L, Of, G, H, D, Predicate> FilterDsl<Predicate>
+    for SelectStatement<ST, S, F, W, O, L, Of, G, H, D> where
     Predicate: SelectableExpression<F, SqlType=Bool> + NonAggregate,
     W: WhereAnd<Predicate>,
-    SelectStatement<ST, S, F, W::Output, O, L, Of>: Query,
+    SelectStatement<ST, S, F, W::Output, O, L, Of, G, H, D>: Query,
 {
-    type Output = SelectStatement<ST, S, F, W::Output, O, L, Of>;
+    type Output = SelectStatement<ST, S, F, W::Output, O, L, Of, G, H, D>;
 
     fn filter(self, predicate: Predicate) -> Self::Output {
         SelectStatement::new(self.select, self.from, self.where_clause.and(predicate),
-            self.order, self.limit, self.offset)
+            self.order, self.limit, self.offset, self.group_by, self.having, self.distinct)
     }
 }
 
-impl<ST, S, F, W, O, L, Of, Expr> OrderDsl<Expr>
-    for SelectStatement<ST, S, F, W, O, L, Of> where
+impl<ST, S, F, W, O, L, Of, G, H, D, Expr> OrderDsl<Expr>
+    for SelectStatement<ST, S, F, W, O, L, Of, G, H, D> where
     Expr: SelectableExpression<F>,
-    SelectStatement<ST, S, F, W, OrderClause<Expr>, L, Of>: Query<SqlType=ST>,
+    SelectStatement<ST, S, F, W, OrderClause<Expr>, L, Of, G, H, D>: Query<SqlType=ST>,
 {
-    type Output = SelectStatement<ST, S, F, W, OrderClause<Expr>, L, Of>;
+    type Output = SelectStatement<ST, S, F, W, OrderClause<Expr>, L, Of, G, H, D>;
 
     fn order(self, expr: Expr) -> Self::Output {
         let order = OrderClause(expr);
         SelectStatement::new(self.select, self.from, self.where_clause, order,
-            self.limit, self.offset)
+            self.limit, self.offset, self.group_by, self.having, self.distinct)
+    }
+}
+
+impl<ST, S, F, W, O, L, Of, G, D, Expr> GroupByDsl<Expr>
+    for SelectStatement<ST, S, F, W, O, L, Of, G, NoHavingClause, D> where
+    Expr: SelectableExpression<F> + NonAggregate,
+{
+    type Output = SelectStatement<ST, S, F, W, O, L, Of, GroupByClause<Expr>, NoHavingClause, D>;
+
+    fn group_by(self, expr: Expr) -> Self::Output {
+        let group_by = GroupByClause(expr);
+        SelectStatement::new(self.select, self.from, self.where_clause,
+            self.order, self.limit, self.offset, group_by, self.having, self.distinct)
+    }
+}
+
+impl<ST, S, F, W, O, L, Of, GB, D, Predicate> HavingDsl<Predicate>
+    for SelectStatement<ST, S, F, W, O, L, Of, GroupByClause<GB>, NoHavingClause, D> where
+    Predicate: SelectableExpression<F, SqlType=Bool> + ValidGrouping<GroupByClause<GB>>,
+{
+    type Output = SelectStatement<ST, S, F, W, O, L, Of, GroupByClause<GB>, HavingClause<Predicate>, D>;
+
+    fn having(self, predicate: Predicate) -> Self::Output {
+        SelectStatement::new(self.select, self.from, self.where_clause, self.order,
+            self.limit, self.offset, self.group_by, HavingClause(predicate), self.distinct)
+    }
+}
+
+impl<ST, S, F, W, O, L, Of, GB, H, D, Predicate> HavingDsl<Predicate>
+    for SelectStatement<ST, S, F, W, O, L, Of, GroupByClause<GB>, HavingClause<H>, D> where
+    H: Expression<SqlType=Bool>,
+    Predicate: SelectableExpression<F, SqlType=Bool> + ValidGrouping<GroupByClause<GB>>,
+{
+    type Output = SelectStatement<ST, S, F, W, O, L, Of, GroupByClause<GB>, HavingClause<And<H, Predicate>>, D>;
+
+    fn having(self, predicate: Predicate) -> Self::Output {
+        let having = HavingClause(self.having.0.and(predicate));
+        SelectStatement::new(self.select, self.from, self.where_clause, self.order,
+            self.limit, self.offset, self.group_by, having, self.distinct)
+    }
+}
+
+impl<ST, S, F, W, O, L, Of, G, H, D> DistinctDsl
+    for SelectStatement<ST, S, F, W, O, L, Of, G, H, D> where
+    SelectStatement<ST, S, F, W, O, L, Of, G, H, DistinctClause>: Query<SqlType=ST>,
+{
+    type Output = SelectStatement<ST, S, F, W, O, L, Of, G, H, DistinctClause>;
+
+    fn distinct(self) -> Self::Output {
+        SelectStatement::new(self.select, self.from, self.where_clause, self.order,
+            self.limit, self.offset, self.group_by, self.having, DistinctClause)
+    }
+}
+
+impl<ST, S, F, W, O, L, Of, G, H, D, Expr> DistinctOnDsl<Expr>
+    for SelectStatement<ST, S, F, W, O, L, Of, G, H, D> where
+    Expr: SelectableExpression<F> + NonAggregate,
+    SelectStatement<ST, S, F, W, O, L, Of, G, H, DistinctOnClause<Expr>>: Query<SqlType=ST>,
+{
+    type Output = SelectStatement<ST, S, F, W, O, L, Of, G, H, DistinctOnClause<Expr>>;
+
+    fn distinct_on(self, expr: Expr) -> Self::Output {
+        SelectStatement::new(self.select, self.from, self.where_clause, self.order,
+            self.limit, self.offset, self.group_by, self.having, DistinctOnClause(expr))
     }
 }
 
 #[doc(hidden)]
 pub type Limit = <i64 as AsExpression<types::BigInt>>::Expression;
 
-impl<ST, S, F, W, O, L, Of> LimitDsl for SelectStatement<ST, S, F, W, O, L, Of> where
-    SelectStatement<ST, S, F, W, O, LimitClause<Limit>, Of>: Query<SqlType=ST>,
+impl<ST, S, F, W, O, L, Of, G, H, D> LimitDsl for SelectStatement<ST, S, F, W, O, L, Of, G, H, D> where
+    SelectStatement<ST, S, F, W, O, LimitClause<Limit>, Of, G, H, D>: Query<SqlType=ST>,
 {
-    type Output = SelectStatement<ST, S, F, W, O, LimitClause<Limit>, Of>;
+    type Output = SelectStatement<ST, S, F, W, O, LimitClause<Limit>, Of, G, H, D>;
 
     fn limit(self, limit: i64) -> Self::Output {
         let limit_clause = LimitClause(AsExpression::<types::BigInt>::as_expression(limit));
         SelectStatement::new(self.select, self.from, self.where_clause,
-            self.order, limit_clause, self.offset)
+            self.order, limit_clause, self.offset, self.group_by, self.having, self.distinct)
     }
 }
 
 #[doc(hidden)]
 pub type Offset = Limit;
 
-impl<ST, S, F, W, O, L, Of> OffsetDsl for SelectStatement<ST, S, F, W, O, L, Of> where
-    SelectStatement<ST, S, F, W, O, L, OffsetClause<Offset>>: Query<SqlType=ST>,
+impl<ST, S, F, W, O, L, Of, G, H, D> OffsetDsl for SelectStatement<ST, S, F, W, O, L, Of, G, H, D> where
+    SelectStatement<ST, S, F, W, O, L, OffsetClause<Offset>, G, H, D>: Query<SqlType=ST>,
 {
-    type Output = SelectStatement<ST, S, F, W, O, L, OffsetClause<Offset>>;
+    type Output = SelectStatement<ST, S, F, W, O, L, OffsetClause<Offset>, G, H, D>;
 
     fn offset(self, offset: i64) -> Self::Output {
         let offset_clause = OffsetClause(AsExpression::<types::BigInt>::as_expression(offset));
         SelectStatement::new(self.select, self.from, self.where_clause,
-            self.order, self.limit, offset_clause)
+            self.order, self.limit, offset_clause, self.group_by, self.having, self.distinct)
     }
 }
 
-impl<'a, ST, S, F, W, O, L, Of, Expr> WithDsl<'a, Expr>
-for SelectStatement<ST, S, F, W, O, L, Of> where
-    SelectStatement<ST, S, WithQuerySource<'a, F, Expr>, W, O, L, Of>: Query,
+impl<'a, ST, S, F, W, O, L, Of, G, H, D, Expr> WithDsl<'a, Expr>
+for SelectStatement<ST, S, F, W, O, L, Of, G, H, D> where
+    SelectStatement<ST, S, WithQuerySource<'a, F, Expr>, W, O, L, Of, G, H, D>: Query,
 {
-    type Output = SelectStatement<ST, S, WithQuerySource<'a, F, Expr>, W, O, L, Of>;
+    type Output = SelectStatement<ST, S, WithQuerySource<'a, F, Expr>, W, O, L, Of, G, H, D>;
 
     fn with(self, expr: Aliased<'a, Expr>) -> Self::Output {
         let source = WithQuerySource::new(self.from, expr);
         SelectStatement::new(self.select, source, self.where_clause,
-            self.order, self.limit, self.offset)
+            self.order, self.limit, self.offset, self.group_by, self.having, self.distinct)
+    }
+}
+
+impl<'a, ST, S, F, W, O, L, Of, G, H, D> BoxedDsl<'a>
+    for SelectStatement<ST, S, F, W, O, L, Of, G, H, D> where
+    SelectStatement<ST, S, F, W, O, L, Of, G, H, D>: Query<SqlType=ST>,
+    S: QueryFragment<Pg> + 'a,
+    D: QueryFragment<Pg> + 'a,
+    W: Into<BoxedWhereClause<'a, F, Pg>>,
+    O: QueryFragment<Pg> + 'a,
+    L: QueryFragment<Pg> + 'a,
+    Of: QueryFragment<Pg> + 'a,
+    G: QueryFragment<Pg> + 'a,
+    H: QueryFragment<Pg> + 'a,
+{
+    type Output = BoxedSelectStatement<'a, ST, F, G>;
+
+    fn into_boxed(self) -> Self::Output {
+        BoxedSelectStatement::new(Box::new(self.select), self.from, Box::new(self.distinct),
+            self.where_clause.into(), Box::new(self.order), Box::new(self.limit),
+            Box::new(self.offset), Box::new(self.group_by), Box::new(self.having))
     }
 }
@@ -1,10 +1,16 @@
+mod boxed;
 mod dsl_impls;
 
+pub use self::boxed::BoxedSelectStatement;
+
 use backend::Backend;
 use expression::*;
 use query_source::*;
 use std::marker::PhantomData;
 use super::{Query, QueryBuilder, QueryFragment, BuildQueryResult, Context};
+use super::distinct_clause::{NoDistinctClause, ValidOrderingForDistinct};
+use super::group_by_clause::NoGroupByClause;
+use super::having_clause::NoHavingClause;
 use super::limit_clause::NoLimitClause;
 use super::offset_clause::NoOffsetClause;
 use super::order_clause::NoOrderClause;
@@ -21,6 +27,9 @@ pub struct SelectStatement<
     Order = NoOrderClause,
     Limit = NoLimitClause,
     Offset = NoOffsetClause,
+    GroupBy = NoGroupByClause,
+    Having = NoHavingClause,
+    Distinct = NoDistinctClause,
 > {
     select: Select,
     from: From,
@@ -28,11 +37,16 @@ pub struct SelectStatement<
     order: Order,
     limit: Limit,
     offset: Offset,
+    group_by: GroupBy,
+    having: Having,
+    distinct: Distinct,
     _marker: PhantomData<SqlType>,
 }
 
-impl<ST, S, F, W, O, L, Of> SelectStatement<ST, S, F, W, O, L, Of> {
-    pub fn new(select: S, from: F, where_clause: W, order: O, limit: L, offset: Of) -> Self {
+impl<ST, S, F, W, O, L, Of, G, H, D> SelectStatement<ST, S, F, W, O, L, Of, G, H, D> {
+    pub fn new(select: S, from: F, where_clause: W, order: O, limit: L, offset: Of,
+        group_by: G, having: H, distinct: D) -> Self
+    {
         SelectStatement {
             select: select,
             from: from,
@@ -40,53 +54,64 @@ impl<ST, S, F, W, O, L, Of> SelectStatement<ST, S, F, W, O, L, Of> {
             order: order,
             limit: limit,
             offset: offset,
+            group_by: group_by,
+            having: having,
+            distinct: distinct,
             _marker: PhantomData,
         }
     }
 
     pub fn inner_join<T>(self, other: T)
-        -> SelectStatement<ST, S, InnerJoinSource<F, T>, W, O, L, Of> where
+        -> SelectStatement<ST, S, InnerJoinSource<F, T>, W, O, L, Of, G, H, D> where
             T: Table,
             F: Table + JoinTo<T, joins::Inner>,
     {
         SelectStatement::new(self.select, self.from.inner_join(other),
-            self.where_clause, self.order, self.limit, self.offset)
+            self.where_clause, self.order, self.limit, self.offset, self.group_by, self.having,
+            self.distinct)
     }
 
     pub fn left_outer_join<T>(self, other: T)
-        -> SelectStatement<ST, S, LeftOuterJoinSource<F, T>, W, O, L, Of> where
+        -> SelectStatement<ST, S, LeftOuterJoinSource<F, T>, W, O, L, Of, G, H, D> where
             T: Table,
             F: Table + JoinTo<T, joins::LeftOuter>,
     {
         SelectStatement::new(self.select, self.from.left_outer_join(other),
-            self.where_clause, self.order, self.limit, self.offset)
+            self.where_clause, self.order, self.limit, self.offset, self.group_by, self.having,
+            self.distinct)
     }
 }
 
 impl<ST, S, F> SelectStatement<ST, S, F> {
     pub fn simple(select: S, from: F) -> Self {
-        SelectStatement::new(select, from, NoWhereClause, NoOrderClause, NoLimitClause, NoOffsetClause)
+        SelectStatement::new(select, from, NoWhereClause, NoOrderClause, NoLimitClause,
+            NoOffsetClause, NoGroupByClause, NoHavingClause, NoDistinctClause)
     }
 }
 
-impl<ST, S, F, W, O, L, Of> Query for SelectStatement<ST, S, F, W, O, L, Of> where
-    S: SelectableExpression<F, ST>,
+impl<ST, S, F, W, O, L, Of, G, H, D> Query for SelectStatement<ST, S, F, W, O, L, Of, G, H, D> where
+    S: SelectableExpression<F, ST> + ValidGrouping<G>,
+    O: ValidOrderingForDistinct<D> + ValidGrouping<G>,
 {
     type SqlType = ST;
 }
 
-impl<ST, S, F, W, O, L, Of> Expression for SelectStatement<ST, S, F, W, O, L, Of> where
-    S: SelectableExpression<F, ST>,
+impl<ST, S, F, W, O, L, Of, G, H, D> Expression for SelectStatement<ST, S, F, W, O, L, Of, G, H, D> where
+    S: SelectableExpression<F, ST> + ValidGrouping<G>,
+    O: ValidOrderingForDistinct<D> + ValidGrouping<G>,
 {
     type SqlType = types::Array<ST>;
 }
 
-impl<ST, S, F, W, O, L, Of, DB> QueryFragment<DB> for SelectStatement<ST, S, F, W, O, L, Of> where
+impl<ST, S, F, W, O, L, Of, G, H, D, DB> QueryFragment<DB> for SelectStatement<ST, S, F, W, O, L, Of, G, H, D> where
     DB: Backend,
     S: QueryFragment<DB>,
     F: QuerySource,
     F::FromClause: QueryFragment<DB>,
     W: QueryFragment<DB>,
+    G: QueryFragment<DB>,
+    H: QueryFragment<DB>,
+    D: QueryFragment<DB>,
     O: QueryFragment<DB>,
     L: QueryFragment<DB>,
     Of: QueryFragment<DB>,
@@ -94,10 +119,13 @@ impl<ST, S, F, W, O, L, Of, DB> QueryFragment<DB> for SelectStatement<ST, S, F,
     fn to_sql(&self, out: &mut DB::QueryBuilder) -> BuildQueryResult {
         out.push_context(Context::Select);
         out.push_sql("SELECT ");
+        try!(self.distinct.to_sql(out));
         try!(self.select.to_sql(out));
         out.push_sql(" FROM ");
         try!(self.from.from_clause().to_sql(out));
         try!(self.where_clause.to_sql(out));
+        try!(self.group_by.to_sql(out));
+        try!(self.having.to_sql(out));
         try!(self.order.to_sql(out));
         try!(self.limit.to_sql(out));
         try!(self.offset.to_sql(out));
@@ -106,10 +134,13 @@ impl<ST, S, F, W, O, L, Of, DB> QueryFragment<DB> for SelectStatement<ST, S, F,
     }
 }
 
-impl<ST, S, W, O, L, Of, DB> QueryFragment<DB> for SelectStatement<ST, S, (), W, O, L, Of> where
+impl<ST, S, W, O, L, Of, G, H, D, DB> QueryFragment<DB> for SelectStatement<ST, S, (), W, O, L, Of, G, H, D> where
     DB: Backend,
     S: QueryFragment<DB>,
     W: QueryFragment<DB>,
+    G: QueryFragment<DB>,
+    H: QueryFragment<DB>,
+    D: QueryFragment<DB>,
     O: QueryFragment<DB>,
     L: QueryFragment<DB>,
     Of: QueryFragment<DB>,
@@ -117,8 +148,11 @@ impl<ST, S, W, O, L, Of, DB> QueryFragment<DB> for SelectStatement<ST, S, (), W,
     fn to_sql(&self, out: &mut DB::QueryBuilder) -> BuildQueryResult {
         out.push_context(Context::Select);
         out.push_sql("SELECT ");
+        try!(self.distinct.to_sql(out));
         try!(self.select.to_sql(out));
         try!(self.where_clause.to_sql(out));
+        try!(self.group_by.to_sql(out));
+        try!(self.having.to_sql(out));
         try!(self.order.to_sql(out));
         try!(self.limit.to_sql(out));
         try!(self.offset.to_sql(out));
@@ -127,12 +161,18 @@ impl<ST, S, W, O, L, Of, DB> QueryFragment<DB> for SelectStatement<ST, S, (), W,
     }
 }
 
-impl<ST, S, F, W, O, L, Of, QS> SelectableExpression<QS> for SelectStatement<ST, S, F, W, O, L, Of> where
-    SelectStatement<ST, S, F, W, O, L, Of>: Expression,
+impl<ST, S, F, W, O, L, Of, G, H, D, QS> SelectableExpression<QS> for SelectStatement<ST, S, F, W, O, L, Of, G, H, D> where
+    SelectStatement<ST, S, F, W, O, L, Of, G, H, D>: Expression,
+{
+}
+
+impl<ST, S, F, W, O, L, Of, G, H, D> NonAggregate for SelectStatement<ST, S, F, W, O, L, Of, G, H, D> where
+    SelectStatement<ST, S, F, W, O, L, Of, G, H, D>: Expression,
 {
 }
 
-impl<ST, S, F, W, O, L, Of> NonAggregate for SelectStatement<ST, S, F, W, O, L, Of> where
-    SelectStatement<ST, S, F, W, O, L, Of>: Expression,
+impl<ST, S, F, W, O, L, Of, G, H, D, GB> ValidGrouping<GB>
+    for SelectStatement<ST, S, F, W, O, L, Of, G, H, D> where
+    SelectStatement<ST, S, F, W, O, L, Of, G, H, D>: Expression,
 {
 }
@@ -0,0 +1,49 @@
+use backend::{Sqlite, SqliteType};
+use super::{QueryBuilder, BuildQueryResult, Context};
+use types::HasSqlType;
+
+pub struct SqliteQueryBuilder {
+    pub sql: String,
+    pub binds: Vec<(SqliteType, Option<Vec<u8>>)>,
+    context_stack: Vec<Context>,
+}
+
+impl SqliteQueryBuilder {
+    pub fn new() -> Self {
+        SqliteQueryBuilder {
+            sql: String::new(),
+            binds: Vec::new(),
+            context_stack: Vec::new(),
+        }
+    }
+}
+
+impl QueryBuilder<Sqlite> for SqliteQueryBuilder {
+    fn push_sql(&mut self, sql: &str) {
+        self.sql.push_str(sql);
+    }
+
+    fn push_identifier(&mut self, identifier: &str) -> BuildQueryResult {
+        self.push_sql("\"");
+        self.push_sql(&identifier.replace("\"", "\"\""));
+        self.push_sql("\"");
+        Ok(())
+    }
+
+    /// SQLite has no `DEFAULT` keyword for use in `VALUES`, so unlike PG a
+    /// `None` is always sent as `NULL`, even when inserting.
+    fn push_bound_value<T>(&mut self, bind: Option<Vec<u8>>) where
+        Sqlite: HasSqlType<T>,
+    {
+        self.push_sql("?");
+        self.binds.push((Sqlite::metadata(), bind));
+    }
+
+    fn push_context(&mut self, context: Context) {
+        self.context_stack.push(context);
+    }
+
+    fn pop_context(&mut self) {
+        self.context_stack.pop();
+    }
+}
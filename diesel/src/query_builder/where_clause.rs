@@ -53,3 +53,56 @@ impl<Expr, Predicate> WhereAnd<Predicate> for WhereClause<Expr> where
         WhereClause(self.0.and(predicate))
     }
 }
+
+/// The where clause of a boxed query. Unlike `WhereClause`, its type doesn't
+/// change when another predicate is added.
+pub enum BoxedWhereClause<'a, QS, DB> {
+    Where(Box<BoxableExpression<QS, Bool, DB, SqlType=Bool> + 'a>),
+    None,
+}
+
+impl<'a, QS, DB> QueryFragment<DB> for BoxedWhereClause<'a, QS, DB> where
+    DB: Backend,
+{
+    fn to_sql(&self, out: &mut DB::QueryBuilder) -> BuildQueryResult {
+        match *self {
+            BoxedWhereClause::Where(ref predicate) => {
+                out.push_sql(" WHERE ");
+                predicate.to_sql(out)
+            }
+            BoxedWhereClause::None => Ok(()),
+        }
+    }
+}
+
+impl<'a, QS, DB, Predicate> WhereAnd<Predicate> for BoxedWhereClause<'a, QS, DB> where
+    DB: Backend + 'a,
+    QS: 'a,
+    Predicate: BoxableExpression<QS, Bool, DB, SqlType=Bool> + 'a,
+{
+    type Output = Self;
+
+    fn and(self, predicate: Predicate) -> Self::Output {
+        match self {
+            BoxedWhereClause::Where(where_clause) => {
+                BoxedWhereClause::Where(Box::new(where_clause.and(predicate)))
+            }
+            BoxedWhereClause::None => BoxedWhereClause::Where(Box::new(predicate)),
+        }
+    }
+}
+
+impl<'a, QS, DB> From<NoWhereClause> for BoxedWhereClause<'a, QS, DB> {
+    fn from(_: NoWhereClause) -> Self {
+        BoxedWhereClause::None
+    }
+}
+
+impl<'a, QS, DB, Expr> From<WhereClause<Expr>> for BoxedWhereClause<'a, QS, DB> where
+    DB: Backend,
+    Expr: BoxableExpression<QS, Bool, DB, SqlType=Bool> + 'a,
+{
+    fn from(where_clause: WhereClause<Expr>) -> Self {
+        BoxedWhereClause::Where(Box::new(where_clause.0))
+    }
+}
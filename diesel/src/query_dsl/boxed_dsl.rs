@@ -0,0 +1,55 @@
+use query_builder::AsQuery;
+use query_source::QuerySource;
+
+/// Boxes the pieces of a query into a single type. This is useful for cases
+/// where you want to conditionally modify a query, but need the type to remain
+/// the same. The SQL type and the query source are still part of the type, so
+/// `.filter()` and `.order()` are checked against the query source as usual.
+/// The boxed query can only be executed on PostgreSQL.
+///
+/// The type of the group by clause also remains part of the type, so that
+/// orderings given after boxing are checked against it like they are before.
+/// Any order given after boxing replaces the previous one, and is no longer
+/// checked against a `.distinct_on()` clause.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate diesel;
+/// # include!("src/doctest_setup.rs");
+/// #
+/// # table! {
+/// #     users {
+/// #         id -> Serial,
+/// #         name -> VarChar,
+/// #     }
+/// # }
+/// #
+/// # fn main() {
+/// #     use self::users::dsl::*;
+/// #     let connection = establish_connection();
+/// let search_for_name: Option<&str> = Some("Tess");
+/// let mut query = users.select(id).into_boxed();
+/// if let Some(search_name) = search_for_name {
+///     query = query.filter(name.eq(search_name));
+/// }
+/// query = query.order(id.desc());
+/// assert_eq!(Ok(vec![2]), query.load(&connection).map(Iterator::collect));
+/// # }
+/// ```
+pub trait BoxedDsl<'a> {
+    type Output;
+
+    fn into_boxed(self) -> Self::Output;
+}
+
+impl<'a, T> BoxedDsl<'a> for T where
+    T: QuerySource + AsQuery,
+    T::Query: BoxedDsl<'a>,
+{
+    type Output = <T::Query as BoxedDsl<'a>>::Output;
+
+    fn into_boxed(self) -> Self::Output {
+        self.as_query().into_boxed()
+    }
+}
@@ -0,0 +1,75 @@
+use query_builder::AsQuery;
+use query_builder::combination::*;
+
+/// Combines the rows of two queries which return the same SQL type. This is
+/// automatically implemented for all queries.
+///
+/// The combined query can be ordered and limited. Since the order clause can
+/// only refer to the columns of the result by name, it can only contain
+/// columns selected by the left hand query, optionally with `.asc()` or
+/// `.desc()` applied. This isn't checked at compile time, so ordering by any
+/// other column is an error from the database.
+///
+/// On SQLite the queries being combined can't be ordered or limited
+/// themselves, only the combined query can.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate diesel;
+/// # include!("src/doctest_setup.rs");
+/// #
+/// # table! {
+/// #     users {
+/// #         id -> Serial,
+/// #         name -> VarChar,
+/// #     }
+/// # }
+/// #
+/// # fn main() {
+/// #     use self::users::dsl::*;
+/// #     let connection = establish_connection();
+/// let seans = users.select(name).filter(name.eq("Sean"));
+/// let everyone = users.select(name);
+/// let names = seans.union_all(everyone)
+///     .order(name)
+///     .load(&connection).unwrap()
+///     .collect::<Vec<String>>();
+/// assert_eq!(vec!["Sean".to_string(), "Sean".to_string(), "Tess".to_string()], names);
+///
+/// let not_sean = everyone.except(seans).load::<String>(&connection).unwrap().collect::<Vec<_>>();
+/// assert_eq!(vec!["Tess".to_string()], not_sean);
+/// # }
+/// ```
+pub trait CombineDsl: AsQuery + Sized {
+    /// Combines the rows of both queries, removing duplicate rows.
+    fn union<Rhs>(self, rhs: Rhs) -> CombinedQuery<Self::Query, Rhs::Query, Union> where
+        Rhs: AsQuery<SqlType=Self::SqlType>,
+    {
+        CombinedQuery::new(self.as_query(), rhs.as_query(), Union)
+    }
+
+    /// Combines the rows of both queries, keeping duplicate rows.
+    fn union_all<Rhs>(self, rhs: Rhs) -> CombinedQuery<Self::Query, Rhs::Query, UnionAll> where
+        Rhs: AsQuery<SqlType=Self::SqlType>,
+    {
+        CombinedQuery::new(self.as_query(), rhs.as_query(), UnionAll)
+    }
+
+    /// Returns the distinct rows which are returned by both queries.
+    fn intersect<Rhs>(self, rhs: Rhs) -> CombinedQuery<Self::Query, Rhs::Query, Intersect> where
+        Rhs: AsQuery<SqlType=Self::SqlType>,
+    {
+        CombinedQuery::new(self.as_query(), rhs.as_query(), Intersect)
+    }
+
+    /// Returns the distinct rows of this query which aren't returned by the
+    /// other query.
+    fn except<Rhs>(self, rhs: Rhs) -> CombinedQuery<Self::Query, Rhs::Query, Except> where
+        Rhs: AsQuery<SqlType=Self::SqlType>,
+    {
+        CombinedQuery::new(self.as_query(), rhs.as_query(), Except)
+    }
+}
+
+impl<T: AsQuery> CombineDsl for T {}
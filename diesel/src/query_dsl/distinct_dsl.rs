@@ -0,0 +1,99 @@
+use expression::Expression;
+use query_builder::{Query, AsQuery};
+use query_source::QuerySource;
+
+/// Adds the `DISTINCT` keyword to a query, removing duplicate rows. This is
+/// automatically implemented for the various query builder types.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate diesel;
+/// # include!("src/doctest_setup.rs");
+/// #
+/// # table! {
+/// #     users {
+/// #         id -> Serial,
+/// #         name -> VarChar,
+/// #     }
+/// # }
+/// #
+/// # fn main() {
+/// #     use self::users::dsl::*;
+/// #     let connection = establish_connection();
+/// connection.execute("INSERT INTO users (name) VALUES ('Sean')").unwrap();
+/// let names = users.select(name).distinct().order(name)
+///     .load(&connection).unwrap()
+///     .collect::<Vec<String>>();
+/// assert_eq!(vec!["Sean".to_string(), "Tess".to_string()], names);
+/// # }
+/// ```
+pub trait DistinctDsl {
+    type Output: Query;
+
+    fn distinct(self) -> Self::Output;
+}
+
+impl<T> DistinctDsl for T where
+    T: QuerySource + AsQuery,
+    T::Query: DistinctDsl,
+{
+    type Output = <T::Query as DistinctDsl>::Output;
+
+    fn distinct(self) -> Self::Output {
+        self.as_query().distinct()
+    }
+}
+
+/// Adds a PostgreSQL `DISTINCT ON` clause to a query, which keeps only the
+/// first row of each set of rows where the given expression is equal. If
+/// there was already a distinct clause, it will be overridden.
+///
+/// PostgreSQL requires the order clause to start with the distinct
+/// expression. This is checked at compile time when the query is ordered by
+/// the distinct expression itself, by it with `.asc()` or `.desc()` applied,
+/// or by a tuple starting with either of those. Ordering by anything else is
+/// a compile error.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate diesel;
+/// # include!("src/doctest_setup.rs");
+/// #
+/// # table! {
+/// #     users {
+/// #         id -> Serial,
+/// #         name -> VarChar,
+/// #     }
+/// # }
+/// #
+/// # fn main() {
+/// #     use self::users::dsl::*;
+/// #     let connection = establish_connection();
+/// connection.execute("INSERT INTO users (name) VALUES ('Sean')").unwrap();
+/// let newest_user_per_name = users.distinct_on(name)
+///     .order((name, id.desc()))
+///     .load(&connection).unwrap()
+///     .collect::<Vec<(i32, String)>>();
+/// let expected = vec![(3, "Sean".to_string()), (2, "Tess".to_string())];
+/// assert_eq!(expected, newest_user_per_name);
+/// # }
+/// ```
+pub trait DistinctOnDsl<Expr: Expression> {
+    type Output: Query;
+
+    fn distinct_on(self, expr: Expr) -> Self::Output;
+}
+
+impl<T, Expr> DistinctOnDsl<Expr> for T where
+    Expr: Expression,
+    T: QuerySource + AsQuery,
+    T::Query: DistinctOnDsl<Expr>,
+{
+    type Output = <T::Query as DistinctOnDsl<Expr>>::Output;
+
+    fn distinct_on(self, expr: Expr) -> Self::Output {
+        self.as_query().distinct_on(expr)
+    }
+}
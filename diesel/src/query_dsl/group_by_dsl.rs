@@ -0,0 +1,60 @@
+use expression::Expression;
+use query_builder::AsQuery;
+use query_source::QuerySource;
+
+/// Sets the group by clause of a query. If there was already a group by
+/// clause, it will be overridden. This is automatically implemented for the
+/// various query builder types. It can't be called once `.having` has been,
+/// as the having clause was checked against the previous group by clause.
+///
+/// Once a query is grouped, every expression which is selected or ordered by
+/// outside of an aggregate function must appear in the group by clause. This
+/// is checked at compile time, but only when the group by clause consists of
+/// columns from the same table as the selected columns. Since the default
+/// select clause of a table selects every column, `.select` must be called
+/// after `.group_by`.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate diesel;
+/// # include!("src/doctest_setup.rs");
+/// # use diesel::expression::dsl::*;
+/// #
+/// # table! {
+/// #     users {
+/// #         id -> Serial,
+/// #         name -> VarChar,
+/// #     }
+/// # }
+/// #
+/// # fn main() {
+/// #     use self::users::dsl::*;
+/// #     let connection = establish_connection();
+/// #     connection.execute("INSERT INTO users (name) VALUES ('Sean')").unwrap();
+/// let users_per_name = users.group_by(name)
+///     .select((name, count(id)))
+///     .order(name)
+///     .load(&connection)
+///     .unwrap()
+///     .collect::<Vec<(String, i64)>>();
+/// assert_eq!(vec![("Sean".to_string(), 2), ("Tess".to_string(), 1)], users_per_name);
+/// # }
+/// ```
+pub trait GroupByDsl<Expr: Expression> {
+    type Output;
+
+    fn group_by(self, expr: Expr) -> Self::Output;
+}
+
+impl<T, Expr> GroupByDsl<Expr> for T where
+    Expr: Expression,
+    T: QuerySource + AsQuery,
+    T::Query: GroupByDsl<Expr>,
+{
+    type Output = <T::Query as GroupByDsl<Expr>>::Output;
+
+    fn group_by(self, expr: Expr) -> Self::Output {
+        self.as_query().group_by(expr)
+    }
+}
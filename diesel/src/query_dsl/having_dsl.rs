@@ -0,0 +1,41 @@
+use expression::Expression;
+use types::Bool;
+
+/// Adds to the `HAVING` clause of a grouped query. If there is already a
+/// `HAVING` clause, the result will be `old AND new`. This is only available
+/// after calling [`group_by`](trait.GroupByDsl.html), and the predicate may
+/// only reference columns which appear in the group by clause outside of
+/// aggregate functions.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate diesel;
+/// # include!("src/doctest_setup.rs");
+/// # use diesel::expression::dsl::*;
+/// #
+/// # table! {
+/// #     users {
+/// #         id -> Serial,
+/// #         name -> VarChar,
+/// #     }
+/// # }
+/// #
+/// # fn main() {
+/// #     use self::users::dsl::*;
+/// #     let connection = establish_connection();
+/// #     connection.execute("INSERT INTO users (name) VALUES ('Sean')").unwrap();
+/// let repeated_names = users.group_by(name)
+///     .select(name)
+///     .having(count(id).gt(1))
+///     .load(&connection)
+///     .unwrap()
+///     .collect::<Vec<String>>();
+/// assert_eq!(vec!["Sean".to_string()], repeated_names);
+/// # }
+/// ```
+pub trait HavingDsl<Predicate: Expression<SqlType=Bool>> {
+    type Output;
+
+    fn having(self, predicate: Predicate) -> Self::Output;
+}
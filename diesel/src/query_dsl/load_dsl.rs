@@ -13,13 +13,38 @@ pub trait LoadDsl<Conn: Connection>: AsQuery + Sized where
     Conn::Backend: HasSqlType<Self::SqlType>,
 {
     /// Executes the given query, returning an `Iterator` over the returned
-    /// rows.
+    /// rows. Every row is deserialized before this returns, so a value which
+    /// can't be loaded into `U` results in `Err(DeserializationError)` rather
+    /// than a partially consumed iterator.
     fn load<'a, U>(self, conn: &Conn) -> QueryResult<Box<Iterator<Item=U> + 'a>> where
         U: Queryable<Self::SqlType, Conn::Backend> + 'a,
     {
         conn.query_all(self)
     }
 
+    /// Executes the given query, returning an `Iterator` which reads rows
+    /// from the database as it is advanced, rather than loading the entire
+    /// result set into memory first. Use this when loading more rows than
+    /// you'd like to hold in memory at once.
+    ///
+    /// Errors which occur part way through reading the results are returned
+    /// from the iterator. The connection can't be used to run other queries
+    /// until the iterator has been exhausted or dropped.
+    ///
+    /// With PostgreSQL, this uses single row mode. SQLite steps through
+    /// the results lazily as well, but MySQL buffers the entire result set on
+    /// the client.
+    ///
+    /// Dropping the iterator before it is exhausted cancels a PostgreSQL
+    /// query. Inside of a transaction, where cancelling would abort the
+    /// transaction, the remaining rows are read and discarded instead, which
+    /// blocks until the query has finished.
+    fn load_iter<'a, U>(self, conn: &'a Conn) -> QueryResult<Box<Iterator<Item=QueryResult<U>> + 'a>> where
+        U: Queryable<Self::SqlType, Conn::Backend> + 'a,
+    {
+        conn.stream_query(self)
+    }
+
     /// Attempts to load a single record. Returns `Ok(record)` if found, and
     /// `Err(NotFound)` if no results are returned. If the query truly is
     /// optional, you can call `.optional()` on the result of this to get a
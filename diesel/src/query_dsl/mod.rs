@@ -1,5 +1,8 @@
 mod belonging_to_dsl;
+mod boxed_dsl;
+mod combine_dsl;
 mod count_dsl;
+mod distinct_dsl;
 #[doc(hidden)]
 pub mod limit_dsl;
 #[doc(hidden)]
@@ -8,16 +11,25 @@ pub mod load_dsl;
 pub mod select_dsl;
 #[doc(hidden)]
 pub mod filter_dsl;
+mod group_by_dsl;
+mod having_dsl;
 mod offset_dsl;
 mod order_dsl;
+mod single_value_dsl;
 mod with_dsl;
 
 pub use self::belonging_to_dsl::BelongingToDsl;
+pub use self::boxed_dsl::BoxedDsl;
+pub use self::combine_dsl::CombineDsl;
 pub use self::count_dsl::CountDsl;
+pub use self::distinct_dsl::{DistinctDsl, DistinctOnDsl};
 pub use self::filter_dsl::{FilterDsl, FindDsl};
+pub use self::group_by_dsl::GroupByDsl;
+pub use self::having_dsl::HavingDsl;
 pub use self::limit_dsl::LimitDsl;
 pub use self::load_dsl::{LoadDsl, ExecuteDsl};
 pub use self::offset_dsl::OffsetDsl;
 pub use self::order_dsl::OrderDsl;
 pub use self::select_dsl::{SelectDsl, SelectSqlDsl};
+pub use self::single_value_dsl::SingleValueDsl;
 pub use self::with_dsl::{WithDsl, WithQuerySource};
@@ -0,0 +1,42 @@
+use expression::subselect::Subselect;
+use super::LimitDsl;
+
+/// Allows a query which selects a single column to be used as an expression,
+/// for example in the select clause of another query. The query is limited to
+/// one row, and evaluates to `NULL` when it returns no rows. Columns of the
+/// outer query can be referenced by wrapping them in
+/// [`outer`](../expression/dsl/fn.outer.html). Automatically implemented for
+/// all types which implement `LimitDsl`.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate diesel;
+/// # include!("src/doctest_setup.rs");
+/// #
+/// # table! {
+/// #     users {
+/// #         id -> Serial,
+/// #         name -> VarChar,
+/// #     }
+/// # }
+/// #
+/// # fn main() {
+/// #     use self::users::dsl::*;
+/// #     let connection = establish_connection();
+/// let first_name = users.select(name).order(name).single_value();
+/// let data = users.select((name, first_name)).order(id);
+/// let expected = vec![
+///     ("Sean".to_string(), Some("Sean".to_string())),
+///     ("Tess".to_string(), Some("Sean".to_string())),
+/// ];
+/// assert_eq!(Ok(expected), data.load(&connection).map(Iterator::collect));
+/// # }
+/// ```
+pub trait SingleValueDsl: LimitDsl + Sized {
+    fn single_value(self) -> Subselect<Self::Output> {
+        Subselect::new(self.limit(1))
+    }
+}
+
+impl<T: LimitDsl> SingleValueDsl for T {}
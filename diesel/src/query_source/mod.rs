@@ -45,7 +45,7 @@ pub trait Column: Expression {
 /// generated by the [`table!` macro](../macro.table!.html).
 pub trait Table: QuerySource + AsQuery + Sized {
     type PrimaryKey: Column<Table=Self> + Expression + NonAggregate;
-    type AllColumns: SelectableExpression<Self>;
+    type AllColumns: SelectableExpression<Self> + NonAggregate;
 
     fn name() -> &'static str;
     fn primary_key(&self) -> Self::PrimaryKey;
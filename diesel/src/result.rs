@@ -2,19 +2,121 @@ use std::convert::From;
 use std::error::Error as StdError;
 use std::fmt::{self, Display, Write};
 use std::ffi::NulError;
+use std::io;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
 /// The generic "things can fail in a myriad of ways" enum. This type is not
 /// indended to be exhaustively matched, and new variants may be added in the
 /// future without a major version bump.
 pub enum Error {
     InvalidCString(NulError),
-    DatabaseError(String),
+    /// An error returned by the database while running a query. The kind
+    /// identifies common errors which applications may want to handle, and
+    /// the second field contains everything the database told us about it.
+    DatabaseError(DatabaseErrorKind, Box<DatabaseErrorInformation + Send + Sync>),
     NotFound,
+    /// A value returned by the database could not be converted to the Rust
+    /// type it was loaded into, such as an unexpected `NULL`. Contains the
+    /// zero based index of the column which failed, and the reason.
+    DeserializationError(usize, Box<StdError + Send + Sync>),
+    /// The connection to the database was lost, for example because the
+    /// server was restarted. If this happened inside of a transaction, the
+    /// transaction has been rolled back by the server.
+    ConnectionLost,
+    /// An isolation level, read mode or deferrable mode was given to
+    /// [`TransactionBuilder`](../connection/pg/struct.TransactionBuilder.html)
+    /// while a transaction was already open. They can only be set on the
+    /// outermost transaction.
+    InvalidTransactionOptions,
+    /// The query could not be built, for example because the value of a bind
+    /// param couldn't be serialized.
+    QueryBuilderError(Box<StdError + Send + Sync>),
+    /// A `COPY ... FROM STDIN` or `COPY ... TO STDOUT` statement was run with
+    /// something other than `PgConnection::copy_in` or `copy_out`, which are
+    /// the only ways to send or receive its data. The `COPY` was abandoned.
+    UnexpectedCopy,
+    /// An I/O error which happened on the client, such as a failure to write
+    /// the data exported by `PgConnection::copy_out`.
+    IoError(io::Error),
+    /// A cancel request could not be sent by a
+    /// [`CancelHandle`](../connection/pg/struct.CancelHandle.html), or the
+    /// handle could not be created. Contains the reason given by libpq.
+    CancelFailed(String),
     #[doc(hidden)]
     __Nonexhaustive,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The kinds of database errors which are common enough to be worth
+/// identifying. New kinds may be added in the future without a major version
+/// bump, so this should not be matched exhaustively.
+pub enum DatabaseErrorKind {
+    UniqueViolation,
+    ForeignKeyViolation,
+    NotNullViolation,
+    SerializationFailure,
+    Deadlock,
+    /// The query was cancelled, either explicitly or because it ran for
+    /// longer than the statement timeout.
+    QueryCanceled,
+    #[doc(hidden)]
+    __Unknown,
+}
+
+/// Details about a [`DatabaseError`](enum.Error.html#variant.DatabaseError).
+/// Everything but the message is optional, and will only be present when the
+/// backend reports it. PostgreSQL provides all of these fields for most
+/// errors, while SQLite only provides the message. Implementors must be
+/// `Send` and `Sync`, so that errors can be sent between threads.
+pub trait DatabaseErrorInformation: Send + Sync {
+    /// The primary, human readable error message.
+    fn message(&self) -> &str;
+    /// The five character SQLSTATE code of the error.
+    fn sql_state(&self) -> Option<&str>;
+    /// A secondary error message, with more detail about the problem.
+    fn details(&self) -> Option<&str>;
+    /// A suggestion about what to do about the problem.
+    fn hint(&self) -> Option<&str>;
+    fn table_name(&self) -> Option<&str>;
+    fn column_name(&self) -> Option<&str>;
+    fn constraint_name(&self) -> Option<&str>;
+}
+
+impl fmt::Debug for DatabaseErrorInformation + Send + Sync {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.message(), f)
+    }
+}
+
+impl DatabaseErrorInformation for String {
+    fn message(&self) -> &str {
+        self
+    }
+
+    fn sql_state(&self) -> Option<&str> { None }
+    fn details(&self) -> Option<&str> { None }
+    fn hint(&self) -> Option<&str> { None }
+    fn table_name(&self) -> Option<&str> { None }
+    fn column_name(&self) -> Option<&str> { None }
+    fn constraint_name(&self) -> Option<&str> { None }
+}
+
+impl DatabaseErrorKind {
+    /// Determines the kind of error from a SQLSTATE code, as reported by
+    /// PostgreSQL.
+    pub fn from_sql_state(sql_state: &str) -> Self {
+        match sql_state {
+            "23505" => DatabaseErrorKind::UniqueViolation,
+            "23503" => DatabaseErrorKind::ForeignKeyViolation,
+            "23502" => DatabaseErrorKind::NotNullViolation,
+            "40001" => DatabaseErrorKind::SerializationFailure,
+            "40P01" => DatabaseErrorKind::Deadlock,
+            "57014" => DatabaseErrorKind::QueryCanceled,
+            _ => DatabaseErrorKind::__Unknown,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum ConnectionError {
     InvalidCString(NulError),
@@ -27,9 +129,20 @@ pub enum TransactionError<E> {
     UserReturnedError(E),
 }
 
+#[derive(Debug)]
+/// Returned by [`Pool::get`](../connection/pool/struct.Pool.html#method.get)
+/// when no connection could be checked out.
+pub enum PoolError {
+    /// No connection became available before the checkout timeout elapsed.
+    Timeout,
+    /// The pool tried to open a new connection, and failed.
+    ConnectionError(ConnectionError),
+}
+
 pub type QueryResult<T> = Result<T, Error>;
 pub type ConnectionResult<T> = Result<T, ConnectionError>;
 pub type TransactionResult<T, E> = Result<T, TransactionError<E>>;
+pub type PoolResult<T> = Result<T, PoolError>;
 
 pub trait OptionalExtension<T> {
     fn optional(self) -> Result<Option<T>, Error>;
@@ -76,8 +189,17 @@ impl Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             &Error::InvalidCString(ref nul_err) => nul_err.fmt(f),
-            &Error::DatabaseError(ref s) => write!(f, "{}", &s),
+            &Error::DatabaseError(_, ref e) => write!(f, "{}", e.message()),
             &Error::NotFound => f.write_str("NotFound"),
+            &Error::DeserializationError(column, ref e) =>
+                write!(f, "Error deserializing column {}: {}", column, e),
+            &Error::ConnectionLost => f.write_str("The connection to the database was lost"),
+            &Error::InvalidTransactionOptions =>
+                f.write_str("Transaction options can only be set on the outermost transaction"),
+            &Error::QueryBuilderError(ref e) => write!(f, "Could not build the query: {}", e),
+            &Error::UnexpectedCopy => f.write_str("COPY can only be run with `copy_in` or `copy_out`"),
+            &Error::IoError(ref e) => e.fmt(f),
+            &Error::CancelFailed(ref message) => write!(f, "Could not cancel the query: {}", message),
             &Error::__Nonexhaustive => unreachable!(),
         }
     }
@@ -87,11 +209,52 @@ impl StdError for Error {
     fn description(&self) -> &str {
         match self {
             &Error::InvalidCString(ref nul_err) => nul_err.description(),
-            &Error::DatabaseError(ref s) => &s,
+            &Error::DatabaseError(_, ref e) => e.message(),
             &Error::NotFound => "Record not found",
+            &Error::DeserializationError(_, ref e) => e.description(),
+            &Error::ConnectionLost => "The connection to the database was lost",
+            &Error::InvalidTransactionOptions =>
+                "Transaction options can only be set on the outermost transaction",
+            &Error::QueryBuilderError(ref e) => e.description(),
+            &Error::UnexpectedCopy => "COPY can only be run with `copy_in` or `copy_out`",
+            &Error::IoError(ref e) => e.description(),
+            &Error::CancelFailed(ref message) => message,
             &Error::__Nonexhaustive => unreachable!(),
         }
     }
+
+    fn cause(&self) -> Option<&StdError> {
+        match self {
+            &Error::DeserializationError(_, ref e) => Some(&**e),
+            &Error::QueryBuilderError(ref e) => Some(&**e),
+            &Error::IoError(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+// Database, deserialization and query builder errors are compared by their
+// messages, as the underlying error information can be anything. I/O errors
+// are compared by their kind.
+impl PartialEq for Error {
+    fn eq(&self, other: &Error) -> bool {
+        match (self, other) {
+            (&Error::InvalidCString(ref a), &Error::InvalidCString(ref b)) => a == b,
+            (&Error::DatabaseError(a_kind, ref a), &Error::DatabaseError(b_kind, ref b)) =>
+                a_kind == b_kind && a.message() == b.message(),
+            (&Error::NotFound, &Error::NotFound) => true,
+            (&Error::DeserializationError(a_column, ref a), &Error::DeserializationError(b_column, ref b)) =>
+                a_column == b_column && a.description() == b.description(),
+            (&Error::ConnectionLost, &Error::ConnectionLost) => true,
+            (&Error::InvalidTransactionOptions, &Error::InvalidTransactionOptions) => true,
+            (&Error::QueryBuilderError(ref a), &Error::QueryBuilderError(ref b)) =>
+                a.description() == b.description(),
+            (&Error::UnexpectedCopy, &Error::UnexpectedCopy) => true,
+            (&Error::IoError(ref a), &Error::IoError(ref b)) => a.kind() == b.kind(),
+            (&Error::CancelFailed(ref a), &Error::CancelFailed(ref b)) => a == b,
+            _ => false,
+        }
+    }
 }
 
 impl Display for ConnectionError {
@@ -112,6 +275,30 @@ impl StdError for ConnectionError {
     }
 }
 
+impl From<ConnectionError> for PoolError {
+    fn from(e: ConnectionError) -> Self {
+        PoolError::ConnectionError(e)
+    }
+}
+
+impl Display for PoolError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            &PoolError::Timeout => f.write_str("Timed out waiting for a connection"),
+            &PoolError::ConnectionError(ref e) => e.fmt(f),
+        }
+    }
+}
+
+impl StdError for PoolError {
+    fn description(&self) -> &str {
+        match self {
+            &PoolError::Timeout => "Timed out waiting for a connection",
+            &PoolError::ConnectionError(ref e) => e.description(),
+        }
+    }
+}
+
 impl<E: Display> Display for TransactionError<E> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -1,11 +1,51 @@
 use backend::{Backend, Pg};
 use db_result::PgResult;
+use query_source::Queryable;
+use result::{Error, QueryResult};
+use types::{HasSqlType, FromSqlRow};
 
 pub trait Row<DB: Backend> {
     fn take(&mut self) -> Option<&DB::RawValue>;
     fn next_is_null(&self, count: usize) -> bool;
 }
 
+/// Builds a `T` from the row, returning `Error::DeserializationError` with
+/// the index of the column that couldn't be read if it fails.
+pub fn deserialize_row<ST, T, DB, R>(row: &mut R) -> QueryResult<T> where
+    DB: Backend + HasSqlType<ST>,
+    T: Queryable<ST, DB>,
+    R: Row<DB>,
+{
+    let mut row = ColumnCountingRow { row: row, columns_taken: 0 };
+    match T::Row::build_from_row(&mut row) {
+        Ok(values) => Ok(T::build(values)),
+        Err(e) => {
+            // The column which failed has already been taken
+            let column = if row.columns_taken == 0 { 0 } else { row.columns_taken - 1 };
+            Err(Error::DeserializationError(column, e))
+        }
+    }
+}
+
+struct ColumnCountingRow<'a, R: 'a> {
+    row: &'a mut R,
+    columns_taken: usize,
+}
+
+impl<'a, R, DB> Row<DB> for ColumnCountingRow<'a, R> where
+    DB: Backend,
+    R: Row<DB>,
+{
+    fn take(&mut self) -> Option<&DB::RawValue> {
+        self.columns_taken += 1;
+        self.row.take()
+    }
+
+    fn next_is_null(&self, count: usize) -> bool {
+        self.row.next_is_null(count)
+    }
+}
+
 pub struct PgRow<'a> {
     db_result: &'a PgResult,
     row_idx: usize,
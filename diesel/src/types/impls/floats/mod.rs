@@ -12,9 +12,9 @@ use types::{self, FromSql, ToSql, IsNull};
 mod quickcheck_impls;
 
 impl<DB: Backend<RawValue=[u8]>> FromSql<types::Float, DB> for f32 {
-    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error + Send + Sync>> {
         let mut bytes = not_none!(bytes);
-        bytes.read_f32::<BigEndian>().map_err(|e| Box::new(e) as Box<Error>)
+        bytes.read_f32::<BigEndian>().map_err(|e| Box::new(e) as Box<Error + Send + Sync>)
     }
 }
 
@@ -27,9 +27,9 @@ impl<DB: Backend> ToSql<types::Float, DB> for f32 {
 }
 
 impl<DB: Backend<RawValue=[u8]>> FromSql<types::Double, DB> for f64 {
-    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error + Send + Sync>> {
         let mut bytes = not_none!(bytes);
-        bytes.read_f64::<BigEndian>().map_err(|e| Box::new(e) as Box<Error>)
+        bytes.read_f64::<BigEndian>().map_err(|e| Box::new(e) as Box<Error + Send + Sync>)
     }
 }
 
@@ -74,7 +74,7 @@ impl Error for InvalidNumericSign {
 use backend::Pg;
 
 impl FromSql<types::Numeric, Pg> for PgNumeric {
-    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error + Send + Sync>> {
         let mut bytes = not_none!(bytes);
         let ndigits = try!(bytes.read_u16::<BigEndian>());
         let mut digits = Vec::with_capacity(ndigits as usize);
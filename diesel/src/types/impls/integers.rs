@@ -9,9 +9,9 @@ use super::option::UnexpectedNullError;
 use types::{self, FromSql, ToSql, IsNull};
 
 impl<DB: Backend<RawValue=[u8]>> FromSql<types::SmallInt, DB> for i16 {
-    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error + Send + Sync>> {
         let mut bytes = not_none!(bytes);
-        bytes.read_i16::<BigEndian>().map_err(|e| Box::new(e) as Box<Error>)
+        bytes.read_i16::<BigEndian>().map_err(|e| Box::new(e) as Box<Error + Send + Sync>)
     }
 }
 
@@ -24,9 +24,9 @@ impl<DB: Backend> ToSql<types::SmallInt, DB> for i16 {
 }
 
 impl<DB: Backend<RawValue=[u8]>> FromSql<types::Integer, DB> for i32 {
-    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error + Send + Sync>> {
         let mut bytes = not_none!(bytes);
-        bytes.read_i32::<BigEndian>().map_err(|e| Box::new(e) as Box<Error>)
+        bytes.read_i32::<BigEndian>().map_err(|e| Box::new(e) as Box<Error + Send + Sync>)
     }
 }
 
@@ -39,9 +39,9 @@ impl<DB: Backend> ToSql<types::Integer, DB> for i32 {
 }
 
 impl<DB: Backend<RawValue=[u8]>> FromSql<types::BigInt, DB> for i64 {
-    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error + Send + Sync>> {
         let mut bytes = not_none!(bytes);
-        bytes.read_i64::<BigEndian>().map_err(|e| Box::new(e) as Box<Error>)
+        bytes.read_i64::<BigEndian>().map_err(|e| Box::new(e) as Box<Error + Send + Sync>)
     }
 }
 
@@ -54,7 +54,7 @@ impl<DB: Backend> ToSql<types::BigInt, DB> for i64 {
 }
 
 impl FromSql<types::Oid, Pg> for u32 {
-    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error + Send + Sync>> {
         let mut bytes = not_none!(bytes);
         bytes.read_u32::<BigEndian>().map_err(|e| e.into())
     }
@@ -0,0 +1,244 @@
+use std::error::Error;
+use std::io::Write;
+use std::iter;
+use std::str;
+
+use backend::{Mysql, MysqlType};
+use data_types::PgNumeric;
+use super::option::UnexpectedNullError;
+use types::{self, FromSql, ToSql, IsNull, HasSqlType};
+
+macro_rules! mysql_types {
+    ($($Source:ident -> $Type:ident),+,) => {$(
+        impl HasSqlType<types::$Source> for Mysql {
+            fn metadata() -> MysqlType {
+                MysqlType::$Type
+            }
+        }
+    )+}
+}
+
+mysql_types! {
+    Bool -> Tiny,
+    SmallInt -> Short,
+    Integer -> Long,
+    BigInt -> LongLong,
+    Float -> Float,
+    Double -> Double,
+    Numeric -> String,
+    VarChar -> String,
+    Text -> String,
+    Binary -> Blob,
+    Date -> String,
+    Time -> String,
+    Timestamp -> String,
+}
+
+impl FromSql<types::Bool, Mysql> for bool {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error + Send + Sync>> {
+        Ok(not_none!(bytes).iter().any(|&byte| byte != 0))
+    }
+}
+
+impl ToSql<types::Bool, Mysql> for bool {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        let tiny_int = if *self { 1 } else { 0 };
+        out.write_all(&[tiny_int])
+            .map(|_| IsNull::No)
+            .map_err(|e| Box::new(e) as Box<Error>)
+    }
+}
+
+// MySQL sends decimals as text, which is converted to the base 10000 digits
+// used by `PgNumeric`.
+impl FromSql<types::Numeric, Mysql> for PgNumeric {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error + Send + Sync>> {
+        let text = try!(str::from_utf8(not_none!(bytes)));
+        parse_decimal(text)
+    }
+}
+
+impl ToSql<types::Numeric, Mysql> for PgNumeric {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        let text = match *self {
+            PgNumeric::Positive { weight, scale, ref digits } =>
+                format_decimal(false, weight, scale, digits),
+            PgNumeric::Negative { weight, scale, ref digits } =>
+                format_decimal(true, weight, scale, digits),
+            PgNumeric::NaN => return Err(Box::<Error + Send + Sync>::from("MySQL decimals can't be NaN")),
+        };
+        out.write_all(text.as_bytes())
+            .map(|_| IsNull::No)
+            .map_err(|e| Box::new(e) as Box<Error>)
+    }
+}
+
+fn parse_decimal(text: &str) -> Result<PgNumeric, Box<Error + Send + Sync>> {
+    let (negative, unsigned) = if text.starts_with('-') {
+        (true, &text[1..])
+    } else {
+        (false, text)
+    };
+    let mut parts = unsigned.splitn(2, '.');
+    let integral = parts.next().unwrap_or("");
+    let fractional = parts.next().unwrap_or("");
+    let is_valid = !(integral.is_empty() && fractional.is_empty()) &&
+        integral.chars().chain(fractional.chars()).all(|c| c.is_digit(10));
+    if !is_valid {
+        return Err(format!("{:?} is not a valid decimal", text).into());
+    }
+
+    // Pad both parts to whole groups of 4 decimal digits
+    let integral_padding = (4 - integral.len() % 4) % 4;
+    let fractional_padding = (4 - fractional.len() % 4) % 4;
+    let padded = iter::repeat(b'0').take(integral_padding)
+        .chain(integral.bytes())
+        .chain(fractional.bytes())
+        .chain(iter::repeat(b'0').take(fractional_padding))
+        .collect::<Vec<_>>();
+    let mut digits = padded.chunks(4)
+        .map(|group| group.iter().fold(0, |acc, &digit| acc * 10 + (digit - b'0') as i16))
+        .collect::<Vec<_>>();
+    let mut weight = ((integral.len() + integral_padding) / 4) as i16 - 1;
+
+    let leading_zeros = digits.iter().take_while(|&&digit| digit == 0).count();
+    digits.drain(..leading_zeros);
+    weight -= leading_zeros as i16;
+    while digits.last() == Some(&0) {
+        digits.pop();
+    }
+    if digits.is_empty() {
+        weight = 0;
+    }
+
+    let scale = fractional.len() as u16;
+    if negative && !digits.is_empty() {
+        Ok(PgNumeric::Negative { weight: weight, scale: scale, digits: digits })
+    } else {
+        Ok(PgNumeric::Positive { weight: weight, scale: scale, digits: digits })
+    }
+}
+
+fn format_decimal(negative: bool, weight: i16, scale: u16, digits: &[i16]) -> String {
+    let digit_at = |idx: i16| if idx < 0 {
+        0
+    } else {
+        digits.get(idx as usize).cloned().unwrap_or(0)
+    };
+
+    let mut result = String::new();
+    if negative {
+        result.push('-');
+    }
+    if weight < 0 {
+        result.push('0');
+    } else {
+        result.push_str(&digit_at(0).to_string());
+        for idx in 1..(weight + 1) {
+            result.push_str(&format!("{:04}", digit_at(idx)));
+        }
+    }
+    if scale > 0 {
+        let mut fractional = String::new();
+        for idx in (weight + 1)..(weight + 1 + (scale as i16 + 3) / 4) {
+            fractional.push_str(&format!("{:04}", digit_at(idx)));
+        }
+        fractional.truncate(scale as usize);
+        result.push('.');
+        result.push_str(&fractional);
+    }
+    result
+}
+
+// Dates and times are sent and received as text as well
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    extern crate chrono;
+
+    use std::error::Error;
+    use std::io::Write;
+    use std::str;
+    use self::chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+    use backend::Mysql;
+    use super::UnexpectedNullError;
+    use types::{self, FromSql, ToSql, IsNull};
+
+    macro_rules! mysql_chrono_impls {
+        ($($Source:ident -> $Target:ident, $format:expr),+,) => {$(
+            impl FromSql<types::$Source, Mysql> for $Target {
+                fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error + Send + Sync>> {
+                    let text = try!(str::from_utf8(not_none!(bytes)));
+                    $Target::parse_from_str(text, $format)
+                        .map_err(|e| Box::new(e) as Box<Error + Send + Sync>)
+                }
+            }
+
+            impl ToSql<types::$Source, Mysql> for $Target {
+                fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+                    out.write_all(self.format($format).to_string().as_bytes())
+                        .map(|_| IsNull::No)
+                        .map_err(|e| Box::new(e) as Box<Error>)
+                }
+            }
+        )+}
+    }
+
+    mysql_chrono_impls! {
+        Date -> NaiveDate, "%Y-%m-%d",
+        Time -> NaiveTime, "%H:%M:%S%.f",
+        Timestamp -> NaiveDateTime, "%Y-%m-%d %H:%M:%S%.f",
+    }
+
+    #[test]
+    fn dates_and_times_are_sent_as_text() {
+        let mut bytes = vec![];
+        let timestamp = NaiveDate::from_ymd(2016, 2, 29).and_hms_micro(13, 5, 7, 123456);
+        ToSql::<types::Timestamp, Mysql>::to_sql(&timestamp, &mut bytes).unwrap();
+        assert_eq!(b"2016-02-29 13:05:07.123456", &*bytes);
+
+        bytes.clear();
+        ToSql::<types::Time, Mysql>::to_sql(&NaiveTime::from_hms(8, 0, 0), &mut bytes).unwrap();
+        assert_eq!(b"08:00:00", &*bytes);
+    }
+
+    #[test]
+    fn dates_and_times_are_read_from_text() {
+        let date = FromSql::<types::Date, Mysql>::from_sql(Some(b"2016-02-29"));
+        assert_eq!(NaiveDate::from_ymd(2016, 2, 29), date.unwrap());
+        let time = FromSql::<types::Time, Mysql>::from_sql(Some(b"13:05:07.5"));
+        assert_eq!(NaiveTime::from_hms_milli(13, 5, 7, 500), time.unwrap());
+        let timestamp = FromSql::<types::Timestamp, Mysql>::from_sql(Some(b"2016-02-29 13:05:07"));
+        assert_eq!(NaiveDate::from_ymd(2016, 2, 29).and_hms(13, 5, 7), timestamp.unwrap());
+    }
+}
+
+#[test]
+fn bool_is_sent_as_a_tiny_int() {
+    let mut bytes = vec![];
+    ToSql::<types::Bool, Mysql>::to_sql(&true, &mut bytes).unwrap();
+    ToSql::<types::Bool, Mysql>::to_sql(&false, &mut bytes).unwrap();
+    assert_eq!(vec![1u8, 0u8], bytes);
+}
+
+#[test]
+fn decimals_are_read_from_text() {
+    let read = |text: &str| FromSql::<types::Numeric, Mysql>::from_sql(Some(text.as_bytes())).unwrap();
+    assert_eq!(PgNumeric::Positive { weight: 1, scale: 3, digits: vec![12, 3456, 7890] },
+               read("123456.789"));
+    assert_eq!(PgNumeric::Negative { weight: -1, scale: 4, digits: vec![1] }, read("-0.0001"));
+    assert_eq!(PgNumeric::Positive { weight: 1, scale: 0, digits: vec![1] }, read("10000"));
+    assert_eq!(PgNumeric::Positive { weight: 0, scale: 2, digits: vec![] }, read("0.00"));
+    assert!(parse_decimal("1e5").is_err());
+}
+
+#[test]
+fn decimals_are_sent_as_text() {
+    for text in &["123456.789", "-0.0001", "10000", "0.00", "1.50", "-98765432.1"] {
+        let numeric = parse_decimal(text).unwrap();
+        let mut bytes = vec![];
+        ToSql::<types::Numeric, Mysql>::to_sql(&numeric, &mut bytes).unwrap();
+        assert_eq!(text.as_bytes(), &*bytes);
+    }
+    assert!(ToSql::<types::Numeric, Mysql>::to_sql(&PgNumeric::NaN, &mut vec![]).is_err());
+}
@@ -0,0 +1,101 @@
+extern crate byteorder;
+
+use self::byteorder::{WriteBytesExt, BigEndian};
+use std::error::Error;
+use std::io::Write;
+
+use backend::{Sqlite, SqliteType};
+use connection::sqlite::SqliteValue;
+use super::option::UnexpectedNullError;
+use types::{self, FromSql, ToSql, IsNull, HasSqlType};
+
+macro_rules! sqlite_types {
+    ($($Source:ident -> $Type:ident),+,) => {$(
+        impl HasSqlType<types::$Source> for Sqlite {
+            fn metadata() -> SqliteType {
+                SqliteType::$Type
+            }
+        }
+    )+}
+}
+
+sqlite_types! {
+    Bool -> Integer,
+    SmallInt -> SmallInt,
+    Integer -> Integer,
+    BigInt -> Long,
+    Float -> Float,
+    Double -> Double,
+    Numeric -> Double,
+    VarChar -> Text,
+    Text -> Text,
+    Binary -> Binary,
+    Date -> Text,
+    Time -> Text,
+    Timestamp -> Text,
+}
+
+impl FromSql<types::Bool, Sqlite> for bool {
+    fn from_sql(value: Option<&SqliteValue>) -> Result<Self, Box<Error + Send + Sync>> {
+        Ok(not_none!(value).read_integer() != 0)
+    }
+}
+
+impl ToSql<types::Bool, Sqlite> for bool {
+    fn to_sql<W: Write>(&self, out: &mut W) -> Result<IsNull, Box<Error>> {
+        let int_value = if *self { 1 } else { 0 };
+        out.write_i32::<BigEndian>(int_value)
+            .map(|_| IsNull::No)
+            .map_err(|e| Box::new(e) as Box<Error>)
+    }
+}
+
+impl FromSql<types::SmallInt, Sqlite> for i16 {
+    fn from_sql(value: Option<&SqliteValue>) -> Result<Self, Box<Error + Send + Sync>> {
+        Ok(not_none!(value).read_integer() as i16)
+    }
+}
+
+impl FromSql<types::Integer, Sqlite> for i32 {
+    fn from_sql(value: Option<&SqliteValue>) -> Result<Self, Box<Error + Send + Sync>> {
+        Ok(not_none!(value).read_integer())
+    }
+}
+
+impl FromSql<types::BigInt, Sqlite> for i64 {
+    fn from_sql(value: Option<&SqliteValue>) -> Result<Self, Box<Error + Send + Sync>> {
+        Ok(not_none!(value).read_long())
+    }
+}
+
+impl FromSql<types::Float, Sqlite> for f32 {
+    fn from_sql(value: Option<&SqliteValue>) -> Result<Self, Box<Error + Send + Sync>> {
+        Ok(not_none!(value).read_double() as f32)
+    }
+}
+
+impl FromSql<types::Double, Sqlite> for f64 {
+    fn from_sql(value: Option<&SqliteValue>) -> Result<Self, Box<Error + Send + Sync>> {
+        Ok(not_none!(value).read_double())
+    }
+}
+
+impl FromSql<types::VarChar, Sqlite> for String {
+    fn from_sql(value: Option<&SqliteValue>) -> Result<Self, Box<Error + Send + Sync>> {
+        Ok(not_none!(value).read_text().into())
+    }
+}
+
+impl FromSql<types::Binary, Sqlite> for Vec<u8> {
+    fn from_sql(value: Option<&SqliteValue>) -> Result<Self, Box<Error + Send + Sync>> {
+        Ok(not_none!(value).read_blob().into())
+    }
+}
+
+#[test]
+fn bool_to_sql_is_sent_as_an_integer() {
+    let mut bytes = vec![];
+    ToSql::<types::Bool, Sqlite>::to_sql(&true, &mut bytes).unwrap();
+    ToSql::<types::Bool, Sqlite>::to_sql(&false, &mut bytes).unwrap();
+    assert_eq!(bytes, vec![0, 0, 0, 1, 0, 0, 0, 0]);
+}
@@ -1,6 +1,8 @@
 use backend::Backend;
-use expression::{Expression, SelectableExpression, NonAggregate};
-use persistable::InsertableColumns;
+use expression::{Expression, SelectableExpression, NonAggregate, ValidGrouping};
+use expression::valid_grouping::{GroupByContains, AnyYes};
+use persistable::{InsertableColumns, CopyInValues};
+use query_builder::group_by_clause::{GroupByClause, NoGroupByClause};
 use query_builder::{Changeset, AsChangeset, QueryBuilder, BuildQueryResult, QueryFragment};
 use query_source::{QuerySource, Queryable, Table, Column};
 use row::Row;
@@ -38,7 +40,7 @@ macro_rules! tuple_impls {
                 $(DB: HasSqlType<$ST>),+,
                 DB: HasSqlType<($($ST,)+)>,
             {
-                fn build_from_row<RowT: Row<DB>>(row: &mut RowT) -> Result<Self, Box<Error>> {
+                fn build_from_row<RowT: Row<DB>>(row: &mut RowT) -> Result<Self, Box<Error + Send + Sync>> {
                     Ok(($(try!($T::build_from_row(row)),)+))
                 }
             }
@@ -49,7 +51,7 @@ macro_rules! tuple_impls {
                 $(DB: HasSqlType<$ST>),+,
                 DB: HasSqlType<($($ST,)+)>,
             {
-                fn build_from_row<RowT: Row<DB>>(row: &mut RowT) -> Result<Self, Box<Error>> {
+                fn build_from_row<RowT: Row<DB>>(row: &mut RowT) -> Result<Self, Box<Error + Send + Sync>> {
                     if e!(row.next_is_null($Tuple)) {
                         Ok(None)
                     } else {
@@ -71,7 +73,7 @@ macro_rules! tuple_impls {
                 }
             }
 
-            impl<$($T: Expression + NonAggregate),+> Expression for ($($T,)+) {
+            impl<$($T: Expression),+> Expression for ($($T,)+) {
                 type SqlType = ($(<$T as Expression>::SqlType),+);
             }
 
@@ -91,6 +93,21 @@ macro_rules! tuple_impls {
             impl<$($T: Expression + NonAggregate),+> NonAggregate for ($($T,)+) {
             }
 
+            impl<$($T: NonAggregate),+> ValidGrouping<NoGroupByClause> for ($($T,)+) {
+            }
+
+            impl<$($T),+, GB> ValidGrouping<GroupByClause<GB>> for ($($T,)+) where
+                $($T: ValidGrouping<GroupByClause<GB>>),+,
+            {
+            }
+
+            impl<$($T),+, Col> GroupByContains<Col> for ($($T,)+) where
+                $($T: GroupByContains<Col>),+,
+                ($($T::Output,)+): AnyYes,
+            {
+                type Output = <($($T::Output,)+) as AnyYes>::Output;
+            }
+
             impl<$($T: Column<Table=Tab>),+, Tab: Table> InsertableColumns<Tab> for ($($T,)+) {
                 type SqlType = ($(<$T as Expression>::SqlType),+);
 
@@ -129,6 +146,13 @@ macro_rules! tuple_impls {
                 }
             }
 
+            impl<$($T: CopyInValues),+> CopyInValues for ($($T,)+) {
+                fn push_copy_fields(&self, fields: &mut Vec<Option<Vec<u8>>>) -> BuildQueryResult {
+                    $(try!(e!(self.$idx.push_copy_fields(fields)));)+
+                    Ok(())
+                }
+            }
+
             impl<DB, $($T,)+> Changeset<DB> for ($($T,)+) where
                 DB: Backend,
                 $($T: Changeset<DB>,)+
@@ -0,0 +1,109 @@
+use schema::*;
+use diesel::*;
+use diesel::connection::pg::{Async, AsyncPgConnection};
+use diesel::expression::dsl::sql;
+use diesel::types::Integer;
+use std::time::{Duration, Instant};
+
+#[test]
+fn async_connection_loads_rows_and_gets_results() {
+    use schema::users::dsl::*;
+
+    let connection = async_connection_with_sean_and_tess_in_users_table();
+    let names = connection.load::<_, String>(users.select(name).order(id));
+    let count = connection.get_result::<_, i64>(users.count());
+
+    assert_eq!(Ok(vec!["Sean".to_string(), "Tess".to_string()]), names.wait());
+    assert_eq!(Ok(2), count.wait());
+}
+
+#[test]
+fn async_connection_executes_statements() {
+    use schema::users::dsl::*;
+
+    let connection = async_connection_with_sean_and_tess_in_users_table();
+    let updated = connection.execute(update(users.filter(id.eq(1))).set(name.eq("Jim")));
+    let names = connection.load::<_, String>(users.select(name).order(id));
+
+    assert_eq!(Ok(1), updated.wait());
+    assert_eq!(Ok(vec!["Jim".to_string(), "Tess".to_string()]), names.wait());
+}
+
+#[test]
+fn async_get_result_returns_not_found_when_there_are_no_rows() {
+    use schema::users::dsl::*;
+
+    let connection = async_connection();
+    let user = connection.get_result::<_, String>(users.select(name));
+
+    assert_eq!(Err(NotFound), user.wait());
+}
+
+#[test]
+fn async_queries_complete_in_order_and_can_be_polled_in_any_order() {
+    let connection = AsyncPgConnection::establish(database_url()).unwrap();
+    let mut slow = connection.get_result::<_, i32>(select(sql::<Integer>("1 FROM pg_sleep(0.1)")));
+    let mut fast = connection.get_result::<_, i32>(select(sql::<Integer>("2")));
+
+    assert_eq!(Ok(Async::NotReady), fast.poll());
+    assert_eq!(Ok(2), fast.wait());
+    assert_eq!(Ok(Async::Ready(1)), slow.poll());
+}
+
+#[test]
+fn async_errors_only_affect_their_own_query() {
+    let connection = AsyncPgConnection::establish(database_url()).unwrap();
+    let failing = connection.get_result::<_, i32>(select(sql::<Integer>("1 / 0")));
+    let succeeding = connection.get_result::<_, i32>(select(sql::<Integer>("1")));
+
+    assert!(failing.wait().is_err());
+    assert_eq!(Ok(1), succeeding.wait());
+}
+
+#[test]
+fn async_results_of_dropped_futures_are_discarded() {
+    let connection = AsyncPgConnection::establish(database_url()).unwrap();
+    let queued = connection.get_result::<_, i32>(select(sql::<Integer>("1 FROM pg_sleep(0.1)")));
+    drop(connection.get_result::<_, i32>(select(sql::<Integer>("2"))));
+    drop(queued);
+
+    let result = connection.get_result::<_, i32>(select(sql::<Integer>("3")));
+    assert_eq!(Ok(3), result.wait());
+}
+
+#[test]
+fn one_thread_can_run_queries_on_many_async_connections_at_once() {
+    let connections = (0..3)
+        .map(|_| AsyncPgConnection::establish(database_url()).unwrap())
+        .collect::<Vec<_>>();
+    let start = Instant::now();
+    let mut futures = connections.iter()
+        .map(|conn| conn.get_result::<_, i32>(select(sql::<Integer>("1 FROM pg_sleep(0.5)"))))
+        .collect::<Vec<_>>();
+
+    let mut results = vec![None; futures.len()];
+    while results.iter().any(Option::is_none) {
+        for (future, result) in futures.iter_mut().zip(&mut results) {
+            if result.is_none() {
+                if let Async::Ready(value) = future.poll().unwrap() {
+                    *result = Some(value);
+                }
+            }
+        }
+    }
+    assert_eq!(vec![Some(1); 3], results);
+    assert!(start.elapsed() < Duration::from_millis(1400));
+}
+
+fn async_connection() -> AsyncPgConnection {
+    let connection = AsyncPgConnection::establish(database_url()).unwrap();
+    connection.batch_execute("BEGIN").wait().unwrap();
+    connection
+}
+
+fn async_connection_with_sean_and_tess_in_users_table() -> AsyncPgConnection {
+    let connection = async_connection();
+    connection.batch_execute("INSERT INTO users (id, name) VALUES (1, 'Sean'), (2, 'Tess')")
+        .wait().unwrap();
+    connection
+}
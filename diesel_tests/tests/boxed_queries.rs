@@ -0,0 +1,113 @@
+use schema::*;
+use diesel::*;
+use diesel::query_builder::pg_sql_with_binds;
+
+fn connection_with_three_users() -> PgConnection {
+    let connection = connection();
+    connection.execute("INSERT INTO users (id, name, hair_color) VALUES
+        (1, 'Sean', 'black'), (2, 'Tess', 'brown'), (3, 'Jim', 'black')").unwrap();
+    connection
+}
+
+#[test]
+fn boxed_queries_can_be_loaded() {
+    let connection = connection_with_three_users();
+    let all_users = users::table.into_boxed()
+        .load(&connection).unwrap()
+        .collect::<Vec<User>>();
+
+    assert_eq!(3, all_users.len());
+}
+
+#[test]
+fn filters_can_be_added_conditionally() {
+    use schema::users::dsl::*;
+
+    let connection = connection_with_three_users();
+    let search = vec![Some("black"), None, Some("Jim")];
+    let mut query = users.select(name).into_boxed();
+    if let Some(color) = search[0] {
+        query = query.filter(hair_color.eq(color));
+    }
+    if let Some(search_name) = search[1] {
+        query = query.filter(name.eq(search_name));
+    }
+    let data = query.order(id).load(&connection).unwrap().collect::<Vec<String>>();
+    assert_eq!(vec!["Sean".to_string(), "Jim".to_string()], data);
+}
+
+#[test]
+fn filters_can_be_chained_in_loops() {
+    use schema::users::dsl::*;
+
+    let connection = connection_with_three_users();
+    let excluded_names = vec!["Sean", "Tess"];
+    let mut query = users.select(name).filter(id.gt(0)).into_boxed();
+    for excluded_name in &excluded_names {
+        query = query.filter(name.ne(*excluded_name));
+    }
+    let data = query.load(&connection).unwrap().collect::<Vec<String>>();
+
+    assert_eq!(vec!["Jim".to_string()], data);
+}
+
+#[test]
+fn order_limit_and_offset_can_be_replaced() {
+    use schema::users::dsl::*;
+
+    let connection = connection_with_three_users();
+    let mut query = users.select(name).order(id).limit(1).into_boxed();
+    let descending = true;
+    if descending {
+        query = query.order(id.desc());
+    }
+    query = query.limit(2).offset(1);
+    let data = query.load(&connection).unwrap().collect::<Vec<String>>();
+
+    assert_eq!(vec!["Tess".to_string(), "Sean".to_string()], data);
+}
+
+#[test]
+fn first_works_with_boxed_queries() {
+    use schema::users::dsl::*;
+
+    let connection = connection_with_three_users();
+    let query = users.select(name).into_boxed().order(name);
+
+    assert_eq!(Ok("Jim".to_string()), query.first(&connection));
+}
+
+#[test]
+fn boxed_queries_keep_the_existing_clauses() {
+    use schema::users::dsl::*;
+
+    let query = users.select(name).filter(id.eq(1)).distinct().into_boxed()
+        .filter(hair_color.is_null())
+        .order(name)
+        .limit(1);
+    let expected = "SELECT DISTINCT \"users\".\"name\" FROM \"users\" \
+                    WHERE \"users\".\"id\" = 1 AND \"users\".\"hair_color\" IS NULL \
+                    ORDER BY \"users\".\"name\" LIMIT 1::int8";
+    assert_eq!(expected, pg_sql_with_binds(&query).unwrap());
+}
+
+#[test]
+fn grouped_boxed_queries_can_be_ordered_by_grouped_columns_and_aggregates() {
+    use diesel::expression::dsl::count;
+    use schema::users::dsl::*;
+
+    let connection = connection_with_three_users();
+    let query = || users.group_by(hair_color)
+        .select((hair_color, count(id)))
+        .into_boxed();
+
+    let by_count = query().order(count(id).desc())
+        .load(&connection).unwrap()
+        .collect::<Vec<(Option<String>, i64)>>();
+    assert_eq!(vec![(Some("black".to_string()), 2), (Some("brown".to_string()), 1)], by_count);
+
+    let by_color = query().order(hair_color.desc())
+        .load(&connection).unwrap()
+        .collect::<Vec<(Option<String>, i64)>>();
+    assert_eq!(vec![(Some("brown".to_string()), 1), (Some("black".to_string()), 2)], by_color);
+}
@@ -0,0 +1,132 @@
+use schema::*;
+use diesel::*;
+use diesel::query_builder::pg_sql_with_binds;
+
+fn connection_with_users_and_posts() -> PgConnection {
+    let connection = connection_with_sean_and_tess_in_users_table();
+    connection.execute("INSERT INTO users (id, name) VALUES (3, 'Jim'), (4, 'Sean')").unwrap();
+    connection.execute("INSERT INTO posts (user_id, title) VALUES
+        (1, 'Hello'), (2, 'Sean'), (2, 'World')").unwrap();
+    connection
+}
+
+#[test]
+fn union_removes_duplicate_rows() {
+    use schema::users::dsl::*;
+
+    let connection = connection_with_users_and_posts();
+    let names = users.select(name).filter(id.lt(3))
+        .union(users.select(name).filter(id.gt(1)))
+        .order(name)
+        .load(&connection).unwrap()
+        .collect::<Vec<String>>();
+
+    assert_eq!(vec!["Jim".to_string(), "Sean".to_string(), "Tess".to_string()], names);
+}
+
+#[test]
+fn union_all_keeps_duplicate_rows() {
+    use schema::users::dsl::*;
+
+    let connection = connection_with_users_and_posts();
+    let names = users.select(name).filter(id.lt(3))
+        .union_all(users.select(name).filter(id.gt(1)))
+        .order(name.desc())
+        .load(&connection).unwrap()
+        .collect::<Vec<String>>();
+
+    let expected = vec!["Tess", "Tess", "Sean", "Sean", "Jim"].into_iter()
+        .map(|n| n.to_string()).collect::<Vec<_>>();
+    assert_eq!(expected, names);
+}
+
+#[test]
+fn intersect_returns_rows_in_both_queries() {
+    use schema::users::dsl::*;
+
+    let connection = connection_with_users_and_posts();
+    let names = users.select(name).filter(id.lt(3))
+        .intersect(users.select(name).filter(id.gt(2)))
+        .load(&connection).unwrap()
+        .collect::<Vec<String>>();
+
+    assert_eq!(vec!["Sean".to_string()], names);
+}
+
+#[test]
+fn except_removes_rows_in_the_other_query() {
+    use schema::users::dsl::*;
+
+    let connection = connection_with_users_and_posts();
+    let names = users.select(name)
+        .except(users.select(name).filter(id.lt(3)))
+        .load(&connection).unwrap()
+        .collect::<Vec<String>>();
+
+    assert_eq!(vec!["Jim".to_string()], names);
+}
+
+#[test]
+fn queries_on_different_tables_can_be_combined() {
+    let connection = connection_with_users_and_posts();
+    let feed = users::table.select((users::id, users::name))
+        .union_all(posts::table.select((posts::user_id, posts::title)))
+        .order((users::id, users::name.desc()))
+        .limit(3)
+        .load(&connection).unwrap()
+        .collect::<Vec<(i32, String)>>();
+
+    let expected = vec![
+        (1, "Sean".to_string()),
+        (1, "Hello".to_string()),
+        (2, "World".to_string()),
+    ];
+    assert_eq!(expected, feed);
+}
+
+#[test]
+fn combined_queries_can_be_combined_again() {
+    use schema::users::dsl::*;
+
+    let connection = connection_with_users_and_posts();
+    let seans_and_jim = users.select(name).filter(name.eq("Sean"))
+        .union(users.select(name).filter(name.eq("Jim")));
+    let all_names = users.select(name).filter(name.eq("Tess"))
+        .union(seans_and_jim)
+        .union_all(posts::table.select(posts::title).filter(posts::title.eq("Sean")))
+        .order(name)
+        .load(&connection).unwrap()
+        .collect::<Vec<String>>();
+
+    let expected = vec!["Jim", "Sean", "Sean", "Tess"].into_iter()
+        .map(|n| n.to_string()).collect::<Vec<_>>();
+    assert_eq!(expected, all_names);
+}
+
+#[test]
+fn first_limits_the_combined_query() {
+    use schema::users::dsl::*;
+
+    let connection = connection_with_users_and_posts();
+    let last_name = users.select(name)
+        .union(posts::table.select(posts::title))
+        .order(name.desc())
+        .first(&connection);
+
+    assert_eq!(Ok("World".to_string()), last_name);
+}
+
+#[test]
+fn combined_queries_are_ordered_by_unqualified_column_names() {
+    use schema::users::dsl::*;
+
+    let query = users.select(name).filter(id.eq(1))
+        .except(users.select(name).filter(id.eq(2)))
+        .order(name.desc())
+        .limit(1);
+    let expected = "(SELECT \"users\".\"name\" FROM \"users\" WHERE \"users\".\"id\" = 1) \
+                    EXCEPT \
+                    (SELECT \"users\".\"name\" FROM \"users\" WHERE \"users\".\"id\" = 2) \
+                    ORDER BY \"name\" DESC LIMIT 1::int8";
+    assert_eq!(expected, pg_sql_with_binds(&query).unwrap());
+}
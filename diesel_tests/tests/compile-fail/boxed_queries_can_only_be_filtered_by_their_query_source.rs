@@ -0,0 +1,23 @@
+#[macro_use]
+extern crate diesel;
+
+use diesel::*;
+
+table! {
+    users {
+        id -> Serial,
+        name -> VarChar,
+    }
+}
+
+table! {
+    posts {
+        id -> Serial,
+        title -> VarChar,
+    }
+}
+
+fn main() {
+    let _ = users::table.into_boxed().filter(posts::id.eq(1));
+    //~^ ERROR SelectableExpression
+}
@@ -0,0 +1,18 @@
+#[macro_use]
+extern crate diesel;
+
+use diesel::*;
+
+table! {
+    users {
+        id -> Serial,
+        name -> VarChar,
+    }
+}
+
+fn main() {
+    use self::users::dsl::*;
+
+    let source = users.distinct_on(name).order(id);
+    //~^ ERROR ValidOrderingForDistinct
+}
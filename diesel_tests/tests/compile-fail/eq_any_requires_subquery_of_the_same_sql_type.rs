@@ -0,0 +1,18 @@
+#[macro_use]
+extern crate diesel;
+
+use diesel::*;
+
+table! {
+    users {
+        id -> Serial,
+        name -> VarChar,
+    }
+}
+
+fn main() {
+    use self::users::dsl::*;
+
+    let source = users.filter(id.eq_any(users.select(name)));
+    //~^ ERROR type mismatch
+}
@@ -0,0 +1,19 @@
+#[macro_use]
+extern crate diesel;
+
+use diesel::*;
+use diesel::expression::count;
+
+table! {
+    users {
+        id -> Serial,
+        name -> VarChar,
+    }
+}
+
+fn main() {
+    use self::users::dsl::*;
+
+    let source = users.group_by(name).select((id, count(id)));
+    //~^ ERROR GroupByContains
+}
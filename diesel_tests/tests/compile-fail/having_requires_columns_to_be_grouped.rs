@@ -0,0 +1,18 @@
+#[macro_use]
+extern crate diesel;
+
+use diesel::*;
+
+table! {
+    users {
+        id -> Serial,
+        name -> VarChar,
+    }
+}
+
+fn main() {
+    use self::users::dsl::*;
+
+    let source = users.group_by(name).select(name).having(id.gt(1));
+    //~^ ERROR GroupByContains
+}
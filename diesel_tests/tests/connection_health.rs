@@ -0,0 +1,82 @@
+use std::time::Duration;
+
+use schema::{connection_without_transaction, users, wait_until, NewUser};
+use diesel::*;
+use diesel::connection::PgConnection;
+use diesel::expression::dsl::sql;
+use diesel::result::{Error, TransactionError};
+use diesel::types::Integer;
+
+#[test]
+fn ping_succeeds_on_a_working_connection() {
+    let connection = connection_without_transaction();
+
+    assert_eq!(Ok(()), connection.ping());
+    assert!(!connection.is_broken());
+}
+
+#[test]
+fn statements_fail_with_connection_lost_once_the_connection_is_lost() {
+    let connection = connection_without_transaction();
+    terminate_backend(&connection);
+
+    assert_eq!(Err(Error::ConnectionLost), connection.execute("SELECT 1"));
+    assert!(connection.is_broken());
+    assert_eq!(Err(Error::ConnectionLost), connection.ping());
+}
+
+#[test]
+fn auto_reconnect_reestablishes_a_lost_connection() {
+    let connection = connection_without_transaction();
+    connection.set_auto_reconnect(true);
+    let old_pid = backend_pid(&connection);
+    terminate_backend(&connection);
+
+    // The statement cached for the query has to be prepared again
+    let new_pid = backend_pid(&connection);
+    assert!(old_pid != new_pid);
+    assert!(!connection.is_broken());
+}
+
+#[test]
+fn auto_reconnect_reestablishes_a_lost_connection_before_copy_in() {
+    let connection = connection_without_transaction();
+    connection.set_auto_reconnect(true);
+    terminate_backend(&connection);
+
+    // Nothing is copied, so that no rows are committed outside of a
+    // transaction
+    assert_eq!(Ok(0), connection.copy_in(users::table, &Vec::<NewUser>::new()));
+    assert!(!connection.is_broken());
+}
+
+#[test]
+fn losing_the_connection_inside_of_a_transaction_fails_the_transaction() {
+    let connection = connection_without_transaction();
+    connection.set_auto_reconnect(true);
+    let result = connection.transaction(|| {
+        terminate_backend(&connection);
+        connection.execute("SELECT 1")
+    });
+
+    assert_eq!(Err(TransactionError::CouldntCreateTransaction(Error::ConnectionLost)), result);
+    assert_eq!(0, connection.get_transaction_depth());
+    assert_eq!(Ok(()), connection.ping());
+}
+
+fn backend_pid(connection: &PgConnection) -> i32 {
+    select(sql::<Integer>("pg_backend_pid()")).get_result(connection).unwrap()
+}
+
+/// Terminates the server process of `connection` from another connection,
+/// and waits until `connection` has noticed that the server closed it.
+fn terminate_backend(connection: &PgConnection) {
+    let pid = backend_pid(connection);
+    let other_connection = connection_without_transaction();
+    other_connection.execute(&format!("SELECT pg_terminate_backend({})", pid)).unwrap();
+    wait_until(|| {
+        // Reads whatever the server sent, noticing when the socket is closed
+        let _ = connection.wait_for_notification(Duration::from_millis(100));
+        connection.is_broken()
+    });
+}
@@ -0,0 +1,52 @@
+use schema::*;
+use diesel::*;
+use diesel::connection::pg::{AsyncPgConnection, PgConnectionOptions};
+use diesel::expression::dsl::sql;
+use diesel::types::Text;
+use std::time::Duration;
+
+#[test]
+fn connections_can_be_established_with_options() {
+    let options = database_url().parse::<PgConnectionOptions>().unwrap()
+        .application_name("diesel tests")
+        .parameter("search_path", "pg_catalog, public")
+        .parameter("statement_timeout", "5s");
+    let connection = PgConnection::establish_with_options(&options).unwrap();
+
+    assert_eq!("diesel tests", current_setting(&connection, "application_name"));
+    assert_eq!("pg_catalog, public", current_setting(&connection, "search_path"));
+    assert_eq!("5s", current_setting(&connection, "statement_timeout"));
+}
+
+#[test]
+fn options_rendered_as_a_url_can_be_used_to_establish_a_connection() {
+    let options = database_url().parse::<PgConnectionOptions>().unwrap()
+        .application_name("diesel tests: url");
+    let connection = PgConnection::establish(&options.to_url()).unwrap();
+
+    assert_eq!("diesel tests: url", current_setting(&connection, "application_name"));
+}
+
+#[test]
+fn async_connections_can_be_established_with_options() {
+    let options = database_url().parse::<PgConnectionOptions>().unwrap()
+        .application_name("diesel tests: async");
+    let connection = AsyncPgConnection::establish_with_options(&options).unwrap();
+
+    let application_name = connection.get_result::<_, String>(
+        select(sql::<Text>("current_setting('application_name')")));
+    assert_eq!(Ok("diesel tests: async".to_string()), application_name.wait());
+}
+
+#[test]
+fn invalid_options_cause_a_connection_error() {
+    let options = database_url().parse::<PgConnectionOptions>().unwrap()
+        .dbname("diesel_database_which_does_not_exist")
+        .connect_timeout(Duration::from_secs(5));
+
+    assert!(PgConnection::establish_with_options(&options).is_err());
+}
+
+fn current_setting(connection: &PgConnection, name: &str) -> String {
+    select(sql::<Text>(&format!("current_setting('{}')", name))).get_result(connection).unwrap()
+}
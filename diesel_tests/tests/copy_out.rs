@@ -0,0 +1,80 @@
+use schema::*;
+use diesel::*;
+use diesel::connection::pg::{CopyFormat, CopyOutOptions};
+use std::io::{self, Write};
+
+#[test]
+fn copy_out_exports_csv_with_a_header() {
+    use schema::users::dsl::*;
+    let connection = connection_with_sean_and_tess_in_users_table();
+    connection.execute("UPDATE users SET hair_color = 'black, brown' WHERE id = 1").unwrap();
+
+    let mut out = Vec::new();
+    let options = CopyOutOptions::new(CopyFormat::Csv).header(true);
+    let count = connection.copy_out(users.order(id), options, &mut out);
+
+    assert_eq!(Ok(2), count);
+    let expected = "id,name,hair_color\n1,Sean,\"black, brown\"\n2,Tess,\n";
+    assert_eq!(expected, String::from_utf8(out).unwrap());
+}
+
+#[test]
+fn copy_out_inlines_bind_params() {
+    use schema::users::dsl::*;
+    let connection = connection_with_sean_and_tess_in_users_table();
+
+    let mut out = Vec::new();
+    let query = users.select((id, name)).filter(name.eq("Tess's").or(id.eq(1)));
+    let count = connection.copy_out(query, CopyOutOptions::new(CopyFormat::Text), &mut out);
+
+    assert_eq!(Ok(1), count);
+    assert_eq!("1\tSean\n", String::from_utf8(out).unwrap());
+}
+
+#[test]
+fn copy_out_uses_the_given_delimiter() {
+    use schema::users::dsl::*;
+    let connection = connection_with_sean_and_tess_in_users_table();
+
+    let mut out = Vec::new();
+    let options = CopyOutOptions::new(CopyFormat::Text).delimiter('|');
+    connection.copy_out(users.order(id), options, &mut out).unwrap();
+
+    assert_eq!("1|Sean|\\N\n2|Tess|\\N\n", String::from_utf8(out).unwrap());
+}
+
+#[test]
+fn copy_out_exports_the_binary_format() {
+    use schema::users::dsl::*;
+    let connection = connection_with_sean_and_tess_in_users_table();
+
+    let mut out = Vec::new();
+    let options = CopyOutOptions::new(CopyFormat::Binary);
+    connection.copy_out(users.select(id), options, &mut out).unwrap();
+
+    assert_eq!(b"PGCOPY\n\xff\r\n\0", &out[..11]);
+}
+
+#[test]
+fn copy_out_returns_write_errors_and_leaves_the_connection_usable() {
+    use schema::users::dsl::*;
+    let connection = connection_with_sean_and_tess_in_users_table();
+
+    let result = connection.copy_out(users, CopyOutOptions::new(CopyFormat::Csv), FailingWriter);
+
+    let expected_error = io::Error::new(io::ErrorKind::Other, "disk full");
+    assert_eq!(Err(result::Error::IoError(expected_error)), result);
+    assert_eq!(Ok(2), users.count().first(&connection));
+}
+
+struct FailingWriter;
+
+impl Write for FailingWriter {
+    fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+        Err(io::Error::new(io::ErrorKind::Other, "disk full"))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -13,3 +13,10 @@ fn test_debug_output() {
     let command = update(users.filter(id.eq(1))).set(name.eq("new_name"));
     assert_eq!(debug_sql!(command), "UPDATE `users` SET `name` = ? WHERE `users`.`id` = ?")
 }
+
+#[test]
+fn test_debug_star_output() {
+    use schema::users;
+    let sql = debug_sql!(users::table.select(users::star));
+    assert_eq!(sql, "SELECT `users`.* FROM `users`");
+}
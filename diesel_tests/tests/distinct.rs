@@ -0,0 +1,94 @@
+use schema::*;
+use diesel::*;
+use diesel::query_builder::pg_sql_with_binds;
+
+fn connection_with_users_sharing_names() -> PgConnection {
+    let connection = connection();
+    connection.execute("INSERT INTO users (id, name, hair_color) VALUES
+        (1, 'Sean', 'black'), (2, 'Tess', 'brown'), (3, 'Sean', 'brown'), (4, 'Tess', NULL)")
+        .unwrap();
+    connection
+}
+
+#[test]
+fn distinct_removes_duplicate_rows() {
+    use schema::users::dsl::*;
+
+    let connection = connection_with_users_sharing_names();
+    let names = users.select(name).distinct().order(name)
+        .load(&connection).unwrap()
+        .collect::<Vec<String>>();
+
+    assert_eq!(vec!["Sean".to_string(), "Tess".to_string()], names);
+}
+
+#[test]
+fn distinct_can_be_called_before_select() {
+    use schema::users::dsl::*;
+
+    let connection = connection_with_users_sharing_names();
+    let hair_colors = users.distinct().select(hair_color).order(hair_color)
+        .load(&connection).unwrap()
+        .collect::<Vec<Option<String>>>();
+
+    let expected = vec![Some("black".to_string()), Some("brown".to_string()), None];
+    assert_eq!(expected, hair_colors);
+}
+
+#[test]
+fn distinct_on_keeps_the_first_row_of_each_group() {
+    use schema::users::dsl::*;
+
+    let connection = connection_with_users_sharing_names();
+    let newest_users = users.distinct_on(name)
+        .order((name, id.desc()))
+        .load(&connection).unwrap()
+        .collect::<Vec<User>>();
+
+    let expected = vec![
+        User::with_hair_color(3, "Sean", "brown"),
+        User::new(4, "Tess"),
+    ];
+    assert_eq!(expected, newest_users);
+}
+
+#[test]
+fn distinct_on_can_be_ordered_by_the_distinct_expression() {
+    use schema::users::dsl::*;
+
+    let connection = connection_with_users_sharing_names();
+    let names = users.select(name).distinct_on(name).order(name.desc())
+        .load(&connection).unwrap()
+        .collect::<Vec<String>>();
+
+    assert_eq!(vec!["Tess".to_string(), "Sean".to_string()], names);
+}
+
+#[test]
+fn distinct_on_works_with_filters_and_limits() {
+    use schema::users::dsl::*;
+
+    let connection = connection_with_users_sharing_names();
+    let first_user_with_hair_color = users.filter(hair_color.is_not_null())
+        .distinct_on(hair_color)
+        .order((hair_color, id))
+        .limit(1)
+        .select(id)
+        .load(&connection).unwrap()
+        .collect::<Vec<i32>>();
+
+    assert_eq!(vec![1], first_user_with_hair_color);
+}
+
+#[test]
+fn distinct_is_placed_directly_after_select() {
+    use schema::users::dsl::*;
+
+    let query = users.select(name).distinct();
+    assert_eq!("SELECT DISTINCT \"users\".\"name\" FROM \"users\"",
+        pg_sql_with_binds(&query).unwrap());
+    let query = users.select(name).distinct_on(hair_color).order(hair_color.asc());
+    assert_eq!("SELECT DISTINCT ON (\"users\".\"hair_color\") \"users\".\"name\" \
+                FROM \"users\" ORDER BY \"users\".\"hair_color\" ASC",
+        pg_sql_with_binds(&query).unwrap());
+}
@@ -0,0 +1,80 @@
+use schema::*;
+use diesel::*;
+use diesel::result::Error::DatabaseError;
+use diesel::result::DatabaseErrorKind;
+
+#[test]
+fn unique_constraints_are_detected() {
+    let connection = connection_with_sean_and_tess_in_users_table();
+
+    let failure = connection.execute("INSERT INTO users (id, name) VALUES (1, 'Jim')");
+    match failure {
+        Err(DatabaseError(DatabaseErrorKind::UniqueViolation, info)) => {
+            assert_eq!(Some("23505"), info.sql_state());
+            assert_eq!(Some("users"), info.table_name());
+            assert_eq!(Some("users_pkey"), info.constraint_name());
+            assert!(info.details().unwrap().contains("(id)=(1)"));
+        }
+        _ => panic!("{:?} did not match Err(DatabaseError(UniqueViolation, _))", failure),
+    }
+}
+
+#[test]
+fn foreign_key_violations_are_detected() {
+    let connection = connection();
+    connection.execute("CREATE TABLE fk_parents (id SERIAL PRIMARY KEY)").unwrap();
+    connection.execute("CREATE TABLE fk_children (
+        id SERIAL PRIMARY KEY,
+        parent_id INTEGER NOT NULL REFERENCES fk_parents
+    )").unwrap();
+
+    let failure = connection.execute("INSERT INTO fk_children (parent_id) VALUES (1)");
+    match failure {
+        Err(DatabaseError(DatabaseErrorKind::ForeignKeyViolation, info)) => {
+            assert_eq!(Some("23503"), info.sql_state());
+            assert_eq!(Some("fk_children"), info.table_name());
+            assert_eq!(Some("fk_children_parent_id_fkey"), info.constraint_name());
+        }
+        _ => panic!("{:?} did not match Err(DatabaseError(ForeignKeyViolation, _))", failure),
+    }
+}
+
+#[test]
+fn not_null_violations_are_detected() {
+    let connection = connection();
+
+    let failure = connection.execute("INSERT INTO users (name) VALUES (NULL)");
+    match failure {
+        Err(DatabaseError(DatabaseErrorKind::NotNullViolation, info)) => {
+            assert_eq!(Some("23502"), info.sql_state());
+            assert_eq!(Some("users"), info.table_name());
+            assert_eq!(Some("name"), info.column_name());
+        }
+        _ => panic!("{:?} did not match Err(DatabaseError(NotNullViolation, _))", failure),
+    }
+}
+
+#[test]
+fn other_database_errors_include_the_message() {
+    let connection = connection();
+
+    let failure = connection.execute("SELECT * FROM this_table_does_not_exist");
+    match failure {
+        Err(DatabaseError(DatabaseErrorKind::__Unknown, info)) => {
+            assert_eq!("relation \"this_table_does_not_exist\" does not exist", info.message());
+            assert_eq!(Some("42P01"), info.sql_state());
+        }
+        _ => panic!("{:?} did not match Err(DatabaseError(_, _))", failure),
+    }
+}
+
+#[test]
+fn errors_can_be_sent_to_other_threads() {
+    use std::thread;
+
+    let connection = connection();
+    let failure = connection.execute("SELECT * FROM this_table_does_not_exist").unwrap_err();
+    let message = thread::spawn(move || failure.to_string()).join().unwrap();
+
+    assert!(message.contains("this_table_does_not_exist"));
+}
@@ -0,0 +1,155 @@
+use schema::*;
+use diesel::*;
+use diesel::expression::dsl::*;
+use diesel::query_builder::pg_sql_with_binds;
+
+fn connection_with_users_sharing_names() -> PgConnection {
+    let connection = connection();
+    connection.execute("INSERT INTO users (name, hair_color) VALUES
+        ('Sean', 'black'), ('Sean', 'brown'), ('Sean', 'black'), ('Tess', 'brown')")
+        .unwrap();
+    connection
+}
+
+#[test]
+fn group_by_counts_rows_per_group() {
+    use schema::users::dsl::*;
+
+    let connection = connection_with_users_sharing_names();
+    let counts = users.group_by(name)
+        .select((name, count(id)))
+        .order(name)
+        .load(&connection).unwrap()
+        .collect::<Vec<(String, i64)>>();
+
+    assert_eq!(vec![("Sean".to_string(), 3), ("Tess".to_string(), 1)], counts);
+}
+
+#[test]
+fn group_by_multiple_columns() {
+    use schema::users::dsl::*;
+
+    let connection = connection_with_users_sharing_names();
+    let counts = users.group_by((name, hair_color))
+        .select((name, hair_color, count_star()))
+        .order(hair_color)
+        .filter(name.eq("Sean"))
+        .load(&connection).unwrap()
+        .collect::<Vec<(String, Option<String>, i64)>>();
+
+    let expected = vec![
+        ("Sean".to_string(), Some("black".to_string()), 2),
+        ("Sean".to_string(), Some("brown".to_string()), 1),
+    ];
+    assert_eq!(expected, counts);
+}
+
+#[test]
+fn group_by_can_select_aggregates_of_ungrouped_columns() {
+    use schema::users::dsl::*;
+
+    let connection = connection_with_users_sharing_names();
+    let first_ids = users.group_by(hair_color)
+        .select((hair_color, min(id)))
+        .order(hair_color)
+        .load(&connection).unwrap()
+        .collect::<Vec<(Option<String>, i32)>>();
+    let first_black = users.select(id).filter(hair_color.eq("black")).order(id).first(&connection);
+    let first_brown = users.select(id).filter(hair_color.eq("brown")).order(id).first(&connection);
+
+    let expected = vec![
+        (Some("black".to_string()), first_black.unwrap()),
+        (Some("brown".to_string()), first_brown.unwrap()),
+    ];
+    assert_eq!(expected, first_ids);
+}
+
+#[test]
+fn having_filters_groups() {
+    use schema::users::dsl::*;
+
+    let connection = connection_with_users_sharing_names();
+    let names = users.group_by(name)
+        .select(name)
+        .having(count(id).gt(1))
+        .load(&connection).unwrap()
+        .collect::<Vec<String>>();
+
+    assert_eq!(vec!["Sean".to_string()], names);
+}
+
+#[test]
+fn multiple_having_calls_are_anded_together() {
+    use schema::users::dsl::*;
+
+    let connection = connection_with_users_sharing_names();
+    let names = users.group_by(name)
+        .select(name)
+        .having(count(id).gt(0))
+        .having(name.ne("Sean"))
+        .load(&connection).unwrap()
+        .collect::<Vec<String>>();
+
+    assert_eq!(vec!["Tess".to_string()], names);
+}
+
+#[test]
+fn group_by_works_with_filtered_tables() {
+    use schema::users::dsl::*;
+
+    let connection = connection_with_users_sharing_names();
+    let counts = users.filter(hair_color.eq("brown"))
+        .group_by(name)
+        .select((name, count_star()))
+        .order(name.desc())
+        .load(&connection).unwrap()
+        .collect::<Vec<(String, i64)>>();
+
+    assert_eq!(vec![("Tess".to_string(), 1), ("Sean".to_string(), 1)], counts);
+}
+
+#[test]
+fn group_by_and_having_are_placed_between_where_and_order() {
+    use schema::users::dsl::*;
+
+    let query = users.group_by(name)
+        .select((name, count(id)))
+        .filter(id.gt(1))
+        .having(count(id).gt(2))
+        .order(name)
+        .limit(1);
+    let expected = "SELECT \"users\".\"name\", COUNT(\"users\".\"id\") FROM \"users\" \
+                    WHERE \"users\".\"id\" > 1 \
+                    GROUP BY \"users\".\"name\" \
+                    HAVING COUNT(\"users\".\"id\") > 2::int8 \
+                    ORDER BY \"users\".\"name\" \
+                    LIMIT 1::int8";
+    assert_eq!(expected, pg_sql_with_binds(&query).unwrap());
+}
+
+#[test]
+fn aggregates_can_be_used_in_operators_without_group_by() {
+    use schema::users::dsl::*;
+
+    let connection = connection_with_users_sharing_names();
+    let max_id_plus_one = users.select(max(id) + 1).first(&connection);
+    let max_id = users.select(max(id)).first::<i32>(&connection).unwrap();
+    assert_eq!(Ok(max_id + 1), max_id_plus_one);
+
+    let any_users = users.select(count_star().gt(0)).first(&connection);
+    assert_eq!(Ok(true), any_users);
+}
+
+#[test]
+fn group_by_queries_can_be_ordered_by_aggregates() {
+    use schema::users::dsl::*;
+
+    let connection = connection_with_users_sharing_names();
+    let names = users.group_by(name)
+        .select(name)
+        .order(count(id).desc())
+        .load(&connection).unwrap()
+        .collect::<Vec<String>>();
+
+    assert_eq!(vec!["Sean".to_string(), "Tess".to_string()], names);
+}
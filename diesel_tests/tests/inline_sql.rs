@@ -0,0 +1,112 @@
+use quickcheck::quickcheck;
+use schema::*;
+use diesel::*;
+use diesel::backend::Pg;
+use diesel::data_types::*;
+use diesel::expression::AsExpression;
+use diesel::expression::dsl::sql;
+use diesel::query_builder::{pg_sql_with_binds, pretty_pg_sql_with_binds, QueryFragment};
+use diesel::types::{self, HasSqlType, Array, Nullable};
+
+#[test]
+fn binds_are_written_as_escaped_literals() {
+    use schema::users::dsl::*;
+
+    let query = users.select(id).filter(name.eq("Sean's \"friend\" \\o/").and(id.gt(-1)));
+    let expected = r#"SELECT "users"."id" FROM "users" WHERE "users"."name" = 'Sean''s "friend" \o/'::varchar AND "users"."id" > -1"#;
+    assert_eq!(expected, pg_sql_with_binds(&query).unwrap());
+}
+
+#[test]
+fn binds_of_other_types_are_cast_to_their_type() {
+    let bytes = AsExpression::<types::Binary>::as_expression(vec![0u8, 255]);
+    assert_eq!("SELECT '\\x00ff'::bytea", pg_sql_with_binds(&select(bytes)).unwrap());
+    let date = AsExpression::<types::Date>::as_expression(PgDate(5903));
+    assert_eq!("SELECT '2016-02-29'::date", pg_sql_with_binds(&select(date)).unwrap());
+    let array = AsExpression::<Array<types::Integer>>::as_expression(vec![1, 2]);
+    assert_eq!("SELECT ARRAY[1, 2]::int4[]", pg_sql_with_binds(&select(array)).unwrap());
+    let text = AsExpression::<types::Text>::as_expression("Sean");
+    assert_eq!("SELECT 'Sean'::text", pg_sql_with_binds(&select(text)).unwrap());
+    let null = AsExpression::<Nullable<types::Text>>::as_expression(None::<String>);
+    assert_eq!("SELECT NULL", pg_sql_with_binds(&select(null)).unwrap());
+}
+
+#[test]
+fn placeholders_in_raw_sql_are_left_alone() {
+    use schema::users::dsl::*;
+
+    let query = users.select(id).filter(sql::<types::Bool>("\"users\".\"name\" <> $1")).filter(name.eq("Sean"));
+    let expected = r#"SELECT "users"."id" FROM "users" WHERE "users"."name" <> $1 AND "users"."name" = 'Sean'::varchar"#;
+    assert_eq!(expected, pg_sql_with_binds(&query).unwrap());
+}
+
+#[test]
+fn pretty_sql_puts_each_clause_on_its_own_line() {
+    use schema::users::dsl::*;
+
+    let query = users.select(id).filter(name.eq("Sean")).order(id.desc()).limit(1);
+    let expected = "SELECT \"users\".\"id\"\n\
+                    FROM \"users\"\n\
+                    WHERE \"users\".\"name\" = 'Sean'::varchar\n\
+                    ORDER BY \"users\".\"id\" DESC\n\
+                    LIMIT 1::int8";
+    assert_eq!(expected, pretty_pg_sql_with_binds(&query).unwrap());
+}
+
+#[test]
+fn inlined_queries_return_the_same_rows() {
+    use schema::users::dsl::*;
+
+    let connection = connection_with_sean_and_tess_in_users_table();
+    let query = users.select(name).filter(name.ne("Sean's")).order(id.desc());
+    let expected = query.load::<String>(&connection).unwrap().collect::<Vec<_>>();
+    let inlined_sql = format!("* FROM ({}) AS inlined", pretty_pg_sql_with_binds(&query).unwrap());
+    let inlined = select(sql::<types::VarChar>(&inlined_sql))
+        .load::<String>(&connection).unwrap().collect::<Vec<_>>();
+
+    assert_eq!(vec!["Tess".to_string(), "Sean".to_string()], expected);
+    assert_eq!(expected, inlined);
+}
+
+fn inlined_value_round_trips<ST, T>(value: T) -> bool where
+    Pg: HasSqlType<ST>,
+    T: AsExpression<ST> + Queryable<ST, Pg> + PartialEq + Clone + ::std::fmt::Debug,
+    <T as AsExpression<ST>>::Expression: SelectableExpression<()> + QueryFragment<Pg>,
+{
+    let connection = connection();
+    let query = select(AsExpression::<ST>::as_expression(value.clone()));
+    let inlined = pg_sql_with_binds(&query).unwrap();
+    match select(sql::<ST>(&format!("({})", inlined))).get_result::<T>(&connection) {
+        Ok(result) => value == result,
+        // Strings containing a NUL byte can't be sent to the server at all
+        Err(result::Error::InvalidCString(_)) => true,
+        Err(e) => panic!("{} failed: {:?}", inlined, e),
+    }
+}
+
+macro_rules! test_inlined_round_trip {
+    ($test_name:ident, $sql_type:ty, $tpe:ty) => {
+        #[test]
+        fn $test_name() {
+            fn round_trip(value: $tpe) -> bool {
+                inlined_value_round_trips::<$sql_type, $tpe>(value)
+            }
+
+            quickcheck(round_trip as fn($tpe) -> bool);
+        }
+    }
+}
+
+test_inlined_round_trip!(i16_inlined_round_trips, types::SmallInt, i16);
+test_inlined_round_trip!(i64_inlined_round_trips, types::BigInt, i64);
+test_inlined_round_trip!(f32_inlined_round_trips, types::Float, f32);
+test_inlined_round_trip!(f64_inlined_round_trips, types::Double, f64);
+test_inlined_round_trip!(string_inlined_round_trips, types::Text, String);
+test_inlined_round_trip!(bool_inlined_round_trips, types::Bool, bool);
+test_inlined_round_trip!(numeric_inlined_round_trips, types::Numeric, PgNumeric);
+test_inlined_round_trip!(date_inlined_round_trips, types::Date, PgDate);
+test_inlined_round_trip!(time_inlined_round_trips, types::Time, PgTime);
+test_inlined_round_trip!(timestamp_inlined_round_trips, types::Timestamp, PgTimestamp);
+test_inlined_round_trip!(interval_inlined_round_trips, types::Interval, PgInterval);
+test_inlined_round_trip!(vec_of_text_inlined_round_trips, Array<types::Text>, Vec<String>);
+test_inlined_round_trip!(option_inlined_round_trips, Nullable<types::Integer>, Option<i32>);
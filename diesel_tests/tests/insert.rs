@@ -139,6 +139,71 @@ fn insert_borrowed_content() {
     assert_eq!(expected_users, actual_users);
 }
 
+#[test]
+fn copy_in_inserts_records() {
+    use schema::users::table as users;
+    let connection = connection();
+    let new_users = vec![
+        NewUser::new("Sean", Some("Black")),
+        NewUser::new("Tess", None),
+    ];
+
+    assert_eq!(Ok(2), connection.copy_in(users, &new_users));
+
+    let actual_users = users.load(&connection).unwrap().collect::<Vec<User>>();
+    let expected_users = vec![
+        User { id: actual_users[0].id, name: "Sean".to_string(), hair_color: Some("Black".to_string()) },
+        User { id: actual_users[1].id, name: "Tess".to_string(), hair_color: None },
+    ];
+    assert_eq!(expected_users, actual_users);
+}
+
+#[test]
+fn copy_in_streams_large_numbers_of_records() {
+    use schema::users::table as users;
+    let connection = connection();
+    let new_users = (0..20_000)
+        .map(|i| NewUser::new(&format!("User {}", i), Some("Green")))
+        .collect::<Vec<_>>();
+
+    assert_eq!(Ok(20_000), connection.copy_in(users, &new_users));
+    assert_eq!(Ok(20_000), users.count().first(&connection));
+}
+
+#[test]
+fn copy_in_returns_errors_from_the_database() {
+    use schema::users::table as users;
+    let connection = connection();
+    connection.execute("DROP TABLE users").unwrap();
+    connection.execute("CREATE TABLE users (
+        id SERIAL PRIMARY KEY,
+        name VARCHAR NOT NULL,
+        hair_color VARCHAR NOT NULL DEFAULT 'Green'
+    )").unwrap();
+    let new_users = vec![
+        NewUser::new("Sean", Some("Black")),
+        NewUser::new("Tess", None),
+    ];
+
+    match connection.copy_in(users, &new_users) {
+        Err(result::Error::DatabaseError(result::DatabaseErrorKind::NotNullViolation, _)) => {},
+        other => panic!("Expected a not null violation, got {:?}", other),
+    }
+}
+
+#[test]
+fn copy_statements_can_only_be_run_with_copy_in_or_copy_out() {
+    use schema::users::table as users;
+    let connection = connection_with_sean_and_tess_in_users_table();
+
+    assert_eq!(Err(result::Error::UnexpectedCopy), connection.execute("COPY users TO STDOUT"));
+    assert_eq!(Ok(2), users.count().first(&connection));
+    // Ending a `COPY ... FROM STDIN` with an error aborts the transaction
+    let copy_in = connection.transaction(|| connection.execute("COPY users FROM STDIN"));
+    assert_eq!(Err(TransactionError::UserReturnedError(result::Error::UnexpectedCopy)), copy_in);
+    assert_eq!(Ok(2), users.count().first(&connection));
+}
+
 #[test]
 fn delete_records() {
     use schema::users::dsl::*;
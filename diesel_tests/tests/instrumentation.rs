@@ -0,0 +1,174 @@
+use schema::*;
+use diesel::*;
+use diesel::connection::SimpleConnection;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Mutex};
+
+type RecordedEvents = Arc<Mutex<Vec<(String, usize, Result<usize, String>)>>>;
+
+fn record_events(connection: &PgConnection) -> RecordedEvents {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let recorded = events.clone();
+    connection.set_instrumentation(move |event| {
+        let result = event.result.map_err(|e| e.to_string());
+        recorded.lock().unwrap().push((event.sql.to_string(), event.bind_count, result));
+    });
+    events
+}
+
+#[test]
+fn instrumentation_receives_queries_with_bind_counts_and_row_counts() {
+    use schema::users::dsl::*;
+    let connection = connection_with_sean_and_tess_in_users_table();
+    let events = record_events(&connection);
+
+    let _ = users.filter(name.eq("Sean")).load::<User>(&connection).unwrap();
+    let _ = update(users).set(hair_color.eq(Some("black"))).execute(&connection).unwrap();
+
+    let events = events.lock().unwrap().iter()
+        .filter(|e| !e.0.starts_with("PREPARE "))
+        .cloned()
+        .collect::<Vec<_>>();
+    assert_eq!(2, events.len());
+    assert!(events[0].0.starts_with("SELECT "));
+    assert_eq!(1, events[0].1);
+    assert_eq!(Ok(1), events[0].2);
+    assert!(events[1].0.starts_with("UPDATE "));
+    assert_eq!(Ok(2), events[1].2);
+}
+
+#[test]
+fn instrumentation_receives_transaction_statements_and_batch_execute() {
+    let connection = connection_without_transaction();
+    let events = record_events(&connection);
+
+    connection.transaction::<_, result::Error, _>(|| {
+        connection.batch_execute("SELECT 1; SELECT 2")
+    }).unwrap();
+
+    let statements = events.lock().unwrap().iter()
+        .map(|e| e.0.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["BEGIN", "SELECT 1; SELECT 2", "COMMIT"], statements);
+}
+
+#[test]
+fn instrumentation_receives_errors() {
+    let connection = connection();
+    let events = record_events(&connection);
+
+    assert!(connection.execute("SELECT * FROM this_table_does_not_exist").is_err());
+
+    let events = events.lock().unwrap();
+    assert_eq!(1, events.len());
+    assert_eq!(Err("relation \"this_table_does_not_exist\" does not exist".to_string()), events[0].2);
+}
+
+#[test]
+fn instrumentation_can_be_cleared() {
+    let connection = connection();
+    let events = record_events(&connection);
+
+    connection.execute("SELECT 1").unwrap();
+    connection.clear_instrumentation();
+    connection.execute("SELECT 2").unwrap();
+
+    assert_eq!(1, events.lock().unwrap().len());
+}
+
+#[test]
+fn statements_prepared_for_the_statement_cache_are_reported() {
+    use schema::users::dsl::*;
+    let connection = connection_with_sean_and_tess_in_users_table();
+    let events = record_events(&connection);
+
+    let _ = users.filter(name.eq("Sean")).load::<User>(&connection).unwrap();
+    let _ = users.filter(name.eq("Tess")).load::<User>(&connection).unwrap();
+
+    let statements = events.lock().unwrap().iter()
+        .map(|e| e.0.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(3, statements.len());
+    assert!(statements[0].starts_with("PREPARE "));
+    assert!(statements[0].ends_with(&*statements[1]));
+    assert_eq!(statements[1], statements[2]);
+}
+
+#[test]
+fn load_iter_is_reported_once_every_row_has_been_read() {
+    use schema::users::dsl::*;
+    let connection = connection_with_sean_and_tess_in_users_table();
+    let events = record_events(&connection);
+
+    let mut rows = users.filter(id.gt(0)).load_iter::<User>(&connection).unwrap();
+    rows.next().unwrap().unwrap();
+    rows.next().unwrap().unwrap();
+    assert!(events.lock().unwrap().iter().all(|e| e.0.starts_with("PREPARE ")));
+    assert!(rows.next().is_none());
+
+    let events = events.lock().unwrap();
+    let last = events.last().unwrap();
+    assert!(last.0.starts_with("SELECT "));
+    assert_eq!(1, last.1);
+    assert_eq!(Ok(2), last.2);
+}
+
+#[test]
+fn load_iter_is_reported_when_dropped_early() {
+    use schema::users::dsl::*;
+    let connection = connection_with_sean_and_tess_in_users_table();
+    let events = record_events(&connection);
+
+    {
+        let mut rows = users.filter(id.gt(0)).load_iter::<User>(&connection).unwrap();
+        rows.next().unwrap().unwrap();
+    }
+
+    let events = events.lock().unwrap();
+    let last = events.last().unwrap();
+    assert!(last.0.starts_with("SELECT "));
+    // Inside of a transaction, the rest of the rows are read rather than the
+    // query being cancelled
+    assert_eq!(Ok(2), last.2);
+}
+
+#[test]
+fn copy_in_and_copy_out_are_reported() {
+    use diesel::connection::pg::{CopyFormat, CopyOutOptions};
+    use schema::users::dsl::*;
+    let connection = connection();
+    let events = record_events(&connection);
+
+    let new_users = vec![NewUser::new("Sean", None), NewUser::new("Tess", None)];
+    connection.copy_in(users, &new_users).unwrap();
+    let options = CopyOutOptions::new(CopyFormat::Text);
+    connection.copy_out(users.filter(name.eq("Sean")), options, Vec::new()).unwrap();
+
+    let events = events.lock().unwrap();
+    assert_eq!(2, events.len());
+    assert!(events[0].0.starts_with("COPY \"users\""));
+    assert_eq!(Ok(2), events[0].2);
+    assert!(events[1].0.starts_with("COPY (SELECT "));
+    assert_eq!(Ok(1), events[1].2);
+}
+
+#[test]
+fn a_callback_which_panics_is_kept() {
+    let connection = connection();
+    let calls = Arc::new(Mutex::new(0));
+    let counted = calls.clone();
+    connection.set_instrumentation(move |_| {
+        let mut calls = counted.lock().unwrap();
+        *calls += 1;
+        if *calls == 1 {
+            drop(calls);
+            panic!("The first call panics");
+        }
+    });
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| connection.execute("SELECT 1")));
+    assert!(result.is_err());
+    connection.execute("SELECT 2").unwrap();
+
+    assert_eq!(2, *calls.lock().unwrap());
+}
@@ -11,3 +11,72 @@ fn bind_params_are_passed_for_null_when_not_inserting() {
         .filter(AsExpression::<Nullable<Integer>>::as_expression(None::<i32>).is_null());
     assert_eq!(Ok(1), query.first(&connection));
 }
+
+#[test]
+fn queries_built_with_the_query_builder_are_prepared_once() {
+    use schema::users::dsl::*;
+
+    let connection = connection_with_sean_and_tess_in_users_table();
+    let prepared_statement_count = || {
+        select(sql::<BigInt>("COUNT(*) FROM pg_prepared_statements")).first::<i64>(&connection)
+    };
+    let initial_count = prepared_statement_count().unwrap();
+
+    let sean = users.filter(name.eq("Sean")).first::<User>(&connection).unwrap();
+    let tess = users.filter(name.eq("Tess")).first::<User>(&connection).unwrap();
+
+    assert_eq!("Sean", sean.name);
+    assert_eq!("Tess", tess.name);
+    assert_eq!(Ok(initial_count + 1), prepared_statement_count());
+}
+
+#[test]
+fn queries_containing_sql_literals_are_not_prepared() {
+    let connection = connection();
+    let prepared_statement_count = || {
+        select(sql::<BigInt>("COUNT(*) FROM pg_prepared_statements")).first::<i64>(&connection)
+    };
+
+    assert_eq!(Ok(0), prepared_statement_count());
+    assert_eq!(Ok(1), select(sql::<Integer>("1")).first::<i32>(&connection));
+    assert_eq!(Ok(0), prepared_statement_count());
+}
+
+#[test]
+fn inserts_of_several_records_are_not_prepared() {
+    use schema::users::table as users;
+
+    let connection = connection();
+    let prepared_statement_count = || {
+        select(sql::<BigInt>("COUNT(*) FROM pg_prepared_statements")).first::<i64>(&connection)
+    };
+    let new_users = vec![
+        NewUser::new("Sean", Some("Black")),
+        NewUser::new("Tess", None),
+    ];
+
+    insert(&new_users).into(users).execute(&connection).unwrap();
+    assert_eq!(Ok(0), prepared_statement_count());
+}
+
+#[test]
+fn the_least_recently_used_prepared_statements_are_deallocated() {
+    use schema::users::dsl::*;
+
+    let connection = connection_with_sean_and_tess_in_users_table();
+    let prepared_statement_count = || {
+        select(sql::<BigInt>("COUNT(*) FROM pg_prepared_statements")).first::<i64>(&connection)
+    };
+
+    // Each query has one more condition than the last, so that none of them
+    // share a prepared statement
+    for condition_count in 0..300 {
+        let mut query = users.select(id).into_boxed();
+        for _ in 0..condition_count {
+            query = query.filter(id.ne(0));
+        }
+        assert_eq!(2, query.load::<i32>(&connection).unwrap().count());
+    }
+
+    assert_eq!(Ok(256), prepared_statement_count());
+}
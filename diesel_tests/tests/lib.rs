@@ -11,16 +11,39 @@ include!("lib.in.rs");
 include!(concat!(env!("OUT_DIR"), "/lib.rs"));
 
 mod associations;
+#[cfg(unix)]
+mod async_connection;
+mod boxed_queries;
+mod combination;
+mod connection_health;
+mod connection_options;
+mod copy_out;
+mod distinct;
+mod errors;
 mod expressions;
 mod filter;
 mod filter_operators;
 mod find;
+mod group_by;
+mod inline_sql;
+mod instrumentation;
 mod internal_details;
 mod joins;
+mod load_iter;
 mod macros;
+mod mock_connection;
+#[cfg(feature = "mysql")]
+mod mysql;
+mod notices;
+mod notifications;
 mod order;
 mod perf_details;
+mod pool;
 mod select;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+mod subqueries;
+mod timeouts;
 mod transactions;
 mod types;
 mod types_roundtrip;
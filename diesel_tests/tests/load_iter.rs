@@ -0,0 +1,52 @@
+use super::schema::*;
+use diesel::*;
+use diesel::expression::dsl::sql;
+use diesel::types::Integer;
+
+#[test]
+fn load_iter_returns_rows_one_at_a_time() {
+    use schema::users::dsl::*;
+
+    let connection = connection_with_sean_and_tess_in_users_table();
+    let mut names = users.select(name).order(id).load_iter::<String>(&connection).unwrap();
+
+    assert_eq!(Some(Ok("Sean".to_string())), names.next());
+    assert_eq!(Some(Ok("Tess".to_string())), names.next());
+    assert_eq!(None, names.next());
+}
+
+#[test]
+fn load_iter_returns_errors_which_occur_part_way_through() {
+    let connection = connection();
+    let query = select(sql::<Integer>("1 / (x - 3) FROM generate_series(1, 5) AS x"));
+    let results = query.load_iter::<i32>(&connection).unwrap().collect::<Vec<_>>();
+
+    assert_eq!(3, results.len());
+    assert_eq!(Ok(-1), results[1]);
+    assert!(results[2].is_err());
+}
+
+#[test]
+fn connection_can_be_reused_after_load_iter_is_dropped_early() {
+    use schema::users::dsl::*;
+
+    let connection = connection_with_sean_and_tess_in_users_table();
+    {
+        let mut all_users = users.load_iter::<(i32, String, Option<String>)>(&connection).unwrap();
+        assert!(all_users.next().is_some());
+    }
+
+    assert_eq!(Ok(2), users.count().first(&connection));
+}
+
+#[test]
+fn dropping_load_iter_early_does_not_abort_the_current_transaction() {
+    let connection = connection();
+    {
+        let query = select(sql::<Integer>("x FROM generate_series(1, 100000) AS x"));
+        let mut numbers = query.load_iter::<i32>(&connection).unwrap();
+        assert_eq!(Some(Ok(1)), numbers.next());
+    }
+
+    assert_eq!(Ok(1), select(sql::<Integer>("1")).first(&connection));
+}
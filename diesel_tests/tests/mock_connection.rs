@@ -0,0 +1,131 @@
+use schema::users;
+use diesel::*;
+use diesel::connection::pg::{MockPgConnection, MockValue, RecordedQuery};
+use diesel::result::Error;
+
+#[test]
+fn mock_connection_records_queries_and_their_binds() {
+    use schema::users::dsl::*;
+
+    let connection = MockPgConnection::new();
+    let _ = users.select(name).filter(id.eq(1).or(hair_color.is_null()))
+        .load::<String>(&connection);
+
+    let expected = RecordedQuery {
+        sql: r#"SELECT "users"."name" FROM "users" WHERE ("users"."id" = $1 OR "users"."hair_color" IS NULL)"#
+            .to_string(),
+        binds: vec![MockValue::Integer(1)],
+    };
+    assert_eq!(vec![expected], connection.queries());
+}
+
+#[test]
+fn mock_connection_returns_the_rows_it_was_given() {
+    use schema::users::dsl::*;
+
+    let connection = MockPgConnection::new();
+    connection.push_rows(vec![
+        vec![1.into(), "Sean".into(), None::<&str>.into()],
+        vec![2.into(), "Tess".into(), "brown".into()],
+    ]);
+
+    let expected = vec![
+        (1, "Sean".to_string(), None),
+        (2, "Tess".to_string(), Some("brown".to_string())),
+    ];
+    let loaded = users.load::<(i32, String, Option<String>)>(&connection).unwrap().collect::<Vec<_>>();
+    assert_eq!(expected, loaded);
+    assert_eq!(0, users.select(name).load::<String>(&connection).unwrap().count());
+}
+
+#[test]
+fn mock_connection_returns_the_errors_it_was_given() {
+    use schema::users::dsl::*;
+
+    let connection = MockPgConnection::new();
+    connection.push_error(NotFound);
+    connection.push_rows(vec![vec!["Sean".into()]]);
+
+    assert!(users.select(name).load::<String>(&connection).is_err());
+    assert_eq!(Ok("Sean".to_string()), users.select(name).first(&connection));
+}
+
+#[test]
+fn mock_connection_returns_row_counts_from_execute() {
+    use schema::users::dsl::*;
+
+    let connection = MockPgConnection::new();
+    connection.push_row_count(2);
+    let updated = update(users.filter(id.gt(1))).set(name.eq("Jim")).execute(&connection);
+
+    assert_eq!(Ok(2), updated);
+    assert_eq!(vec![MockValue::Text("Jim".to_string()), MockValue::Integer(1)],
+               connection.queries()[0].binds);
+}
+
+#[test]
+fn mock_connection_records_transactions_without_using_up_responses() {
+    let connection = MockPgConnection::new();
+    connection.push_row_count(1);
+
+    let result = connection.transaction::<_, Error, _>(|| {
+        let _ = connection.transaction::<(), _, _>(|| Err(()));
+        connection.execute("DELETE FROM users")
+    });
+
+    assert_eq!(Ok(1), result);
+    let queries = connection.queries().into_iter().map(|q| q.sql).collect::<Vec<_>>();
+    assert_eq!(vec![
+        "BEGIN",
+        "SAVEPOINT diesel_savepoint_1",
+        "ROLLBACK TO SAVEPOINT diesel_savepoint_1",
+        "DELETE FROM users",
+        "COMMIT",
+    ], queries);
+}
+
+#[test]
+fn mock_connection_can_be_used_wherever_a_connection_is_expected() {
+    fn find_name<Conn>(connection: &Conn, user_id: i32) -> QueryResult<String> where
+        Conn: Connection<Backend=::diesel::backend::Pg>,
+    {
+        users::table.find(user_id).select(users::name).first(connection)
+    }
+
+    let connection = MockPgConnection::new();
+    connection.push_rows(vec![vec!["Sean".into()]]);
+
+    assert_eq!(Ok("Sean".to_string()), find_name(&connection, 1));
+    assert_eq!(vec![MockValue::Integer(1), MockValue::BigInt(1)], connection.queries()[0].binds);
+}
+
+#[test]
+fn mock_connection_returns_errors_building_queries() {
+    use diesel::backend::{Backend, Pg};
+    use diesel::expression::{Expression, SelectableExpression, ValidGrouping};
+    use diesel::query_builder::{BuildQueryResult, QueryFragment};
+    use diesel::types::Integer;
+
+    struct FailsToBuild;
+
+    impl Expression for FailsToBuild {
+        type SqlType = Integer;
+    }
+
+    impl QueryFragment<Pg> for FailsToBuild {
+        fn to_sql(&self, _out: &mut <Pg as Backend>::QueryBuilder) -> BuildQueryResult {
+            Err("This expression can't be built".into())
+        }
+    }
+
+    impl<QS> SelectableExpression<QS> for FailsToBuild {}
+    impl<G> ValidGrouping<G> for FailsToBuild {}
+
+    let connection = MockPgConnection::new();
+    match users::table.select(FailsToBuild).load::<i32>(&connection) {
+        Err(Error::QueryBuilderError(e)) => assert_eq!("This expression can't be built", e.to_string()),
+        Err(e) => panic!("Expected a query builder error, got {}", e),
+        Ok(_) => panic!("Expected a query builder error"),
+    }
+    assert!(connection.queries().is_empty());
+}
@@ -0,0 +1,121 @@
+extern crate chrono;
+
+use self::chrono::{NaiveDate, NaiveTime};
+use diesel::*;
+use diesel::connection::MysqlConnection;
+use diesel::data_types::PgNumeric;
+use diesel::expression::dsl::sql;
+use std::env;
+
+table! {
+    mysql_users {
+        id -> Integer,
+        name -> VarChar,
+        hair_color -> Nullable<VarChar>,
+    }
+}
+
+fn connection() -> MysqlConnection {
+    let connection_url = env::var("MYSQL_DATABASE_URL")
+        .expect("MYSQL_DATABASE_URL must be set in order to run MySQL tests");
+    let connection = MysqlConnection::establish(&connection_url).unwrap();
+    // Temporary tables are only visible to the connection that created them,
+    // so tests running in parallel don't see each other's rows.
+    connection.execute("CREATE TEMPORARY TABLE mysql_users (
+        id INTEGER PRIMARY KEY AUTO_INCREMENT,
+        name VARCHAR(255) NOT NULL,
+        hair_color VARCHAR(255)
+    )").unwrap();
+    connection.execute("INSERT INTO mysql_users (name) VALUES ('Sean'), ('Tess')").unwrap();
+    connection
+}
+
+#[test]
+fn mysql_load_and_filter() {
+    use self::mysql_users::dsl::*;
+
+    let connection = connection();
+    let sean = (1, "Sean".to_string(), None::<String>);
+    let tess = (2, "Tess".to_string(), None::<String>);
+
+    let all_users = mysql_users.load(&connection).map(Iterator::collect);
+    assert_eq!(Ok(vec![sean.clone(), tess]), all_users);
+    assert_eq!(Ok(sean), mysql_users.filter(name.eq("Sean")).first(&connection));
+    assert_eq!(Ok(2), mysql_users.count().first(&connection));
+}
+
+#[test]
+fn mysql_update_and_delete() {
+    use self::mysql_users::dsl::*;
+
+    let connection = connection();
+    let command = update(mysql_users.filter(id.eq(1))).set(hair_color.eq(Some("black")));
+    assert_eq!(Ok(1), command.execute(&connection));
+    let sean_hair_color = mysql_users.select(hair_color).filter(name.eq("Sean")).first(&connection);
+    assert_eq!(Ok(Some("black".to_string())), sean_hair_color);
+
+    assert_eq!(Ok(1), delete(mysql_users.filter(id.eq(2))).execute(&connection));
+    assert_eq!(Ok(1), mysql_users.count().first(&connection));
+}
+
+#[test]
+fn mysql_values_larger_than_the_initial_buffer_are_read() {
+    use self::mysql_users::dsl::*;
+
+    let connection = connection();
+    let long_name = vec!["Sean"; 100].concat();
+    update(mysql_users.filter(id.eq(1))).set(name.eq(&long_name)).execute(&connection).unwrap();
+    assert_eq!(Ok(long_name), mysql_users.select(name).filter(id.eq(1)).first(&connection));
+}
+
+#[test]
+fn mysql_transactions_are_rolled_back_on_error() {
+    use self::mysql_users::dsl::*;
+
+    let connection = connection();
+    let _ = connection.transaction::<(), (), _>(|| {
+        connection.execute("INSERT INTO mysql_users (name) VALUES ('Jim')").unwrap();
+        assert_eq!(Ok(3), mysql_users.count().first(&connection));
+        Err(())
+    });
+    assert_eq!(Ok(2), mysql_users.count().first(&connection));
+}
+
+#[test]
+fn mysql_bool_is_stored_as_a_tiny_int() {
+    let connection = connection();
+    assert_eq!(Ok(true), select(sql::<types::Bool>("1")).first(&connection));
+    assert_eq!(Ok(false), select(sql::<types::Bool>("0")).first(&connection));
+}
+
+#[test]
+fn mysql_decimals_are_read_and_sent_as_text() {
+    let connection = connection();
+    let one_and_a_half = PgNumeric::Positive { weight: 0, scale: 2, digits: vec![1, 5000] };
+    let query = select(sql::<types::Numeric>("CAST(1.50 AS DECIMAL(3, 2))"));
+    assert_eq!(Ok(one_and_a_half.clone()), query.first(&connection));
+
+    let query = select(sql::<types::Numeric>("CAST(1.50 AS DECIMAL(3, 2))").eq(one_and_a_half));
+    assert_eq!(Ok(true), query.first(&connection));
+}
+
+#[test]
+fn mysql_dates_and_times_are_read_and_sent_as_text() {
+    let connection = connection();
+    let timestamp = NaiveDate::from_ymd(2016, 2, 29).and_hms(13, 5, 7);
+    let query = select(sql::<types::Timestamp>("CAST('2016-02-29 13:05:07' AS DATETIME)"));
+    assert_eq!(Ok(timestamp), query.first(&connection));
+    let query = select(sql::<types::Date>("CAST('2016-02-29' AS DATE)"));
+    assert_eq!(Ok(timestamp.date()), query.first(&connection));
+    let query = select(sql::<types::Time>("CAST('13:05:07' AS TIME)"));
+    assert_eq!(Ok(NaiveTime::from_hms(13, 5, 7)), query.first(&connection));
+
+    let query = select(sql::<types::Timestamp>("CAST('2016-02-29 13:05:07' AS DATETIME)").eq(timestamp));
+    assert_eq!(Ok(true), query.first(&connection));
+}
+
+#[test]
+fn mysql_invalid_sql_returns_an_error() {
+    let connection = connection();
+    assert!(connection.execute("SELEC 1").is_err());
+}
@@ -0,0 +1,81 @@
+use schema::*;
+use diesel::*;
+use diesel::connection::pg::PgNotice;
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn notice_handler_receives_warnings_with_sql_state_and_hint() {
+    let connection = connection();
+    let notices = record_notices(&connection);
+
+    connection.execute("DO $$ BEGIN RAISE WARNING 'careful' USING ERRCODE = '01000', \
+                        DETAIL = 'some detail', HINT = 'some hint'; END $$").unwrap();
+
+    let expected = PgNotice {
+        severity: "WARNING".to_string(),
+        sql_state: Some("01000".to_string()),
+        message: "careful".to_string(),
+        details: Some("some detail".to_string()),
+        hint: Some("some hint".to_string()),
+    };
+    assert_eq!(vec![expected], *notices.lock().unwrap());
+}
+
+#[test]
+fn notice_handler_receives_notices_sent_by_the_server() {
+    let connection = connection();
+    let notices = record_notices(&connection);
+
+    connection.execute("DROP TABLE IF EXISTS diesel_table_which_does_not_exist").unwrap();
+
+    let notices = notices.lock().unwrap();
+    assert_eq!(1, notices.len());
+    assert_eq!("NOTICE", notices[0].severity);
+    assert_eq!(Some("00000".to_string()), notices[0].sql_state);
+    assert!(notices[0].message.contains("diesel_table_which_does_not_exist"));
+}
+
+#[test]
+fn silence_notices_restores_the_notice_handler() {
+    let connection = connection();
+    let notices = record_notices(&connection);
+
+    connection.silence_notices(|| {
+        connection.execute("DO $$ BEGIN RAISE NOTICE 'silenced'; END $$").unwrap();
+    });
+    connection.execute("DO $$ BEGIN RAISE NOTICE 'not silenced'; END $$").unwrap();
+
+    let messages = notices.lock().unwrap().iter()
+        .map(|n| n.message.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["not silenced".to_string()], messages);
+}
+
+#[test]
+fn reset_notice_handler_removes_the_handler() {
+    let connection = connection();
+    let notices = record_notices(&connection);
+
+    connection.reset_notice_handler();
+    connection.execute("DO $$ BEGIN RAISE NOTICE 'to stderr'; END $$").unwrap();
+
+    assert!(notices.lock().unwrap().is_empty());
+}
+
+fn record_notices(connection: &PgConnection) -> Arc<Mutex<Vec<PgNotice>>> {
+    let notices = Arc::new(Mutex::new(Vec::new()));
+    let recorded = notices.clone();
+    connection.set_notice_handler(move |notice| {
+        recorded.lock().unwrap().push(notice.clone());
+    });
+    notices
+}
+
+#[test]
+fn panics_in_the_notice_handler_do_not_unwind_into_libpq() {
+    let connection = connection();
+    connection.set_notice_handler(|_| panic!("Notice handler panicked"));
+
+    connection.execute("DROP TABLE IF EXISTS diesel_table_which_does_not_exist").unwrap();
+    assert_eq!(Ok(1), connection.execute("SELECT 1"));
+}
@@ -0,0 +1,76 @@
+use schema::*;
+use diesel::*;
+use diesel::connection::pg::PgNotification;
+use diesel::expression::dsl::sql;
+use std::time::Duration;
+
+#[test]
+fn notifications_are_received_by_listening_connections() {
+    let listener = connection_without_transaction();
+    let sender = connection_without_transaction();
+    listener.listen("diesel_test_notifications").unwrap();
+
+    sender.notify("diesel_test_notifications", "hello").unwrap();
+    let notification = listener.wait_for_notification(Duration::from_secs(5)).unwrap();
+
+    let expected = PgNotification {
+        channel: "diesel_test_notifications".to_string(),
+        payload: "hello".to_string(),
+        process_id: backend_pid(&sender),
+    };
+    assert_eq!(Some(expected), notification);
+    assert_eq!(Ok(None), listener.poll_notification());
+}
+
+#[test]
+fn channel_names_are_case_sensitive() {
+    let listener = connection_without_transaction();
+    listener.listen("Diesel_Test_Case_Sensitive").unwrap();
+
+    listener.notify("diesel_test_case_sensitive", "").unwrap();
+    listener.notify("Diesel_Test_Case_Sensitive", "").unwrap();
+    let notification = listener.poll_notification().unwrap().unwrap();
+
+    assert_eq!("Diesel_Test_Case_Sensitive", notification.channel);
+    assert_eq!("", notification.payload);
+    assert_eq!(Ok(None), listener.poll_notification());
+}
+
+#[test]
+fn notifications_are_not_received_after_unlisten() {
+    let listener = connection_without_transaction();
+    listener.listen("diesel_test_unlisten").unwrap();
+    listener.unlisten("diesel_test_unlisten").unwrap();
+
+    listener.notify("diesel_test_unlisten", "hello").unwrap();
+
+    assert_eq!(Ok(None), listener.poll_notification());
+}
+
+#[test]
+fn notifications_are_only_sent_once_the_transaction_commits() {
+    let listener = connection_without_transaction();
+    let sender = connection_without_transaction();
+    listener.listen("diesel_test_transaction").unwrap();
+
+    sender.transaction::<_, result::Error, _>(|| {
+        try!(sender.notify("diesel_test_transaction", "hello"));
+        assert_eq!(Ok(None), listener.wait_for_notification(Duration::from_millis(50)));
+        Ok(())
+    }).unwrap();
+
+    let notification = listener.wait_for_notification(Duration::from_secs(5)).unwrap();
+    assert_eq!(Some("hello".to_string()), notification.map(|n| n.payload));
+}
+
+#[test]
+fn wait_for_notification_times_out() {
+    let listener = connection_without_transaction();
+    listener.listen("diesel_test_timeout").unwrap();
+
+    assert_eq!(Ok(None), listener.wait_for_notification(Duration::from_millis(50)));
+}
+
+fn backend_pid(connection: &PgConnection) -> i32 {
+    select(sql::<types::Integer>("pg_backend_pid()")).first(connection).unwrap()
+}
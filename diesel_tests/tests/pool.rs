@@ -0,0 +1,137 @@
+use schema::{database_url, wait_until};
+use diesel::*;
+use diesel::connection::PgConnection;
+use diesel::connection::pool::{Pool, PoolState};
+use diesel::result::PoolError;
+use std::net::TcpListener;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[test]
+fn pool_establishes_min_size_connections_up_front() {
+    let pool: Pool<PgConnection> = Pool::builder().min_size(2).build(database_url()).unwrap();
+    assert_eq!(PoolState { connections: 2, idle_connections: 2 }, pool.state());
+}
+
+#[test]
+fn connections_are_returned_to_the_pool_when_dropped() {
+    let pool: Pool<PgConnection> = Pool::builder().min_size(0).build(database_url()).unwrap();
+    {
+        let connection = pool.get().unwrap();
+        assert_eq!(Ok(1), connection.execute("SELECT 1"));
+        assert_eq!(PoolState { connections: 1, idle_connections: 0 }, pool.state());
+    }
+    assert_eq!(PoolState { connections: 1, idle_connections: 1 }, pool.state());
+}
+
+#[test]
+fn checkout_times_out_when_all_connections_are_in_use() {
+    let pool: Pool<PgConnection> = Pool::builder()
+        .max_size(1)
+        .checkout_timeout(Duration::from_millis(100))
+        .build(database_url())
+        .unwrap();
+    let _connection = pool.get().unwrap();
+    match pool.get() {
+        Err(PoolError::Timeout) => {},
+        Err(e) => panic!("Expected a timeout, got {}", e),
+        Ok(_) => panic!("Expected a timeout, got a second connection"),
+    }
+}
+
+#[test]
+fn checkout_waits_for_connections_to_be_returned() {
+    let pool: Pool<PgConnection> = Pool::builder().max_size(1).build(database_url()).unwrap();
+    let connection = pool.get().unwrap();
+    let other_pool = pool.clone();
+    let other_thread = thread::spawn(move || {
+        other_pool.get().map(|conn| conn.execute("SELECT 1")).is_ok()
+    });
+    // Gives the other thread a chance to start waiting. It gets the
+    // connection whether or not it was waiting by the time it is returned.
+    thread::sleep(Duration::from_millis(50));
+    drop(connection);
+    assert!(other_thread.join().unwrap());
+}
+
+#[test]
+fn connections_dropped_inside_a_transaction_are_closed() {
+    let pool: Pool<PgConnection> = Pool::builder().min_size(0).build(database_url()).unwrap();
+    {
+        let connection = pool.get().unwrap();
+        connection.begin_test_transaction().unwrap();
+    }
+    assert_eq!(PoolState { connections: 0, idle_connections: 0 }, pool.state());
+}
+
+#[test]
+fn idle_connections_above_min_size_are_closed() {
+    let pool: Pool<PgConnection> = Pool::builder()
+        .min_size(1)
+        .idle_timeout(Some(Duration::from_millis(50)))
+        .build(database_url())
+        .unwrap();
+    {
+        let _first = pool.get().unwrap();
+        let _second = pool.get().unwrap();
+    }
+    assert_eq!(PoolState { connections: 2, idle_connections: 2 }, pool.state());
+    let reaped = PoolState { connections: 1, idle_connections: 1 };
+    wait_until(|| pool.state() == reaped);
+    assert_eq!(reaped, pool.state());
+}
+
+#[test]
+fn broken_connections_are_replaced_on_checkout() {
+    use diesel::expression::dsl::sql;
+    use diesel::types::{Bool, Integer};
+
+    let pool: Pool<PgConnection> = Pool::builder().min_size(0).build(database_url()).unwrap();
+    let backend_pid = || sql::<Integer>("pg_backend_pid()");
+    let original_pid = select(backend_pid()).first::<i32>(&*pool.get().unwrap()).unwrap();
+
+    let other_connection = PgConnection::establish(database_url()).unwrap();
+    other_connection.execute(&format!("SELECT pg_terminate_backend({})", original_pid)).unwrap();
+    let still_running = format!("EXISTS (SELECT 1 FROM pg_stat_activity WHERE pid = {})", original_pid);
+    wait_until(|| !select(sql::<Bool>(&still_running)).first::<bool>(&other_connection).unwrap());
+
+    let new_pid = select(backend_pid()).first::<i32>(&*pool.get().unwrap()).unwrap();
+    assert!(original_pid != new_pid);
+    assert_eq!(PoolState { connections: 1, idle_connections: 1 }, pool.state());
+}
+
+#[test]
+fn establishing_a_connection_is_bounded_by_the_checkout_timeout() {
+    // The OS completes the handshake, but nothing ever answers the startup
+    // message
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let url = format!("postgres://postgres@127.0.0.1:{}/diesel_test", port);
+    let pool: Pool<PgConnection> = Pool::builder()
+        .min_size(0)
+        .checkout_timeout(Duration::from_millis(100))
+        .build(&url)
+        .unwrap();
+    let start = Instant::now();
+
+    match pool.get() {
+        Err(PoolError::Timeout) => {},
+        Err(e) => panic!("Expected a timeout, got {}", e),
+        Ok(_) => panic!("Expected a timeout, got a connection"),
+    }
+    assert!(start.elapsed() < Duration::from_secs(5));
+    drop(listener);
+}
+
+#[test]
+fn a_zero_idle_timeout_closes_idle_connections() {
+    let pool: Pool<PgConnection> = Pool::builder()
+        .min_size(0)
+        .idle_timeout(Some(Duration::from_secs(0)))
+        .build(database_url())
+        .unwrap();
+    drop(pool.get().unwrap());
+
+    wait_until(|| pool.state() == PoolState { connections: 0, idle_connections: 0 });
+}
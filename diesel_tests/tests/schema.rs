@@ -1,4 +1,6 @@
 use diesel::*;
+use std::thread;
+use std::time::{Duration, Instant};
 pub use diesel::connection::PgConnection;
 
 #[derive(PartialEq, Eq, Debug, Clone, Queryable)]
@@ -115,9 +117,11 @@ pub fn connection() -> PgConnection {
 }
 
 pub fn connection_without_transaction() -> PgConnection {
-    let connection_url = dotenv!("DATABASE_URL",
-        "DATABASE_URL must be set in order to run tests");
-    PgConnection::establish(&connection_url).unwrap()
+    PgConnection::establish(database_url()).unwrap()
+}
+
+pub fn database_url() -> &'static str {
+    dotenv!("DATABASE_URL", "DATABASE_URL must be set in order to run tests")
 }
 
 pub fn connection_with_sean_and_tess_in_users_table() -> PgConnection {
@@ -132,3 +136,13 @@ pub fn find_user_by_name(name: &str, connection: &PgConnection) -> User {
         .first(connection)
         .unwrap()
 }
+
+/// Polls `condition` until it is true, failing the test if that takes more
+/// than ten seconds.
+pub fn wait_until<F: FnMut() -> bool>(mut condition: F) {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while !condition() {
+        assert!(Instant::now() < deadline, "Timed out waiting for the condition");
+        thread::sleep(Duration::from_millis(10));
+    }
+}
@@ -164,3 +164,33 @@ fn selection_using_subselect() {
 
     assert_eq!(vec!["Hello".to_string()], data);
 }
+
+#[test]
+fn load_returns_an_error_when_a_column_cannot_be_deserialized() {
+    use diesel::expression::dsl::sql;
+    use diesel::result::Error;
+    use diesel::types::{Integer, VarChar};
+
+    let connection = connection();
+    let query = select(sql::<(Integer, VarChar)>("1, NULL"));
+
+    match query.load::<(i32, String)>(&connection) {
+        Err(Error::DeserializationError(1, _)) => {},
+        Err(e) => panic!("Expected a deserialization error, got {}", e),
+        Ok(_) => panic!("Expected a deserialization error, got Ok"),
+    }
+}
+
+#[test]
+fn load_iter_returns_deserialization_errors_for_individual_rows() {
+    use diesel::expression::dsl::sql;
+    use diesel::types::Integer;
+
+    let connection = connection();
+    let query = select(sql::<Integer>("NULLIF(x, 2) FROM generate_series(1, 3) AS x"));
+    let results = query.load_iter::<i32>(&connection).unwrap().collect::<Vec<_>>();
+
+    assert_eq!(Ok(1), results[0]);
+    assert!(results[1].is_err());
+    assert_eq!(Ok(3), results[2]);
+}
@@ -0,0 +1,119 @@
+use diesel::*;
+use diesel::connection::SqliteConnection;
+use diesel::expression::dsl::sql;
+
+table! {
+    users {
+        id -> Integer,
+        name -> VarChar,
+        hair_color -> Nullable<VarChar>,
+    }
+}
+
+fn connection() -> SqliteConnection {
+    let connection = SqliteConnection::establish(":memory:").unwrap();
+    connection.execute("CREATE TABLE users (
+        id INTEGER PRIMARY KEY,
+        name VARCHAR NOT NULL,
+        hair_color VARCHAR
+    )").unwrap();
+    connection.execute("INSERT INTO users (name) VALUES ('Sean'), ('Tess')").unwrap();
+    connection
+}
+
+#[test]
+fn sqlite_load_and_filter() {
+    use self::users::dsl::*;
+
+    let connection = connection();
+    let sean = (1, "Sean".to_string(), None::<String>);
+    let tess = (2, "Tess".to_string(), None::<String>);
+
+    let all_users = users.load(&connection).map(Iterator::collect);
+    assert_eq!(Ok(vec![sean.clone(), tess]), all_users);
+    assert_eq!(Ok(sean), users.filter(name.eq("Sean")).first(&connection));
+    assert_eq!(Ok(2), users.count().first(&connection));
+}
+
+#[test]
+fn sqlite_update_and_delete() {
+    use self::users::dsl::*;
+
+    let connection = connection();
+    let command = update(users.filter(id.eq(1))).set(hair_color.eq(Some("black")));
+    assert_eq!(Ok(1), command.execute(&connection));
+    let sean_hair_color = users.select(hair_color).filter(name.eq("Sean")).first(&connection);
+    assert_eq!(Ok(Some("black".to_string())), sean_hair_color);
+
+    assert_eq!(Ok(1), delete(users.filter(id.eq(2))).execute(&connection));
+    assert_eq!(Ok(1), users.count().first(&connection));
+}
+
+#[test]
+fn sqlite_transactions_are_rolled_back_on_error() {
+    use self::users::dsl::*;
+
+    let connection = connection();
+    let _ = connection.transaction::<(), (), _>(|| {
+        connection.execute("INSERT INTO users (name) VALUES ('Jim')").unwrap();
+        assert_eq!(Ok(3), users.count().first(&connection));
+        Err(())
+    });
+    assert_eq!(Ok(2), users.count().first(&connection));
+}
+
+#[test]
+fn sqlite_bool_is_stored_as_an_integer() {
+    let connection = connection();
+    assert_eq!(Ok(true), select(sql::<types::Bool>("1")).first(&connection));
+    assert_eq!(Ok(false), select(sql::<types::Bool>("0")).first(&connection));
+}
+
+#[test]
+fn sqlite_unexpected_nulls_return_a_deserialization_error() {
+    let connection = connection();
+    match select(sql::<types::VarChar>("NULL")).load::<String>(&connection) {
+        Err(result::Error::DeserializationError(0, _)) => {},
+        Err(e) => panic!("Expected a deserialization error, got {}", e),
+        Ok(_) => panic!("Expected a deserialization error, got Ok"),
+    }
+}
+
+#[test]
+fn sqlite_invalid_sql_returns_an_error() {
+    let connection = connection();
+    assert!(connection.execute("SELEC 1").is_err());
+}
+
+#[test]
+fn sqlite_unique_violations_are_detected() {
+    use diesel::result::DatabaseErrorKind;
+
+    let connection = connection();
+    match connection.execute("INSERT INTO users (id, name) VALUES (1, 'Jim')") {
+        Err(result::Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => {},
+        Err(e) => panic!("Expected a unique violation, got {}", e),
+        Ok(_) => panic!("Expected a unique violation, got Ok"),
+    }
+}
+
+#[test]
+fn sqlite_queries_can_be_combined() {
+    use self::users::dsl::*;
+
+    let connection = connection();
+    connection.execute("INSERT INTO users (name) VALUES ('Jim')").unwrap();
+    let names = users.select(name).filter(id.lt(3))
+        .union(users.select(name).filter(id.gt(1)))
+        .order(name.desc())
+        .limit(2)
+        .load(&connection)
+        .map(Iterator::collect);
+
+    assert_eq!(Ok(vec!["Tess".to_string(), "Sean".to_string()]), names);
+    let not_sean = users.select(name).except(users.select(name).filter(id.eq(1)))
+        .order(name)
+        .load(&connection)
+        .map(Iterator::collect);
+    assert_eq!(Ok(vec!["Jim".to_string(), "Tess".to_string()]), not_sean);
+}
@@ -0,0 +1,116 @@
+use schema::*;
+use diesel::*;
+use diesel::expression::dsl::*;
+use diesel::query_builder::pg_sql_with_binds;
+
+fn connection_with_users_and_posts() -> PgConnection {
+    let connection = connection_with_sean_and_tess_in_users_table();
+    connection.execute("INSERT INTO users (id, name) VALUES (3, 'Jim')").unwrap();
+    connection.execute("INSERT INTO posts (user_id, title) VALUES
+        (1, 'Hello'), (1, 'World'), (2, 'Hi')").unwrap();
+    connection
+}
+
+#[test]
+fn eq_any_with_a_subquery() {
+    let connection = connection_with_users_and_posts();
+    let authors = posts::table.select(posts::user_id).filter(posts::title.ne("Hi"));
+    let names = users::table.select(users::name)
+        .filter(users::id.eq_any(authors))
+        .load(&connection).unwrap()
+        .collect::<Vec<String>>();
+
+    assert_eq!(vec!["Sean".to_string()], names);
+}
+
+#[test]
+fn eq_any_with_a_vec() {
+    use schema::users::dsl::*;
+
+    let connection = connection_with_users_and_posts();
+    let names = users.select(name)
+        .filter(id.eq_any(vec![2, 3]))
+        .order(id)
+        .load(&connection).unwrap()
+        .collect::<Vec<String>>();
+
+    assert_eq!(vec!["Tess".to_string(), "Jim".to_string()], names);
+}
+
+#[test]
+fn exists_with_a_correlated_subquery() {
+    let connection = connection_with_users_and_posts();
+    let posts_by_user = posts::table.filter(posts::user_id.eq(outer(users::id)));
+    let names = users::table.select(users::name)
+        .filter(exists(posts_by_user))
+        .order(users::id)
+        .load(&connection).unwrap()
+        .collect::<Vec<String>>();
+
+    assert_eq!(vec!["Sean".to_string(), "Tess".to_string()], names);
+}
+
+#[test]
+fn not_exists_with_a_correlated_subquery() {
+    let connection = connection_with_users_and_posts();
+    let posts_by_user = posts::table.filter(posts::user_id.eq(outer(users::id)));
+    let names = users::table.select(users::name)
+        .filter(not_exists(posts_by_user))
+        .load(&connection).unwrap()
+        .collect::<Vec<String>>();
+
+    assert_eq!(vec!["Jim".to_string()], names);
+}
+
+#[test]
+fn scalar_subqueries_can_be_selected() {
+    let connection = connection_with_users_and_posts();
+    let post_count = posts::table.select(count_star())
+        .filter(posts::user_id.eq(outer(users::id)))
+        .single_value();
+    let first_title = posts::table.select(posts::title)
+        .filter(posts::user_id.eq(outer(users::id)))
+        .order(posts::title)
+        .single_value();
+    let data = users::table.select((users::name, post_count, first_title))
+        .order(users::id)
+        .load(&connection).unwrap()
+        .collect::<Vec<(String, Option<i64>, Option<String>)>>();
+
+    let expected = vec![
+        ("Sean".to_string(), Some(2), Some("Hello".to_string())),
+        ("Tess".to_string(), Some(1), Some("Hi".to_string())),
+        ("Jim".to_string(), Some(0), None),
+    ];
+    assert_eq!(expected, data);
+}
+
+#[test]
+fn scalar_subqueries_can_be_compared() {
+    let connection = connection_with_users_and_posts();
+    let latest_author = posts::table.select(posts::user_id)
+        .order(posts::id.desc())
+        .single_value();
+    let names = users::table.select(users::name)
+        .filter(users::id.nullable().eq(latest_author))
+        .load(&connection).unwrap()
+        .collect::<Vec<String>>();
+
+    assert_eq!(vec!["Tess".to_string()], names);
+}
+
+#[test]
+fn subqueries_are_wrapped_in_parentheses() {
+    let posts_by_user = posts::table.select(posts::id)
+        .filter(posts::user_id.eq(outer(users::id)));
+    let query = users::table.select(users::id).filter(exists(posts_by_user));
+    let expected = "SELECT \"users\".\"id\" FROM \"users\" WHERE EXISTS (\
+                    SELECT \"posts\".\"id\" FROM \"posts\" \
+                    WHERE \"posts\".\"user_id\" = \"users\".\"id\")";
+    assert_eq!(expected, pg_sql_with_binds(&query).unwrap());
+
+    let query = users::table.select(posts::table.select(posts::title).single_value());
+    let expected = "SELECT (SELECT \"posts\".\"title\" FROM \"posts\" LIMIT 1::int8) \
+                    FROM \"users\"";
+    assert_eq!(expected, pg_sql_with_binds(&query).unwrap());
+}
@@ -0,0 +1,87 @@
+use schema::*;
+use diesel::*;
+use diesel::expression::dsl::sql;
+use diesel::result::{Error, DatabaseErrorKind};
+use diesel::types::{Bool, Integer, Text};
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn statements_which_exceed_the_statement_timeout_are_cancelled() {
+    let connection = connection_without_transaction();
+    connection.set_statement_timeout(Some(Duration::from_millis(50))).unwrap();
+
+    assert_query_canceled(connection.execute("SELECT pg_sleep(5)"));
+    assert_eq!("50ms", statement_timeout(&connection));
+
+    connection.set_statement_timeout(None).unwrap();
+    assert_eq!(Ok(1), connection.execute("SELECT pg_sleep(0.1)"));
+}
+
+#[test]
+fn with_statement_timeout_only_applies_to_the_given_function() {
+    let connection = connection_without_transaction();
+    connection.set_statement_timeout(Some(Duration::from_secs(60))).unwrap();
+
+    let result = connection.with_statement_timeout(Duration::from_millis(50), || {
+        assert_eq!("50ms", statement_timeout(&connection));
+        connection.execute("SELECT pg_sleep(5)")
+    });
+
+    assert_query_canceled(result);
+    assert_eq!("1min", statement_timeout(&connection));
+}
+
+#[test]
+fn with_statement_timeout_returns_the_result_of_the_function() {
+    let connection = connection();
+
+    let result = connection.with_statement_timeout(Duration::from_secs(5), || {
+        connection.execute("SELECT 1")
+    });
+
+    assert_eq!(Ok(1), result);
+    assert_eq!("0", statement_timeout(&connection));
+}
+
+#[test]
+fn queries_can_be_cancelled_from_another_thread() {
+    let connection = connection_without_transaction();
+    let cancel_handle = connection.cancel_handle().unwrap();
+    let pid = select(sql::<Integer>("pg_backend_pid()")).get_result::<i32>(&connection).unwrap();
+
+    let canceller = thread::spawn(move || {
+        // Wait for the query to start, so the cancel request doesn't arrive
+        // before it
+        let other_connection = connection_without_transaction();
+        let query_is_running = format!("EXISTS (SELECT 1 FROM pg_stat_activity \
+            WHERE pid = {} AND state = 'active' AND query = 'SELECT pg_sleep(5)')", pid);
+        wait_until(|| select(sql::<Bool>(&query_is_running)).get_result::<bool>(&other_connection).unwrap());
+        cancel_handle.cancel().unwrap();
+    });
+    let result = connection.execute("SELECT pg_sleep(5)");
+    canceller.join().unwrap();
+
+    assert_query_canceled(result);
+    assert_eq!(Ok(1), connection.execute("SELECT 1"));
+}
+
+#[test]
+fn cancelling_when_no_query_is_running_does_nothing() {
+    let connection = connection();
+
+    connection.cancel_handle().unwrap().cancel().unwrap();
+
+    assert_eq!(Ok(1), connection.execute("SELECT 1"));
+}
+
+fn assert_query_canceled<T: ::std::fmt::Debug>(result: QueryResult<T>) {
+    match result {
+        Err(Error::DatabaseError(DatabaseErrorKind::QueryCanceled, _)) => {},
+        other => panic!("Expected the query to be cancelled, got {:?}", other),
+    }
+}
+
+fn statement_timeout(connection: &PgConnection) -> String {
+    select(sql::<Text>("current_setting('statement_timeout')")).get_result(connection).unwrap()
+}
@@ -1,5 +1,9 @@
+use std::time::{Duration, Instant};
+
 use schema::*;
 use diesel::*;
+use diesel::connection::RetryPolicy;
+use diesel::expression::dsl::sql;
 
 macro_rules! try_no_coerce {
     ($e:expr) => ({
@@ -111,6 +115,187 @@ fn test_transaction_panics_on_error() {
     });
 }
 
+#[test]
+fn build_transaction_sets_the_isolation_level_and_read_mode() {
+    let connection = connection_without_transaction();
+
+    let result = connection.build_transaction().serializable().read_only().run(|| {
+        assert_eq!(1, connection.get_transaction_depth());
+        Ok::<_, ()>((current_setting(&connection, "transaction_isolation"),
+                    current_setting(&connection, "transaction_read_only")))
+    });
+    assert_eq!(Ok(("serializable".to_string(), "on".to_string())), result);
+    assert_eq!(0, connection.get_transaction_depth());
+
+    let result = connection.build_transaction().repeatable_read().deferrable().run(|| {
+        Ok::<_, ()>((current_setting(&connection, "transaction_isolation"),
+                    current_setting(&connection, "transaction_deferrable")))
+    });
+    assert_eq!(Ok(("repeatable read".to_string(), "on".to_string())), result);
+}
+
+#[test]
+fn read_only_transactions_reject_writes() {
+    let connection = connection_without_transaction();
+    let test_name = "read_only_transactions_reject_writes";
+    setup_test_table(&connection, test_name);
+
+    let result = connection.build_transaction().read_only().run(|| {
+        connection.execute(&format!("INSERT INTO {} DEFAULT VALUES", test_name))
+    });
+    assert!(result.is_err());
+    assert_eq!(0, connection.get_transaction_depth());
+    assert_eq!(0, count_test_table(&connection, test_name));
+
+    drop_test_table(&connection, test_name);
+}
+
+#[test]
+fn build_transaction_uses_savepoints_when_nested() {
+    let connection = connection_without_transaction();
+    let test_name = "build_transaction_uses_savepoints_when_nested";
+    setup_test_table(&connection, test_name);
+    let get_count = || count_test_table(&connection, test_name);
+
+    let _ = connection.build_transaction().serializable().run::<(), (), _>(|| {
+        connection.execute(&format!("INSERT INTO {} DEFAULT VALUES", test_name)).unwrap();
+        let _ = connection.build_transaction().run::<(), (), _>(|| {
+            assert_eq!(2, connection.get_transaction_depth());
+            connection.execute(&format!("INSERT INTO {} DEFAULT VALUES", test_name)).unwrap();
+            assert_eq!(2, get_count());
+            Err(())
+        });
+        assert_eq!(1, get_count());
+        assert_eq!(1, connection.get_transaction_depth());
+        Ok(())
+    });
+    assert_eq!(1, get_count());
+    assert_eq!(0, connection.get_transaction_depth());
+
+    drop_test_table(&connection, test_name);
+}
+
+#[test]
+fn build_transaction_options_cannot_be_set_when_nested() {
+    let connection = connection_without_transaction();
+
+    let result = connection.build_transaction().serializable().run::<_, (), _>(|| {
+        let mut ran = false;
+        let nested = connection.build_transaction().read_only().run::<(), (), _>(|| {
+            ran = true;
+            Ok(())
+        });
+        let expected_error = result::Error::InvalidTransactionOptions;
+        assert_eq!(Err(TransactionError::CouldntCreateTransaction(expected_error)), nested);
+        assert!(!ran);
+        assert_eq!(1, connection.get_transaction_depth());
+        Ok(current_setting(&connection, "transaction_read_only"))
+    });
+    assert_eq!(Ok("off".to_string()), result);
+    assert_eq!(0, connection.get_transaction_depth());
+}
+
+#[test]
+fn transaction_with_retry_retries_serialization_failures() {
+    let conn1 = connection_without_transaction();
+    let conn2 = connection_without_transaction();
+    let test_name = "transaction_with_retry_retries_serialization_failures";
+    conn1.execute(&format!("CREATE TABLE {} (id SERIAL PRIMARY KEY, n INTEGER NOT NULL)", test_name)).unwrap();
+    conn1.execute(&format!("INSERT INTO {} (n) VALUES (0)", test_name)).unwrap();
+    let increment = format!("UPDATE {} SET n = n + 1", test_name);
+
+    let mut attempts = 0;
+    let result = conn1.build_transaction().serializable().run_with_retry(RetryPolicy::default(), || {
+        attempts += 1;
+        try!(conn1.execute(&format!("SELECT * FROM {}", test_name)));
+        if attempts == 1 {
+            conn2.execute(&increment).unwrap();
+        }
+        conn1.execute(&increment)
+    });
+
+    assert_eq!(Ok(1), result);
+    assert_eq!(2, attempts);
+    assert_eq!(0, conn1.get_transaction_depth());
+    let n = select(sql::<types::Integer>(&format!("n FROM {}", test_name))).first(&conn1);
+    assert_eq!(Ok(2), n);
+
+    drop_test_table(&conn1, test_name);
+}
+
+#[test]
+fn transaction_with_retry_gives_up_after_max_attempts() {
+    let connection = connection_without_transaction();
+
+    let mut attempts = 0;
+    let result = connection.transaction_with_retry::<(), _>(RetryPolicy::new(4), || {
+        attempts += 1;
+        Err(serialization_failure())
+    });
+
+    assert_eq!(Err(TransactionError::UserReturnedError(serialization_failure())), result);
+    assert_eq!(4, attempts);
+    assert_eq!(0, connection.get_transaction_depth());
+}
+
+#[test]
+fn transaction_with_retry_caps_the_backoff() {
+    let connection = connection_without_transaction();
+    let policy = RetryPolicy::new(4)
+        .backoff(Duration::new(u64::max_value(), 0))
+        .max_backoff(Duration::from_millis(1));
+
+    let mut attempts = 0;
+    let start = Instant::now();
+    let result = connection.transaction_with_retry::<(), _>(policy, || {
+        attempts += 1;
+        Err(serialization_failure())
+    });
+
+    assert_eq!(Err(TransactionError::UserReturnedError(serialization_failure())), result);
+    assert_eq!(4, attempts);
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn transaction_with_retry_does_not_retry_other_errors() {
+    let connection = connection_without_transaction();
+
+    let mut attempts = 0;
+    let result = connection.transaction_with_retry::<(), _>(RetryPolicy::default(), || {
+        attempts += 1;
+        Err(result::Error::NotFound)
+    });
+
+    assert_eq!(Err(TransactionError::UserReturnedError(result::Error::NotFound)), result);
+    assert_eq!(1, attempts);
+}
+
+#[test]
+fn transaction_with_retry_does_not_retry_nested_transactions() {
+    let connection = connection_without_transaction();
+
+    let mut attempts = 0;
+    let _ = connection.transaction::<(), (), _>(|| {
+        let result = connection.transaction_with_retry::<(), _>(RetryPolicy::default(), || {
+            attempts += 1;
+            Err(serialization_failure())
+        });
+        assert!(result.is_err());
+        assert_eq!(1, connection.get_transaction_depth());
+        Err(())
+    });
+
+    assert_eq!(1, attempts);
+}
+
+fn serialization_failure() -> result::Error {
+    use diesel::result::DatabaseErrorKind;
+
+    let message = "could not serialize access".to_string();
+    result::Error::DatabaseError(DatabaseErrorKind::SerializationFailure, Box::new(message))
+}
+
 fn setup_test_table(connection: &PgConnection, table_name: &str) {
     connection.execute(&format!("CREATE TABLE {} (id SERIAL PRIMARY KEY)", table_name)).unwrap();
 }
@@ -120,7 +305,11 @@ fn drop_test_table(connection: &PgConnection, table_name: &str) {
 }
 
 fn count_test_table(connection: &PgConnection, table_name: &str) -> i64 {
-    use diesel::expression::dsl::sql;
     select(sql::<types::BigInt>(&format!("COUNT(*) FROM {}", table_name)))
         .first(connection).unwrap()
 }
+
+fn current_setting(connection: &PgConnection, setting: &str) -> String {
+    select(sql::<types::VarChar>(&format!("current_setting('{}')", setting)))
+        .first(connection).unwrap()
+}
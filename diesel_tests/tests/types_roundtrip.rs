@@ -31,8 +31,8 @@ pub fn test_type_round_trips<ST, T>(value: T) -> bool where
                 true
             }
         }
-        Err(Error::DatabaseError(msg)) =>
-            &msg == "ERROR:  invalid byte sequence for encoding \"UTF8\": 0x00\n",
+        Err(Error::DatabaseError(_, info)) =>
+            info.message() == "invalid byte sequence for encoding \"UTF8\": 0x00",
         Err(e) => panic!("Query failed: {:?}", e),
     }
 }